@@ -4,9 +4,10 @@ use std::env;
 use std::path::PathBuf;
 
 fn main() {
-    // Rebuild when version or Roboflow key changes so baked-in envs stay in sync.
+    // Rebuild when version or keys change so baked-in envs stay in sync.
     println!("cargo:rerun-if-env-changed=FEEDIE_VERSION");
     println!("cargo:rerun-if-env-changed=FEEDIE_ROBOFLOW_API_KEY");
+    println!("cargo:rerun-if-env-changed=FEEDIE_UPDATE_PUBLIC_KEY");
 
     let version =
         env::var("FEEDIE_VERSION").unwrap_or_else(|_| env::var("CARGO_PKG_VERSION").unwrap());
@@ -15,6 +16,10 @@ fn main() {
     let roboflow = env::var("FEEDIE_ROBOFLOW_API_KEY").unwrap_or_default();
     println!("cargo:rustc-env=ROBOFLOW_API_KEY={roboflow}");
 
+    // Hex-encoded ed25519 key that update manifests and packages are signed with.
+    let update_key = env::var("FEEDIE_UPDATE_PUBLIC_KEY").unwrap_or_default();
+    println!("cargo:rustc-env=UPDATE_PUBLIC_KEY={}", update_key.trim());
+
     #[cfg(target_os = "windows")]
     apply_windows_icon();
 }
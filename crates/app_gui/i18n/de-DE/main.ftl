@@ -9,10 +9,13 @@ status-scanning = Scanne...
 status-done = Fertig.
 status-ready = Bereit.
 status-cache-loaded = Fertig: Cache geladen
+status-cache-other-model = Fertig: Cache geladen, erstellt mit Modell { $model }; erneut scannen, um das aktive Modell zu verwenden
 status-marked-as = Element(e) markiert als
 
 scan-progress = Scannen
 scan-done = Fertig: Tiere gefunden in { $present } von { $total } Bildern ({ $seconds } s)
+scan-megadetector-failed = MegaDetector-Datei konnte nicht gelesen werden
+scan-megadetector-done = MegaDetector: { $empty } Bilder als leer markiert, { $animal } Bilder mit einem Tier klassifiziert.
 scan-failed = Ordner scannen fehlgeschlagen
 model-load-failed = Modell konnte nicht geladen werden
 classification-failed = Klassifizierung fehlgeschlagen
@@ -22,6 +25,9 @@ folder-no-selection = Kein Fotoordner ausgewählt.
 folder-choose = Ordner wählen...
 folder-scan = Scannen
 folder-include-subfolders = Unterordner einbeziehen
+folder-megadetector-import = MegaDetector-Ergebnisse verwenden...
+folder-megadetector-help = Wählt eine MegaDetector-Batch-Ausgabe (JSON, zum Beispiel aus AddaxAI) für diesen Ordner. Der nächste Scan markiert Bilder ohne Tierbox als leer und klassifiziert nur Bilder mit einem Tier.
+folder-megadetector-clear = Ohne MegaDetector-Ergebnisse scannen
 folder-read-error = Ordner konnte nicht gelesen werden
 
 results-no-scan = Es wurde noch kein Scan durchgeführt.
@@ -29,13 +35,23 @@ results-no-results = Keine Ergebnisse verfügbar.
 results-no-frames = Keine Bilder in dieser Ansicht.
 results-loading-thumbnails = Miniaturansichten werden geladen
 results-page = Seite
+similar-computing = Bildmerkmale werden berechnet
+similar-busy = Die Bildmerkmale werden noch berechnet.
+similar-no-features = Für die Auswahl sind keine Bildmerkmale verfügbar.
+similar-failed = Suche nach ähnlichen Bildern fehlgeschlagen
+similar-found = { $count } Bilder nach Ähnlichkeit sortiert
 
 tab-present = Vorhanden
 tab-empty = Leer
 tab-uncertain = Unsicher
+tab-similar = Ähnlich
+results-similar-close = Ansicht ähnlicher Bilder schließen
 
 context-mark-background = Als Hintergrund (Leer) markieren
 context-mark-something = Als Unbestimmte Art (Unsicher) markieren
+context-find-similar = Ähnliche Bilder suchen
+context-write-xmp = XMP-Filialdatei schreiben
+context-write-xmp-help = Schreibt das Label als Stichwörter in eine .xmp-Datei neben dem Originalfoto, für digiKam und Lightroom.
 context-new = Neu...
 context-new-label = Neuen Artnamen eingeben:
 context-new-label-placeholder = Neue Art
@@ -58,6 +74,7 @@ label-none-entered = Kein Label eingegeben.
 label-invalid = Label ist ungültig.
 
 preview-no-classification = Keine Klassifizierung verfügbar.
+preview-model-prediction = Modell sagte { $label } ({ $percent } %)
 preview-prev = < Vorherige
 preview-next = Nächste >
 preview-image-load-failed = Bild konnte nicht geladen werden.
@@ -74,9 +91,54 @@ settings-batch-size-status = Neue Batchgröße wird beim nächsten Scan angewend
 settings-background-labels = Hintergrundlabels
 settings-background-updated = Hintergrundlabels für aktuelle Ergebnisse aktualisiert
 settings-improve-recognition = Erkennung verbessern helfen
-settings-improve-help = Wenn Sie eine Kategorie manuell ändern, laden wir diese Bilder im Hintergrund zu Roboflow hoch.
+settings-improve-help = Wenn Sie eine Kategorie manuell ändern, laden wir diese Bilder im Hintergrund zum gewählten Annotations-Backend hoch.
 settings-roboflow-dataset = Roboflow-Datensatz (z.B. voederhuiscamera)
 settings-roboflow-note = Uploads verwenden einen eingebetteten Roboflow-API-Schlüssel und laufen vollständig im Hintergrund.
+annotation-sink = Annotations-Backend
+annotation-sink-roboflow = Roboflow
+annotation-sink-label-studio = Label Studio
+annotation-sink-http = Eigener HTTP-Endpunkt
+annotation-url = Server-URL
+annotation-label-studio-project = Projekt-ID
+annotation-token = API-Token
+annotation-token-optional = Token (optional)
+annotation-roboflow-key = Eigener API-Schlüssel (für andere Server erforderlich)
+annotation-label-studio-help = Jedes Foto wird zu einer Aufgabe mit bereits ausgewähltem Label. Das Projekt benötigt ein Image namens "image" und Choices namens "choice".
+annotation-http-help = Fotos werden als Multipart-POST mit den Feldern "file", "label" und "filename" gesendet.
+annotation-secrets-note = Tokens und API-Schlüssel werden unverschlüsselt in der Einstellungsdatei im Datenordner von Feedie gespeichert.
+annotation-missing-config = Upload übersprungen: Das Annotations-Backend ist nicht vollständig eingerichtet.
+settings-references = Eigene Beispiele
+settings-references-help = Manuell beschriftete Fotos werden als Beispiele gespeichert, damit Feedie nach einigen Fotos auch Ihre eigenen Labels erkennt.
+settings-references-empty = Noch keine Beispiele gespeichert.
+settings-references-count = { $label }: { $count } Beispiele
+settings-references-count-pending = { $label }: { $count } Beispiele (mindestens 3 nötig)
+settings-references-clear = Alle Beispiele vergessen
+settings-references-cleared = Alle Beispiele wurden entfernt
+settings-personal-model = Persönliches Modell
+settings-personal-model-help = Trainiert die letzte Schicht des Modells mit Ihren eigenen Beispielen auf diesem Computer. Neue Labels werden berücksichtigt.
+settings-model-stock = Standardmodell
+settings-model-personal = Persönliches Modell
+settings-personal-model-train = Persönliches Modell trainieren
+settings-personal-model-examples = Letztes Training: { $train } Trainingsbeispiele, { $holdout } Testbeispiele
+settings-personal-model-accuracy = Genauigkeit auf Testbeispielen: Standard { $stock } % → persönlich { $personal } %
+settings-personal-model-no-holdout = Zu wenige Beispiele für eine Bewertung (mindestens 5 pro Label).
+settings-personal-model-new-labels = Neue Labels
+personal-model-selected = Das persönliche Modell wird beim nächsten Scan verwendet
+stock-model-selected = Das Standardmodell wird beim nächsten Scan verwendet
+personal-training-running = Persönliches Modell wird trainiert
+personal-training-done = Persönliches Modell trainiert
+personal-training-failed = Training fehlgeschlagen
+personal-training-scan-busy = Warten Sie, bis der Scan abgeschlossen ist
+personal-training-no-examples = Noch keine Beispiele: Beschriften Sie zuerst einige Fotos manuell
+settings-evaluation = Modellbewertung
+settings-evaluation-help = Misst, wie gut das aktive Modell Ihre eigenen Fotos erkennt: Wählen Sie einen Ordner mit einem Unterordner pro Label oder verwenden Sie die manuellen Labels dieses Scans. Ein JSON- und HTML-Bericht wird im Ordner „feedie-evaluatie“ gespeichert.
+settings-evaluation-folder = Ordner bewerten…
+settings-evaluation-scan = Manuelle Labels dieses Scans bewerten
+settings-evaluation-open = Bericht öffnen
+evaluation-running = Modell wird bewertet
+evaluation-done = Genauigkeit { $accuracy } % bei { $total } Bildern
+evaluation-failed = Bewertung fehlgeschlagen
+evaluation-no-samples = Keine beschrifteten Bilder zur Bewertung gefunden
 settings-versions = Versionen
 settings-app-version = App-Version
 settings-model-version = Modell- und Artenlisten-Version
@@ -87,6 +149,35 @@ export-include-present = Fotos mit vorhandenen Arten exportieren
 export-include-uncertain = Fotos mit unsicherer Bestimmung exportieren
 export-include-background = Fotos aus Leer (Hintergrund) exportieren
 export-include-csv = Bestimmungsergebnisse als CSV exportieren
+export-include-camtrap = Als Camtrap-DP-Datenpaket exportieren
+export-camtrap-help = Schreibt deployments.csv, media.csv, observations.csv und datapackage.json (Camera Trap Data Package 1.0) für alle gescannten Fotos, mit den Kamerakoordinaten als Deployment.
+export-package-saved-suffix = ; { $path } gespeichert.
+export-include-dwca = Als Darwin Core Archive (GBIF) exportieren
+export-dwca-help = Schreibt ein Zip mit occurrence.txt, meta.xml und eml.xml: eine MachineObservation pro Foto mit einem Tier, mit wissenschaftlichem Namen, Aufnahmezeit, Kamerakoordinaten und der Angabe, ob Sie das Label geprüft haben.
+export-include-timelapse = Als Timelapse2-CSV exportieren
+export-timelapse-help = Schreibt eine CSV mit File, RelativePath, DateTime, species, count, notes und DeleteFlag für jedes Foto des Scans, zur Prüfung in Timelapse2.
+export-include-megadetector = Als MegaDetector-JSON exportieren
+export-megadetector-help = Schreibt eine MegaDetector-Batch-Ausgabe mit den Boxen und Feedie-Labels jedes Fotos des Scans, für AddaxAI, Timelapse2 und andere Kamerafallen-Programme.
+export-include-ebird = eBird-Checklisten exportieren
+export-ebird-help = Schreibt eine CSV im eBird Record Format (Extended): eine Stationary-Checkliste pro Tag an den Kamerakoordinaten, mit der höchsten Anzahl pro Art auf einem Foto und der Zeit zwischen erstem und letztem Foto als Dauer. Nur Vögel mit bekanntem eBird-Namen werden aufgenommen.
+export-include-xmp = XMP-Stichwörter in exportierte Kopien schreiben
+export-xmp-help = Bettet die Art (übersetzt und wissenschaftlich), die Sicherheit und ob das Label manuell oder vom Modell stammt als dc:subject- und hierarchische Stichwörter ein, für digiKam und Lightroom. Kopien, die kein JPEG sind, erhalten eine .xmp-Filialdatei.
+export-include-records = Fotodatensätze exportieren
+export-records-help = Schreibt jedes Foto des Scans mit relativem Pfad, Aufnahmezeit, Label (kanonisch, Anzeige und wissenschaftlich), Konfidenz, den 3 besten Vorhersagen, Quelle der Entscheidung (Modell, manuell oder Vorfilter), Modellversion, Standort, Besuch und Fehler. Jede Datei nennt die Schemaversion (eine Spalte in CSV, die erste Zeile in JSON Lines), damit Ihre Skripte weiter funktionieren, wenn Spalten hinzukommen.
+xmp-import = Labels aus XMP-Filialdateien wiederherstellen
+xmp-import-help = Liest Feedie-.xmp-Filialdateien neben den gescannten Fotos und übernimmt deren Labels in diesen Scan.
+xmp-import-done = { $restored } Labels aus XMP-Filialdateien wiederhergestellt.
+xmp-sidecars-written = { $written } XMP-Filialdateien geschrieben, { $skipped } übersprungen (kein Label oder Datei eines anderen Programms).
+timelapse-import = Timelapse-CSV importieren…
+timelapse-import-help = Liest eine bearbeitete Timelapse-CSV und speichert geänderte Arten und DeleteFlag-Markierungen als manuelle Labels, zugeordnet über den relativen Pfad.
+timelapse-import-failed = Timelapse-CSV konnte nicht gelesen werden
+timelapse-import-done = Timelapse-Import: { $updated } Fotos neu gelabelt, { $unmatched } Zeilen in diesem Scan nicht gefunden.
+labels-csv-import = Labels aus CSV importieren…
+labels-csv-import-help = Liest eine CSV mit den Spalten file, present und species (etwa den CSV-Export von Feedie) und speichert geänderte Labels als manuelle Labels, zugeordnet über den absoluten oder relativen Pfad.
+labels-csv-import-failed = Label-CSV konnte nicht gelesen werden
+labels-csv-import-done = CSV-Import: { $updated } Fotos neu gelabelt, { $unmatched } Zeilen in diesem Scan nicht gefunden.
+labels-csv-import-unmatched = Nicht gefunden: { $files }.
+labels-csv-import-unknown = Labels, die nicht im aktuellen Modell sind: { $labels }.
 export-select-category = Mindestens eine Kategorie für den Export auswählen.
 export-none = Keine Fotos zum Exportieren.
 export-csv-requires-present = CSV-Export erfordert, dass vorhandene Arten eingeschlossen sind.
@@ -95,7 +186,7 @@ export-none-to = Keine Dateien exportiert nach
 export-photos-to = Foto(s) exportiert nach
 export-csv-saved-suffix = ; CSV gespeichert.
 export-failed = Export fehlgeschlagen
-export-coords-title = Koordinaten für CSV
+export-coords-title = Kamerakoordinaten
 export-coords-prompt = Google-Maps-Koordinaten hier einfügen:
 export-paste-failed = Einfügen fehlgeschlagen
 export-coords-tip-intro = Tipp: öffnen Sie
@@ -105,17 +196,48 @@ export-no-selection = Keine Fotos für den Export ausgewählt.
 export-no-usable-files = Kein Export durchgeführt: keine verwendbaren Dateien gefunden.
 export-copy-failed = Kopieren fehlgeschlagen
 export-create-folder-failed = Ordner konnte nicht erstellt werden
-export-coords-missing = Koordinaten fehlen für CSV-Export
+export-coords-missing = Kamerakoordinaten fehlen für den Export
 export-selection-empty = Keine Dateien entsprechen der aktuellen Auswahl.
 export-csv-open-failed = CSV-Datei konnte nicht geöffnet werden
+dataset-title = Trainingsdatensatz
+dataset-help = Speichert die manuell korrigierten Fotos dieses Scans als Datensatz für ein erneutes Training, pro Art aufgeteilt in train, valid und test.
+dataset-format = Aufbau
+dataset-format-imagefolder = ImageFolder (train/valid/test/<label>/)
+dataset-format-roboflow = Roboflow Multiclass (_classes.csv)
+dataset-include-predictions = Auch Modellvorhersagen mit mindestens { $percent } % Sicherheit aufnehmen
+dataset-export = Trainingsdatensatz exportieren…
+dataset-no-samples = Keine manuell beschrifteten Fotos zum Exportieren.
+dataset-target-not-empty = Der gewählte Ordner enthält bereits einen Ordner train, valid oder test.
+dataset-exported = Datensatz mit { $labels } Label(s) in { $folder } gespeichert: { $train } train, { $valid } valid, { $test } test.
 
 roboflow-no-key = Roboflow-Upload ist aktiviert, aber kein API-Schlüssel ist eingebettet.
+roboflow-custom-host-no-key = Der eingebettete Roboflow-API-Schlüssel wird nur an api.roboflow.com gesendet. Geben Sie für diesen Server einen eigenen API-Schlüssel ein.
 roboflow-no-dataset = Roboflow-Upload übersprungen: Datensatz fehlt.
 roboflow-no-photos = Roboflow-Upload übersprungen: keine Fotos ausgewählt.
 roboflow-uploading = Foto(s) mit manuellen Labels werden hochgeladen...
 roboflow-upload-failed = Hochladen der Foto(s) mit manuellen Labels fehlgeschlagen
 roboflow-uploaded-one = Foto mit manuellem Label hochgeladen.
 roboflow-uploaded-many = Fotos mit manuellen Labels hochgeladen.
+roboflow-upload-retrying = Upload fehlgeschlagen; das Foto bleibt in der Warteschlange und wird automatisch erneut versucht
+upload-queue-empty = Keine Uploads in der Warteschlange.
+upload-queue-status = { $pending } Foto(s) warten auf den Upload, { $uploading } werden gerade hochgeladen.
+upload-queue-failing = { $failing } zuvor fehlgeschlagen; nächster Versuch in { $seconds } s.
+upload-queue-retry = Jetzt erneut versuchen
+upload-queue-clear = Warteschlange leeren
+upload-queue-cleared = Upload-Warteschlange geleert.
+upload-review-title = Vor dem Teilen prüfen
+upload-review-consent = Nur Fotos, die Sie hier bestätigen, werden mit { $destination } geteilt. Standort-, Kamera- und Datumsangaben (EXIF) werden aus der hochgeladenen Kopie entfernt. Entfernen Sie alles, was Personen, Nachbarn oder deren Eigentum zeigt.
+upload-review-empty = Keine Fotos warten auf Prüfung.
+upload-review-share = Teilen
+upload-review-remove = Entfernen
+upload-review-share-all = Alle teilen
+upload-review-remove-all = Alle entfernen
+upload-review-waiting = { $count } Foto(s) warten auf Ihre Prüfung.
+upload-review-open = Prüfen…
+upload-review-queued = { $count } Foto(s) warten vor dem Teilen auf Prüfung (Einstellungen).
+upload-review-history = Geteilte Fotos
+upload-review-history-empty = Bisher wurde nichts geteilt.
+upload-review-open-log = Audit-Protokoll öffnen
 
 updates-title = Updates
 updates-check = Auf Updates prüfen
@@ -128,6 +250,10 @@ updates-app-downloading = App-Update wird heruntergeladen...
 updates-app-installing = App-Update wird installiert...
 updates-app-download-failed = App-Update konnte nicht heruntergeladen werden.
 updates-app-hash-mismatch = Heruntergeladenes Update bestand die Integritätsprüfung nicht.
+updates-app-hash-missing = Das Manifest enthält keine Prüfsumme für dieses Update, daher wurde es nicht heruntergeladen.
+updates-signature-no-key = Dieser Build enthält keinen Update-Schlüssel; Updates können nicht geprüft werden und werden abgelehnt.
+updates-signature-missing = Update abgelehnt: Es ist nicht signiert.
+updates-signature-invalid = Update abgelehnt: Die Signatur ist ungültig. Die Datei wurde möglicherweise verändert.
 updates-app-updater-missing = Update-Hilfsprogramm fehlt.
 updates-app-install-failed = App-Update konnte nicht installiert werden.
 updates-open-download = Downloadseite öffnen
@@ -143,3 +269,48 @@ updates-no-downloads = Keine aktuellen Modell-Downloads.
 updates-download-progress = Modell-Download läuft...
 updates-model-installed = Modell installiert
 updates-download-channel-closed = Download-Kanal geschlossen
+updates-model-staged = Modell { $version } heruntergeladen; prüfen Sie die Änderungen vor der Aktivierung.
+updates-model-activate-failed = Aktivierung des Modells fehlgeschlagen
+updates-model-discarded = Heruntergeladenes Modell verworfen.
+updates-channel = Update-Kanal
+updates-channel-help = Beta erhält Testversionen der App und der Modelle vor allen anderen.
+updates-channel-stable = Stabil
+updates-channel-beta = Beta
+updates-release-notes = Versionshinweise
+updates-model-needs-app = Modell { $version } benötigt eine andere Feedie-Version; aktualisieren Sie die App, um es zu verwenden.
+comparison-start = Mit aktuellem Scan vergleichen
+comparison-show = Vergleich anzeigen
+comparison-running = Modelle werden verglichen
+comparison-failed = Modellvergleich fehlgeschlagen
+comparison-title = Modell { $current } → { $staged }
+comparison-summary = { $changed } von { $total } Vorhersagen ändern sich
+comparison-no-changes = Das neue Modell liefert für diesen Scan dieselben Labels.
+comparison-activate = Neues Modell aktivieren
+comparison-discard = Verwerfen
+settings-installed-models = Installierte Modelle
+settings-installed-models-help = Jede heruntergeladene Modellversion bleibt erhalten. Wechseln Sie zu einer früheren Version zurück, wenn ein Update schlechter funktioniert, oder löschen Sie nicht mehr benötigte Versionen.
+settings-installed-models-none = Keine installierten Modellversionen gefunden.
+models-active = Aktiv
+models-activate = Verwenden
+models-delete = Löschen
+models-selected = Modell { $version } ist jetzt aktiv. Scannen Sie erneut, um die Ergebnisse zu aktualisieren.
+models-select-failed = Modellwechsel fehlgeschlagen
+models-deleted = Modell { $version } gelöscht.
+models-delete-failed = Löschen des Modells fehlgeschlagen
+models-site = Für diesen Ordner verwendet
+site-model = Modell für diesen Standort
+site-model-default = Standard
+site-model-help = Wählen Sie, welches installierte Modell (zum Beispiel ein regionales Paket) diesen Kameraordner scannt. Die Auswahl wird pro Ordner gespeichert.
+packs-title = Regionale Modellpakete
+packs-species = { $count } Arten
+packs-install = Installieren
+packs-installed-label = Installiert
+packs-incompatible = Nicht kompatibel mit dieser App-Version
+packs-downloading = { $name } wird heruntergeladen...
+packs-installed = { $name } installiert. Wählen Sie es im Bereich Fotoordner für einen Ordner aus.
+packs-install-failed = Installation des Modellpakets fehlgeschlagen
+model-import = Modell aus Datei installieren...
+model-import-help = Installieren Sie ein heruntergeladenes Modellpaket (ZIP) auf einem Computer ohne Internetzugang.
+model-import-running = Modellpaket wird installiert...
+model-import-done = Modell { $version } installiert. Wählen Sie es unter Installierte Modelle aus.
+model-import-failed = Modellpaket abgelehnt
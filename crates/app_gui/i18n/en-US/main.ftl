@@ -9,10 +9,13 @@ status-scanning = Scanning...
 status-done = Done.
 status-ready = Ready.
 status-cache-loaded = Done: cache loaded
+status-cache-other-model = Done: cache loaded, produced by model { $model }; scan again to use the active model
 status-marked-as = item(s) marked as
 
 scan-progress = Scanning
 scan-done = Done: animals found in { $present } of { $total } frames ({ $seconds } s)
+scan-megadetector-failed = MegaDetector file could not be read
+scan-megadetector-done = MegaDetector: { $empty } frames marked empty, { $animal } frames with an animal classified.
 scan-failed = Failed to scan folder
 model-load-failed = Failed to load model
 classification-failed = Classification failed
@@ -22,6 +25,9 @@ folder-no-selection = No photo folder selected.
 folder-choose = Choose folder...
 folder-scan = Scan
 folder-include-subfolders = Include subfolders
+folder-megadetector-import = Use MegaDetector results...
+folder-megadetector-help = Picks a MegaDetector batch-output JSON (for example from AddaxAI) for this folder. The next scan marks frames without an animal box as empty and only classifies frames with an animal.
+folder-megadetector-clear = Scan without MegaDetector results
 folder-read-error = Failed to read folder
 
 results-no-scan = No scan has been run yet.
@@ -29,13 +35,23 @@ results-no-results = No results available.
 results-no-frames = No frames to show in this view.
 results-loading-thumbnails = Loading thumbnails
 results-page = Page
+similar-computing = Computing image features
+similar-busy = Image features are still being computed.
+similar-no-features = No image features available for the selection.
+similar-failed = Finding similar images failed
+similar-found = { $count } images ranked by similarity
 
 tab-present = Present
 tab-empty = Empty
 tab-uncertain = Uncertain
+tab-similar = Similar
+results-similar-close = Close the similar images view
 
 context-mark-background = Mark as Background (Empty)
 context-mark-something = Mark as Something sp. (Uncertain)
+context-find-similar = Find similar images
+context-write-xmp = Write XMP sidecar
+context-write-xmp-help = Writes the label as keywords to a .xmp file next to the original photo, for digiKam and Lightroom.
 context-new = New...
 context-new-label = Enter a new species name:
 context-new-label-placeholder = New species
@@ -58,6 +74,7 @@ label-none-entered = No label entered.
 label-invalid = Label is invalid.
 
 preview-no-classification = No classification available.
+preview-model-prediction = model said { $label } ({ $percent }%)
 preview-prev = < Previous
 preview-next = Next >
 preview-image-load-failed = Image could not be loaded.
@@ -74,9 +91,54 @@ settings-batch-size-status = New batch size will be applied on the next scan
 settings-background-labels = Background labels
 settings-background-updated = Background labels updated for current results
 settings-improve-recognition = Help improve recognition
-settings-improve-help = When you manually change a category, we upload those images to Roboflow in the background.
+settings-improve-help = When you manually change a category, we upload those images to the chosen annotation backend in the background.
 settings-roboflow-dataset = Roboflow dataset (e.g. voederhuiscamera)
 settings-roboflow-note = Uploads use an embedded Roboflow API key and run fully in the background.
+annotation-sink = Annotation backend
+annotation-sink-roboflow = Roboflow
+annotation-sink-label-studio = Label Studio
+annotation-sink-http = Own HTTP endpoint
+annotation-url = Server URL
+annotation-label-studio-project = Project ID
+annotation-token = API token
+annotation-token-optional = Token (optional)
+annotation-roboflow-key = Own API key (required for other servers)
+annotation-label-studio-help = Each photo becomes a task with the label pre-selected. The project needs an Image named "image" and Choices named "choice".
+annotation-http-help = Photos are sent as a multipart POST with the fields "file", "label" and "filename".
+annotation-secrets-note = Tokens and API keys are stored unencrypted in the settings file in Feedie's data folder.
+annotation-missing-config = Upload skipped: the annotation backend is not fully configured.
+settings-references = Own examples
+settings-references-help = Manually labelled photos are remembered as examples, so Feedie can also recognise your own labels after a few photos.
+settings-references-empty = No examples saved yet.
+settings-references-count = { $label }: { $count } examples
+settings-references-count-pending = { $label }: { $count } examples (at least 3 needed)
+settings-references-clear = Forget all examples
+settings-references-cleared = All examples were removed
+settings-personal-model = Personal model
+settings-personal-model-help = Trains the last layer of the model on your own examples, on this computer. New labels are included.
+settings-model-stock = Standard model
+settings-model-personal = Personal model
+settings-personal-model-train = Train personal model
+settings-personal-model-examples = Last training: { $train } training examples, { $holdout } test examples
+settings-personal-model-accuracy = Accuracy on test examples: standard { $stock }% → personal { $personal }%
+settings-personal-model-no-holdout = Too few examples to evaluate (at least 5 per label).
+settings-personal-model-new-labels = New labels
+personal-model-selected = The personal model will be used for the next scan
+stock-model-selected = The standard model will be used for the next scan
+personal-training-running = Training personal model
+personal-training-done = Personal model trained
+personal-training-failed = Training failed
+personal-training-scan-busy = Wait until the scan has finished
+personal-training-no-examples = No examples yet: label some photos manually first
+settings-evaluation = Model evaluation
+settings-evaluation-help = Measures how well the active model recognises your own photos: pick a folder with one subfolder per label, or use the manual labels of this scan. A JSON and HTML report is saved in a "feedie-evaluatie" folder.
+settings-evaluation-folder = Evaluate folder…
+settings-evaluation-scan = Evaluate manual labels of this scan
+settings-evaluation-open = Open report
+evaluation-running = Evaluating model
+evaluation-done = Accuracy { $accuracy }% on { $total } images
+evaluation-failed = Evaluation failed
+evaluation-no-samples = No labelled images found to evaluate
 settings-versions = Versions
 settings-app-version = App version
 settings-model-version = Model and species list version
@@ -87,6 +149,35 @@ export-include-present = Export photos with present species
 export-include-uncertain = Export photos with uncertain identification
 export-include-background = Export photos from Empty (background)
 export-include-csv = Export identification results as CSV
+export-include-camtrap = Export as Camtrap DP data package
+export-camtrap-help = Writes deployments.csv, media.csv, observations.csv and datapackage.json (Camera Trap Data Package 1.0) for all scanned photos, with the camera coordinates as deployment.
+export-package-saved-suffix = ; { $path } saved.
+export-include-dwca = Export as Darwin Core Archive (GBIF)
+export-dwca-help = Writes a zip with occurrence.txt, meta.xml and eml.xml: one MachineObservation per photo with an animal, with scientific name, capture time, camera coordinates and whether the label was checked by you.
+export-include-timelapse = Export as Timelapse2 CSV
+export-timelapse-help = Writes a CSV with File, RelativePath, DateTime, species, count, notes and DeleteFlag for every photo of the scan, to review in Timelapse2.
+export-include-megadetector = Export as MegaDetector JSON
+export-megadetector-help = Writes a MegaDetector batch-output file with the boxes and Feedie labels of every photo of the scan, for AddaxAI, Timelapse2 and other camera trap tools.
+export-include-ebird = Export eBird checklists
+export-ebird-help = Writes an eBird Record Format (Extended) CSV: one Stationary checklist per day at the camera coordinates, with the highest count per species on a single photo and the time between the first and last photo as duration. Only birds with a known eBird name are included.
+export-include-xmp = Write XMP keywords into exported copies
+export-xmp-help = Embeds the species (localized and scientific), the confidence and whether the label is manual or from the model as dc:subject and hierarchical keywords, for digiKam and Lightroom. Non-JPEG copies get an .xmp sidecar.
+export-include-records = Export frame records
+export-records-help = Writes every photo of the scan with relative path, capture time, label (canonical, display and scientific), confidence, top 3 predictions, decision source (model, manual or prefilter), model version, site, visit and error. Every file names its schema version (a column in CSV, the first line in JSON Lines) so scripts keep working when columns are added.
+xmp-import = Restore labels from XMP sidecars
+xmp-import-help = Reads Feedie .xmp sidecars next to the scanned photos and restores their labels into this scan.
+xmp-import-done = { $restored } labels restored from XMP sidecars.
+xmp-sidecars-written = { $written } XMP sidecars written, { $skipped } skipped (no label or sidecar from another program).
+timelapse-import = Import Timelapse CSV…
+timelapse-import-help = Reads an edited Timelapse CSV and stores changed species and DeleteFlag marks as manual labels, matched by relative path.
+timelapse-import-failed = Timelapse CSV could not be read
+timelapse-import-done = Timelapse import: { $updated } photos relabelled, { $unmatched } rows not found in this scan.
+labels-csv-import = Import labels from CSV…
+labels-csv-import-help = Reads a CSV with file, present and species columns (such as Feedie's own CSV export) and stores changed labels as manual labels, matched by absolute or relative path.
+labels-csv-import-failed = Label CSV could not be read
+labels-csv-import-done = CSV import: { $updated } photos relabelled, { $unmatched } rows not found in this scan.
+labels-csv-import-unmatched = Not found: { $files }.
+labels-csv-import-unknown = Labels not in the current model: { $labels }.
 export-select-category = Select at least one category to export.
 export-none = No photos to export.
 export-csv-requires-present = CSV export requires including present species.
@@ -95,7 +186,7 @@ export-none-to = No files exported to
 export-photos-to = photo(s) exported to
 export-csv-saved-suffix = ; CSV saved.
 export-failed = Export failed
-export-coords-title = Coordinates for CSV
+export-coords-title = Camera coordinates
 export-coords-prompt = Paste Google Maps coordinates here:
 export-paste-failed = Paste failed
 export-coords-tip-intro = Tip: open
@@ -105,17 +196,48 @@ export-no-selection = No photos selected for export.
 export-no-usable-files = No export performed: no usable files found.
 export-copy-failed = Copy failed
 export-create-folder-failed = Could not create folder
-export-coords-missing = Coordinates missing for CSV export
+export-coords-missing = Camera coordinates missing for export
 export-selection-empty = No files matched the current selection.
 export-csv-open-failed = Could not open CSV file
+dataset-title = Training dataset
+dataset-help = Saves the manually corrected photos of this scan as a dataset for retraining, split per species into train, valid and test.
+dataset-format = Layout
+dataset-format-imagefolder = ImageFolder (train/valid/test/<label>/)
+dataset-format-roboflow = Roboflow multiclass (_classes.csv)
+dataset-include-predictions = Also include model predictions with at least { $percent }% confidence
+dataset-export = Export training dataset…
+dataset-no-samples = No manually labelled photos to export.
+dataset-target-not-empty = The chosen folder already contains a train, valid or test folder.
+dataset-exported = Dataset with { $labels } label(s) saved to { $folder }: { $train } train, { $valid } valid, { $test } test.
 
 roboflow-no-key = Roboflow upload is enabled, but no API key is embedded.
+roboflow-custom-host-no-key = The embedded Roboflow API key is only sent to api.roboflow.com. Enter your own API key for this server.
 roboflow-no-dataset = Roboflow upload skipped: dataset missing.
 roboflow-no-photos = Roboflow upload skipped: no photos selected.
 roboflow-uploading = Photo(s) with manual labels are being uploaded...
 roboflow-upload-failed = Upload of photos with manual labels failed
 roboflow-uploaded-one = Photo with manual label uploaded.
 roboflow-uploaded-many = photos with manual labels uploaded.
+roboflow-upload-retrying = Upload failed; it stays queued and is retried automatically
+upload-queue-empty = No uploads waiting.
+upload-queue-status = { $pending } photo(s) waiting for upload, { $uploading } uploading now.
+upload-queue-failing = { $failing } failed before; next retry in { $seconds } s.
+upload-queue-retry = Retry now
+upload-queue-clear = Clear queue
+upload-queue-cleared = Upload queue cleared.
+upload-review-title = Review before sharing
+upload-review-consent = Only photos you confirm here are shared with { $destination }. Location, camera and date metadata (EXIF) is removed from the uploaded copy. Remove anything that shows people, neighbours or their property.
+upload-review-empty = No photos waiting for review.
+upload-review-share = Share
+upload-review-remove = Remove
+upload-review-share-all = Share all
+upload-review-remove-all = Remove all
+upload-review-waiting = { $count } photo(s) waiting for your review.
+upload-review-open = Review…
+upload-review-queued = { $count } photo(s) waiting for review before sharing (Settings).
+upload-review-history = Shared photos
+upload-review-history-empty = Nothing has been shared yet.
+upload-review-open-log = Open audit log
 
 updates-title = Updates
 updates-check = Check for updates
@@ -128,6 +250,10 @@ updates-app-downloading = Downloading app update...
 updates-app-installing = Installing update...
 updates-app-download-failed = App update download failed.
 updates-app-hash-mismatch = Downloaded update failed integrity check.
+updates-app-hash-missing = The manifest lists no checksum for this update, so it was not downloaded.
+updates-signature-no-key = This build has no update key; updates cannot be verified and are refused.
+updates-signature-missing = Update refused: it is not signed.
+updates-signature-invalid = Update refused: the signature is invalid. The file may have been tampered with.
 updates-app-updater-missing = Updater helper is missing.
 updates-app-install-failed = App update could not be installed.
 updates-open-download = Open download page
@@ -143,3 +269,48 @@ updates-no-downloads = No recent model downloads.
 updates-download-progress = Model download in progress...
 updates-model-installed = Model installed
 updates-download-channel-closed = Download channel closed
+updates-model-staged = Model { $version } downloaded; review the changes before activating it.
+updates-model-activate-failed = Activating the model failed
+updates-model-discarded = Downloaded model discarded.
+updates-channel = Update channel
+updates-channel-help = Beta receives test versions of the app and models before everyone else.
+updates-channel-stable = Stable
+updates-channel-beta = Beta
+updates-release-notes = Release notes
+updates-model-needs-app = Model { $version } needs another Feedie version; update the app to use it.
+comparison-start = Compare with current scan
+comparison-show = Show comparison
+comparison-running = Comparing models
+comparison-failed = Model comparison failed
+comparison-title = Model { $current } → { $staged }
+comparison-summary = { $changed } of { $total } predictions change
+comparison-no-changes = The new model gives the same labels for this scan.
+comparison-activate = Activate new model
+comparison-discard = Discard
+settings-installed-models = Installed models
+settings-installed-models-help = Every downloaded model version is kept. Switch back to an earlier version if an update performs worse, or delete versions you no longer need.
+settings-installed-models-none = No installed model versions found.
+models-active = Active
+models-activate = Use
+models-delete = Delete
+models-selected = Model { $version } is now active. Scan again to update the results.
+models-select-failed = Switching model failed
+models-deleted = Model { $version } deleted.
+models-delete-failed = Deleting model failed
+models-site = Used for this folder
+site-model = Model for this site
+site-model-default = Default
+site-model-help = Choose which installed model (for example a regional pack) scans this camera folder. The choice is remembered per folder.
+packs-title = Regional model packs
+packs-species = { $count } species
+packs-install = Install
+packs-installed-label = Installed
+packs-incompatible = Not compatible with this app version
+packs-downloading = Downloading { $name }...
+packs-installed = { $name } installed. Choose it for a folder in the Photo folder panel.
+packs-install-failed = Installing model pack failed
+model-import = Install model from file...
+model-import-help = Install a downloaded model package (ZIP) on a computer without internet access.
+model-import-running = Installing model package...
+model-import-done = Model { $version } installed. Select it under Installed models.
+model-import-failed = Model package rejected
@@ -9,10 +9,13 @@ status-scanning = Escaneando...
 status-done = Listo.
 status-ready = Listo.
 status-cache-loaded = Listo: caché cargada
+status-cache-other-model = Listo: caché cargada, generada con el modelo { $model }; vuelve a analizar para usar el modelo activo
 status-marked-as = elemento(s) marcado(s) como
 
 scan-progress = Escaneo
 scan-done = Listo: animales encontrados en { $present } de { $total } fotogramas ({ $seconds } s)
+scan-megadetector-failed = No se pudo leer el archivo de MegaDetector
+scan-megadetector-done = MegaDetector: { $empty } fotogramas marcados como vacíos, { $animal } fotogramas con un animal clasificados.
 scan-failed = Error al escanear la carpeta
 model-load-failed = Error al cargar el modelo
 classification-failed = Error de clasificación
@@ -22,6 +25,9 @@ folder-no-selection = No se seleccionó ninguna carpeta de fotos.
 folder-choose = Elegir carpeta...
 folder-scan = Escanear
 folder-include-subfolders = Incluir subcarpetas
+folder-megadetector-import = Usar resultados de MegaDetector...
+folder-megadetector-help = Elige un JSON de salida por lotes de MegaDetector (por ejemplo de AddaxAI) para esta carpeta. El próximo escaneo marca como vacíos los fotogramas sin recuadro de animal y solo clasifica los fotogramas con un animal.
+folder-megadetector-clear = Escanear sin resultados de MegaDetector
 folder-read-error = Error al leer la carpeta
 
 results-no-scan = Aún no se ha ejecutado ningún escaneo.
@@ -29,13 +35,23 @@ results-no-results = No hay resultados disponibles.
 results-no-frames = No hay fotogramas para mostrar en esta vista.
 results-loading-thumbnails = Cargando miniaturas
 results-page = Página
+similar-computing = Calculando características de imagen
+similar-busy = Las características de imagen aún se están calculando.
+similar-no-features = No hay características de imagen disponibles para la selección.
+similar-failed = La búsqueda de imágenes similares falló
+similar-found = { $count } imágenes ordenadas por similitud
 
 tab-present = Presente
 tab-empty = Vacío
 tab-uncertain = Incierto
+tab-similar = Similares
+results-similar-close = Cerrar la vista de imágenes similares
 
 context-mark-background = Marcar como Fondo (Vacío)
 context-mark-something = Marcar como Especie indeterminada (Incierto)
+context-find-similar = Buscar imágenes similares
+context-write-xmp = Escribir archivo XMP
+context-write-xmp-help = Escribe la etiqueta como palabras clave en un archivo .xmp junto a la foto original, para digiKam y Lightroom.
 context-new = Nuevo...
 context-new-label = Introduce un nuevo nombre de especie:
 context-new-label-placeholder = Nueva especie
@@ -58,6 +74,7 @@ label-none-entered = No se introdujo ninguna etiqueta.
 label-invalid = La etiqueta no es válida.
 
 preview-no-classification = No hay clasificación disponible.
+preview-model-prediction = el modelo dijo { $label } ({ $percent } %)
 preview-prev = < Anterior
 preview-next = Siguiente >
 preview-image-load-failed = No se pudo cargar la imagen.
@@ -74,9 +91,54 @@ settings-batch-size-status = El nuevo tamaño del lote se aplicará en el próxi
 settings-background-labels = Etiquetas de fondo
 settings-background-updated = Etiquetas de fondo actualizadas para los resultados actuales
 settings-improve-recognition = Ayuda a mejorar el reconocimiento
-settings-improve-help = Cuando cambias manualmente una categoría, subimos esas imágenes a Roboflow en segundo plano.
+settings-improve-help = Cuando cambias manualmente una categoría, subimos esas imágenes al servicio de anotación elegido en segundo plano.
 settings-roboflow-dataset = Conjunto de datos de Roboflow (p. ej. voederhuiscamera)
 settings-roboflow-note = Las cargas usan una clave API de Roboflow integrada y se ejecutan completamente en segundo plano.
+annotation-sink = Servicio de anotación
+annotation-sink-roboflow = Roboflow
+annotation-sink-label-studio = Label Studio
+annotation-sink-http = Endpoint HTTP propio
+annotation-url = URL del servidor
+annotation-label-studio-project = ID del proyecto
+annotation-token = Token de API
+annotation-token-optional = Token (opcional)
+annotation-roboflow-key = Tu clave API (obligatoria para otros servidores)
+annotation-label-studio-help = Cada foto se convierte en una tarea con la etiqueta ya elegida. El proyecto necesita una Image llamada "image" y Choices llamadas "choice".
+annotation-http-help = Las fotos se envían como POST multipart con los campos "file", "label" y "filename".
+annotation-secrets-note = Los tokens y las claves API se guardan sin cifrar en el archivo de ajustes de la carpeta de datos de Feedie.
+annotation-missing-config = Carga omitida: el servicio de anotación no está configurado por completo.
+settings-references = Ejemplos propios
+settings-references-help = Las fotos etiquetadas manualmente se guardan como ejemplos, para que Feedie reconozca también tus propias etiquetas tras unas pocas fotos.
+settings-references-empty = Aún no hay ejemplos guardados.
+settings-references-count = { $label }: { $count } ejemplos
+settings-references-count-pending = { $label }: { $count } ejemplos (se necesitan al menos 3)
+settings-references-clear = Olvidar todos los ejemplos
+settings-references-cleared = Se eliminaron todos los ejemplos
+settings-personal-model = Modelo personal
+settings-personal-model-help = Entrena la última capa del modelo con tus propios ejemplos, en este ordenador. Se incluyen las etiquetas nuevas.
+settings-model-stock = Modelo estándar
+settings-model-personal = Modelo personal
+settings-personal-model-train = Entrenar modelo personal
+settings-personal-model-examples = Último entrenamiento: { $train } ejemplos de entrenamiento, { $holdout } ejemplos de prueba
+settings-personal-model-accuracy = Precisión en los ejemplos de prueba: estándar { $stock } % → personal { $personal } %
+settings-personal-model-no-holdout = Muy pocos ejemplos para evaluar (al menos 5 por etiqueta).
+settings-personal-model-new-labels = Etiquetas nuevas
+personal-model-selected = El modelo personal se usará en el próximo análisis
+stock-model-selected = El modelo estándar se usará en el próximo análisis
+personal-training-running = Entrenando modelo personal
+personal-training-done = Modelo personal entrenado
+personal-training-failed = El entrenamiento falló
+personal-training-scan-busy = Espera a que termine el análisis
+personal-training-no-examples = Aún no hay ejemplos: etiqueta primero algunas fotos manualmente
+settings-evaluation = Evaluación del modelo
+settings-evaluation-help = Mide lo bien que el modelo activo reconoce tus propias fotos: elige una carpeta con una subcarpeta por etiqueta o usa las etiquetas manuales de este análisis. Se guarda un informe JSON y HTML en una carpeta "feedie-evaluatie".
+settings-evaluation-folder = Evaluar carpeta…
+settings-evaluation-scan = Evaluar etiquetas manuales de este análisis
+settings-evaluation-open = Abrir informe
+evaluation-running = Evaluando modelo
+evaluation-done = Precisión { $accuracy } % en { $total } imágenes
+evaluation-failed = La evaluación falló
+evaluation-no-samples = No se encontraron imágenes etiquetadas para evaluar
 settings-versions = Versiones
 settings-app-version = Versión de la aplicación
 settings-model-version = Versión del modelo y de la lista de especies
@@ -87,6 +149,35 @@ export-include-present = Exportar fotos con especies presentes
 export-include-uncertain = Exportar fotos con identificación incierta
 export-include-background = Exportar fotos de Vacío (fondo)
 export-include-csv = Exportar resultados de identificación como CSV
+export-include-camtrap = Exportar como paquete de datos Camtrap DP
+export-camtrap-help = Escribe deployments.csv, media.csv, observations.csv y datapackage.json (Camera Trap Data Package 1.0) para todas las fotos escaneadas, con las coordenadas de la cámara como despliegue.
+export-package-saved-suffix = ; { $path } guardado.
+export-include-dwca = Exportar como Darwin Core Archive (GBIF)
+export-dwca-help = Escribe un zip con occurrence.txt, meta.xml y eml.xml: una MachineObservation por foto con un animal, con nombre científico, hora de captura, coordenadas de la cámara y si revisaste la etiqueta.
+export-include-timelapse = Exportar como CSV de Timelapse2
+export-timelapse-help = Escribe un CSV con File, RelativePath, DateTime, species, count, notes y DeleteFlag para cada foto del escaneo, para revisarlo en Timelapse2.
+export-include-megadetector = Exportar como JSON de MegaDetector
+export-megadetector-help = Escribe un archivo de salida por lotes de MegaDetector con los recuadros y las etiquetas de Feedie de cada foto del escaneo, para AddaxAI, Timelapse2 y otras herramientas de fototrampeo.
+export-include-ebird = Exportar listas de eBird
+export-ebird-help = Escribe un CSV en eBird Record Format (Extended): una lista Stationary por día en las coordenadas de la cámara, con el recuento máximo por especie en una sola foto y el tiempo entre la primera y la última foto como duración. Solo se incluyen aves con un nombre de eBird conocido.
+export-include-xmp = Escribir palabras clave XMP en las copias exportadas
+export-xmp-help = Incrusta la especie (traducida y científica), la confianza y si la etiqueta es manual o del modelo como palabras clave dc:subject y jerárquicas, para digiKam y Lightroom. Las copias que no son JPEG reciben un archivo .xmp adjunto.
+export-include-records = Exportar registros de fotos
+export-records-help = Escribe cada foto del escaneo con ruta relativa, hora de captura, etiqueta (canónica, visible y científica), confianza, las 3 mejores predicciones, origen de la decisión (modelo, manual o prefiltro), versión del modelo, sitio, visita y error. Cada archivo indica la versión del esquema (una columna en CSV, la primera línea en JSON Lines) para que tus scripts sigan funcionando cuando se añadan columnas.
+xmp-import = Restaurar etiquetas desde archivos XMP
+xmp-import-help = Lee los archivos .xmp de Feedie junto a las fotos escaneadas y restaura sus etiquetas en este escaneo.
+xmp-import-done = { $restored } etiquetas restauradas desde archivos XMP.
+xmp-sidecars-written = { $written } archivos XMP escritos, { $skipped } omitidos (sin etiqueta o archivo de otro programa).
+timelapse-import = Importar CSV de Timelapse…
+timelapse-import-help = Lee un CSV de Timelapse editado y guarda las especies cambiadas y las marcas DeleteFlag como etiquetas manuales, emparejadas por ruta relativa.
+timelapse-import-failed = No se pudo leer el CSV de Timelapse
+timelapse-import-done = Importación de Timelapse: { $updated } fotos reetiquetadas, { $unmatched } filas no encontradas en este escaneo.
+labels-csv-import = Importar etiquetas desde CSV…
+labels-csv-import-help = Lee un CSV con las columnas file, present y species (como la exportación CSV de Feedie) y guarda las etiquetas cambiadas como etiquetas manuales, emparejadas por ruta absoluta o relativa.
+labels-csv-import-failed = No se pudo leer el CSV de etiquetas
+labels-csv-import-done = Importación CSV: { $updated } fotos reetiquetadas, { $unmatched } filas no encontradas en este escaneo.
+labels-csv-import-unmatched = No encontradas: { $files }.
+labels-csv-import-unknown = Etiquetas que no están en el modelo actual: { $labels }.
 export-select-category = Selecciona al menos una categoría para exportar.
 export-none = No hay fotos para exportar.
 export-csv-requires-present = La exportación CSV requiere incluir especies presentes.
@@ -95,7 +186,7 @@ export-none-to = No se exportó ningún archivo a
 export-photos-to = foto(s) exportada(s) a
 export-csv-saved-suffix = ; CSV guardado.
 export-failed = Error de exportación
-export-coords-title = Coordenadas para CSV
+export-coords-title = Coordenadas de la cámara
 export-coords-prompt = Pega aquí las coordenadas de Google Maps:
 export-paste-failed = Error al pegar
 export-coords-tip-intro = Consejo: abre
@@ -105,17 +196,48 @@ export-no-selection = No hay fotos seleccionadas para exportar.
 export-no-usable-files = No se realizó ninguna exportación: no se encontraron archivos utilizables.
 export-copy-failed = Error al copiar
 export-create-folder-failed = No se pudo crear la carpeta
-export-coords-missing = Faltan coordenadas para la exportación CSV
+export-coords-missing = Faltan las coordenadas de la cámara para la exportación
 export-selection-empty = Ningún archivo coincide con la selección actual.
 export-csv-open-failed = No se pudo abrir el archivo CSV
+dataset-title = Conjunto de entrenamiento
+dataset-help = Guarda las fotos corregidas manualmente de este escaneo como conjunto de datos para reentrenar, repartidas por especie en train, valid y test.
+dataset-format = Estructura
+dataset-format-imagefolder = ImageFolder (train/valid/test/<label>/)
+dataset-format-roboflow = Roboflow multiclase (_classes.csv)
+dataset-include-predictions = Incluir también predicciones del modelo con al menos un { $percent } % de confianza
+dataset-export = Exportar conjunto de entrenamiento…
+dataset-no-samples = No hay fotos etiquetadas manualmente para exportar.
+dataset-target-not-empty = La carpeta elegida ya contiene una carpeta train, valid o test.
+dataset-exported = Conjunto con { $labels } etiqueta(s) guardado en { $folder }: { $train } train, { $valid } valid, { $test } test.
 
 roboflow-no-key = La carga de Roboflow está habilitada, pero no hay ninguna clave API integrada.
+roboflow-custom-host-no-key = La clave API de Roboflow integrada solo se envía a api.roboflow.com. Introduce tu propia clave API para este servidor.
 roboflow-no-dataset = Carga de Roboflow omitida: falta el conjunto de datos.
 roboflow-no-photos = Carga de Roboflow omitida: no hay fotos seleccionadas.
 roboflow-uploading = Se están subiendo foto(s) con etiquetas manuales...
 roboflow-upload-failed = Error al subir foto(s) con etiquetas manuales
 roboflow-uploaded-one = Foto con etiqueta manual subida.
 roboflow-uploaded-many = fotos con etiquetas manuales subidas.
+roboflow-upload-retrying = La subida falló; la foto sigue en la cola y se reintentará automáticamente
+upload-queue-empty = No hay subidas pendientes.
+upload-queue-status = { $pending } foto(s) esperando subida, { $uploading } subiéndose ahora.
+upload-queue-failing = { $failing } fallaron antes; próximo intento en { $seconds } s.
+upload-queue-retry = Reintentar ahora
+upload-queue-clear = Vaciar cola
+upload-queue-cleared = Cola de subidas vaciada.
+upload-review-title = Revisar antes de compartir
+upload-review-consent = Solo se comparten con { $destination } las fotos que confirmes aquí. Los metadatos de ubicación, cámara y fecha (EXIF) se eliminan de la copia subida. Quita todo lo que muestre personas, vecinos o su propiedad.
+upload-review-empty = No hay fotos pendientes de revisión.
+upload-review-share = Compartir
+upload-review-remove = Quitar
+upload-review-share-all = Compartir todo
+upload-review-remove-all = Quitar todo
+upload-review-waiting = { $count } foto(s) esperan tu revisión.
+upload-review-open = Revisar…
+upload-review-queued = { $count } foto(s) esperan revisión antes de compartirse (Configuración).
+upload-review-history = Fotos compartidas
+upload-review-history-empty = Todavía no se ha compartido nada.
+upload-review-open-log = Abrir registro de auditoría
 
 updates-title = Actualizaciones
 updates-check = Buscar actualizaciones
@@ -128,6 +250,10 @@ updates-app-downloading = Descargando actualización de la app...
 updates-app-installing = Instalando actualización...
 updates-app-download-failed = No se pudo descargar la actualización de la app.
 updates-app-hash-mismatch = La actualización descargada no pasó la verificación de integridad.
+updates-app-hash-missing = El manifiesto no indica ninguna suma de comprobación para esta actualización, así que no se ha descargado.
+updates-signature-no-key = Esta compilación no contiene clave de actualización; las actualizaciones no se pueden verificar y se rechazan.
+updates-signature-missing = Actualización rechazada: no está firmada.
+updates-signature-invalid = Actualización rechazada: la firma no es válida. Es posible que el archivo se haya modificado.
 updates-app-updater-missing = Falta el actualizador.
 updates-app-install-failed = No se pudo instalar la actualización de la app.
 updates-open-download = Abrir página de descarga
@@ -143,3 +269,48 @@ updates-no-downloads = No hay descargas recientes del modelo.
 updates-download-progress = Descarga del modelo en curso...
 updates-model-installed = Modelo instalado
 updates-download-channel-closed = Canal de descarga cerrado
+updates-model-staged = Modelo { $version } descargado; revisa los cambios antes de activarlo.
+updates-model-activate-failed = La activación del modelo falló
+updates-model-discarded = Modelo descargado descartado.
+updates-channel = Canal de actualización
+updates-channel-help = El canal beta recibe versiones de prueba de la aplicación y los modelos antes que nadie.
+updates-channel-stable = Estable
+updates-channel-beta = Beta
+updates-release-notes = Notas de la versión
+updates-model-needs-app = El modelo { $version } necesita otra versión de Feedie; actualiza la aplicación para usarlo.
+comparison-start = Comparar con el análisis actual
+comparison-show = Mostrar comparación
+comparison-running = Comparando modelos
+comparison-failed = La comparación de modelos falló
+comparison-title = Modelo { $current } → { $staged }
+comparison-summary = Cambian { $changed } de { $total } predicciones
+comparison-no-changes = El nuevo modelo da las mismas etiquetas para este análisis.
+comparison-activate = Activar nuevo modelo
+comparison-discard = Descartar
+settings-installed-models = Modelos instalados
+settings-installed-models-help = Se conserva cada versión de modelo descargada. Vuelve a una versión anterior si una actualización funciona peor, o elimina las versiones que ya no necesites.
+settings-installed-models-none = No se encontraron versiones de modelo instaladas.
+models-active = Activo
+models-activate = Usar
+models-delete = Eliminar
+models-selected = El modelo { $version } está activo. Vuelve a analizar para actualizar los resultados.
+models-select-failed = El cambio de modelo falló
+models-deleted = Modelo { $version } eliminado.
+models-delete-failed = La eliminación del modelo falló
+models-site = Usado para esta carpeta
+site-model = Modelo para este sitio
+site-model-default = Predeterminado
+site-model-help = Elige qué modelo instalado (por ejemplo un paquete regional) analiza esta carpeta de cámara. La elección se recuerda por carpeta.
+packs-title = Paquetes de modelos regionales
+packs-species = { $count } especies
+packs-install = Instalar
+packs-installed-label = Instalado
+packs-incompatible = No compatible con esta versión de la aplicación
+packs-downloading = Descargando { $name }...
+packs-installed = { $name } instalado. Elígelo para una carpeta en el panel Carpeta de fotos.
+packs-install-failed = La instalación del paquete de modelos falló
+model-import = Instalar modelo desde archivo...
+model-import-help = Instala un paquete de modelo descargado (ZIP) en un equipo sin acceso a Internet.
+model-import-running = Instalando paquete de modelo...
+model-import-done = Modelo { $version } instalado. Selecciónalo en Modelos instalados.
+model-import-failed = Paquete de modelo rechazado
@@ -9,10 +9,13 @@ status-scanning = Analyse en cours...
 status-done = Terminé.
 status-ready = Prêt.
 status-cache-loaded = Terminé: cache chargé
+status-cache-other-model = Terminé : cache chargé, produit par le modèle { $model } ; relancez l'analyse pour utiliser le modèle actif
 status-marked-as = élément(s) marqué(s) comme
 
 scan-progress = Analyse
 scan-done = Terminé : animaux trouvés dans { $present } de { $total } images ({ $seconds } s)
+scan-megadetector-failed = Impossible de lire le fichier MegaDetector
+scan-megadetector-done = MegaDetector : { $empty } images marquées vides, { $animal } images avec un animal classées.
 scan-failed = Échec de l'analyse du dossier
 model-load-failed = Échec du chargement du modèle
 classification-failed = Échec de la classification
@@ -22,6 +25,9 @@ folder-no-selection = Aucun dossier photo sélectionné.
 folder-choose = Choisir un dossier...
 folder-scan = Analyser
 folder-include-subfolders = Inclure les sous-dossiers
+folder-megadetector-import = Utiliser des résultats MegaDetector...
+folder-megadetector-help = Choisit un fichier JSON de sortie par lot de MegaDetector (par exemple d'AddaxAI) pour ce dossier. Le prochain scan marque les images sans cadre d'animal comme vides et ne classe que les images avec un animal.
+folder-megadetector-clear = Scanner sans résultats MegaDetector
 folder-read-error = Échec de lecture du dossier
 
 results-no-scan = Aucun scan n'a encore été exécuté.
@@ -29,13 +35,23 @@ results-no-results = Aucun résultat disponible.
 results-no-frames = Aucune image à afficher dans cette vue.
 results-loading-thumbnails = Chargement des miniatures
 results-page = Page
+similar-computing = Calcul des caractéristiques d'image
+similar-busy = Les caractéristiques d'image sont encore en cours de calcul.
+similar-no-features = Aucune caractéristique d'image disponible pour la sélection.
+similar-failed = La recherche d'images similaires a échoué
+similar-found = { $count } images classées par similarité
 
 tab-present = Présent
 tab-empty = Vide
 tab-uncertain = Incertain
+tab-similar = Similaires
+results-similar-close = Fermer la vue des images similaires
 
 context-mark-background = Marquer comme Arrière-plan (Vide)
 context-mark-something = Marquer comme Espèce indéterminée (Incertain)
+context-find-similar = Rechercher des images similaires
+context-write-xmp = Écrire un fichier XMP
+context-write-xmp-help = Écrit l'étiquette comme mots-clés dans un fichier .xmp à côté de la photo d'origine, pour digiKam et Lightroom.
 context-new = Nouveau...
 context-new-label = Entrez un nouveau nom d'espèce :
 context-new-label-placeholder = Nouvelle espèce
@@ -58,6 +74,7 @@ label-none-entered = Aucun label saisi.
 label-invalid = Le label est invalide.
 
 preview-no-classification = Aucune classification disponible.
+preview-model-prediction = le modèle indiquait { $label } ({ $percent } %)
 preview-prev = < Précédent
 preview-next = Suivant >
 preview-image-load-failed = L'image n'a pas pu être chargée.
@@ -74,9 +91,54 @@ settings-batch-size-status = La nouvelle taille de lot sera appliquée lors du p
 settings-background-labels = Labels d'arrière-plan
 settings-background-updated = Labels d'arrière-plan mis à jour pour les résultats actuels
 settings-improve-recognition = Aider à améliorer la reconnaissance
-settings-improve-help = Lorsque vous modifiez manuellement une catégorie, nous téléversons ces images sur Roboflow en arrière-plan.
+settings-improve-help = Lorsque vous modifiez manuellement une catégorie, nous téléversons ces images vers le service d'annotation choisi en arrière-plan.
 settings-roboflow-dataset = Dataset Roboflow (ex. voederhuiscamera)
 settings-roboflow-note = Les envois utilisent une clé API Roboflow intégrée et s'exécutent entièrement en arrière-plan.
+annotation-sink = Service d'annotation
+annotation-sink-roboflow = Roboflow
+annotation-sink-label-studio = Label Studio
+annotation-sink-http = Point de terminaison HTTP personnalisé
+annotation-url = URL du serveur
+annotation-label-studio-project = ID du projet
+annotation-token = Jeton API
+annotation-token-optional = Jeton (facultatif)
+annotation-roboflow-key = Votre clé API (obligatoire pour les autres serveurs)
+annotation-label-studio-help = Chaque photo devient une tâche avec l'étiquette déjà sélectionnée. Le projet doit contenir une Image nommée « image » et des Choices nommés « choice ».
+annotation-http-help = Les photos sont envoyées en POST multipart avec les champs « file », « label » et « filename ».
+annotation-secrets-note = Les jetons et clés API sont enregistrés sans chiffrement dans le fichier de paramètres du dossier de données de Feedie.
+annotation-missing-config = Envoi ignoré : le service d'annotation n'est pas entièrement configuré.
+settings-references = Exemples personnels
+settings-references-help = Les photos étiquetées manuellement sont conservées comme exemples, afin que Feedie reconnaisse aussi vos propres étiquettes après quelques photos.
+settings-references-empty = Aucun exemple enregistré pour l'instant.
+settings-references-count = { $label } : { $count } exemples
+settings-references-count-pending = { $label } : { $count } exemples (au moins 3 nécessaires)
+settings-references-clear = Oublier tous les exemples
+settings-references-cleared = Tous les exemples ont été supprimés
+settings-personal-model = Modèle personnel
+settings-personal-model-help = Entraîne la dernière couche du modèle sur vos propres exemples, sur cet ordinateur. Les nouvelles étiquettes sont incluses.
+settings-model-stock = Modèle standard
+settings-model-personal = Modèle personnel
+settings-personal-model-train = Entraîner le modèle personnel
+settings-personal-model-examples = Dernier entraînement : { $train } exemples d'entraînement, { $holdout } exemples de test
+settings-personal-model-accuracy = Précision sur les exemples de test : standard { $stock } % → personnel { $personal } %
+settings-personal-model-no-holdout = Trop peu d'exemples pour évaluer (au moins 5 par étiquette).
+settings-personal-model-new-labels = Nouvelles étiquettes
+personal-model-selected = Le modèle personnel sera utilisé lors de la prochaine analyse
+stock-model-selected = Le modèle standard sera utilisé lors de la prochaine analyse
+personal-training-running = Entraînement du modèle personnel
+personal-training-done = Modèle personnel entraîné
+personal-training-failed = Échec de l'entraînement
+personal-training-scan-busy = Attendez la fin de l'analyse
+personal-training-no-examples = Aucun exemple : étiquetez d'abord quelques photos manuellement
+settings-evaluation = Évaluation du modèle
+settings-evaluation-help = Mesure la qualité de reconnaissance de vos propres photos par le modèle actif : choisissez un dossier avec un sous-dossier par étiquette, ou utilisez les étiquettes manuelles de cette analyse. Un rapport JSON et HTML est enregistré dans un dossier « feedie-evaluatie ».
+settings-evaluation-folder = Évaluer un dossier…
+settings-evaluation-scan = Évaluer les étiquettes manuelles de cette analyse
+settings-evaluation-open = Ouvrir le rapport
+evaluation-running = Évaluation du modèle
+evaluation-done = Précision { $accuracy } % sur { $total } images
+evaluation-failed = Échec de l'évaluation
+evaluation-no-samples = Aucune image étiquetée à évaluer
 settings-versions = Versions
 settings-app-version = Version de l'application
 settings-model-version = Version du modèle et de la liste d'espèces
@@ -87,6 +149,35 @@ export-include-present = Exporter les photos avec des espèces présentes
 export-include-uncertain = Exporter les photos avec identification incertaine
 export-include-background = Exporter les photos de Vide (arrière-plan)
 export-include-csv = Exporter les résultats d'identification en CSV
+export-include-camtrap = Exporter en paquet de données Camtrap DP
+export-camtrap-help = Écrit deployments.csv, media.csv, observations.csv et datapackage.json (Camera Trap Data Package 1.0) pour toutes les photos analysées, avec les coordonnées de la caméra comme déploiement.
+export-package-saved-suffix = ; { $path } enregistré.
+export-include-dwca = Exporter en Darwin Core Archive (GBIF)
+export-dwca-help = Écrit un zip avec occurrence.txt, meta.xml et eml.xml : une MachineObservation par photo avec un animal, avec le nom scientifique, l'heure de prise de vue, les coordonnées de la caméra et l'indication si vous avez vérifié l'étiquette.
+export-include-timelapse = Exporter en CSV Timelapse2
+export-timelapse-help = Écrit un CSV avec File, RelativePath, DateTime, species, count, notes et DeleteFlag pour chaque photo du scan, à vérifier dans Timelapse2.
+export-include-megadetector = Exporter en JSON MegaDetector
+export-megadetector-help = Écrit un fichier de sortie par lot MegaDetector avec les cadres et les étiquettes Feedie de chaque photo du scan, pour AddaxAI, Timelapse2 et d'autres outils de pièges photographiques.
+export-include-ebird = Exporter des listes eBird
+export-ebird-help = Écrit un CSV au format eBird Record Format (Extended) : une liste Stationary par jour aux coordonnées de la caméra, avec le nombre maximal par espèce sur une seule photo et le temps entre la première et la dernière photo comme durée. Seuls les oiseaux ayant un nom eBird connu sont inclus.
+export-include-xmp = Écrire des mots-clés XMP dans les copies exportées
+export-xmp-help = Intègre l'espèce (traduite et scientifique), la confiance et l'origine manuelle ou modèle de l'étiquette comme mots-clés dc:subject et hiérarchiques, pour digiKam et Lightroom. Les copies non JPEG reçoivent un fichier annexe .xmp.
+export-include-records = Exporter les enregistrements des photos
+export-records-help = Écrit chaque photo du scan avec son chemin relatif, l'heure de prise de vue, l'étiquette (canonique, affichée et scientifique), la confiance, les 3 meilleures prédictions, la source de la décision (modèle, manuelle ou préfiltre), la version du modèle, le site, la visite et l'erreur. Chaque fichier indique la version du schéma (une colonne en CSV, la première ligne en JSON Lines) pour que vos scripts continuent de fonctionner lorsque des colonnes sont ajoutées.
+xmp-import = Restaurer les étiquettes depuis les fichiers XMP
+xmp-import-help = Lit les fichiers annexes .xmp de Feedie à côté des photos scannées et restaure leurs étiquettes dans ce scan.
+xmp-import-done = { $restored } étiquettes restaurées depuis les fichiers XMP.
+xmp-sidecars-written = { $written } fichiers XMP écrits, { $skipped } ignorés (pas d'étiquette ou fichier d'un autre programme).
+timelapse-import = Importer un CSV Timelapse…
+timelapse-import-help = Lit un CSV Timelapse modifié et enregistre les espèces modifiées et les marques DeleteFlag comme étiquettes manuelles, associées par chemin relatif.
+timelapse-import-failed = Le CSV Timelapse n'a pas pu être lu
+timelapse-import-done = Import Timelapse : { $updated } photos réétiquetées, { $unmatched } lignes introuvables dans ce scan.
+labels-csv-import = Importer des étiquettes depuis un CSV…
+labels-csv-import-help = Lit un CSV avec les colonnes file, present et species (comme l'export CSV de Feedie) et enregistre les étiquettes modifiées comme étiquettes manuelles, associées par chemin absolu ou relatif.
+labels-csv-import-failed = Le CSV d'étiquettes n'a pas pu être lu
+labels-csv-import-done = Import CSV : { $updated } photos réétiquetées, { $unmatched } lignes introuvables dans ce scan.
+labels-csv-import-unmatched = Introuvables : { $files }.
+labels-csv-import-unknown = Étiquettes absentes du modèle actuel : { $labels }.
 export-select-category = Sélectionnez au moins une catégorie à exporter.
 export-none = Aucune photo à exporter.
 export-csv-requires-present = L'export CSV nécessite d'inclure les espèces présentes.
@@ -95,7 +186,7 @@ export-none-to = Aucun fichier exporté vers
 export-photos-to = photo(s) exportée(s) vers
 export-csv-saved-suffix = ; CSV enregistré.
 export-failed = Échec de l'exportation
-export-coords-title = Coordonnées pour le CSV
+export-coords-title = Coordonnées de la caméra
 export-coords-prompt = Collez ici les coordonnées Google Maps :
 export-paste-failed = Échec du collage
 export-coords-tip-intro = Astuce : ouvrez
@@ -105,17 +196,48 @@ export-no-selection = Aucune photo sélectionnée pour l'exportation.
 export-no-usable-files = Aucun export effectué : aucun fichier utilisable trouvé.
 export-copy-failed = Échec de la copie
 export-create-folder-failed = Impossible de créer le dossier
-export-coords-missing = Coordonnées manquantes pour l'export CSV
+export-coords-missing = Coordonnées de la caméra manquantes pour l'export
 export-selection-empty = Aucun fichier ne correspond à la sélection actuelle.
 export-csv-open-failed = Impossible d'ouvrir le fichier CSV
+dataset-title = Jeu de données d'entraînement
+dataset-help = Enregistre les photos corrigées manuellement de cette analyse comme jeu de données pour un nouvel entraînement, réparties par espèce entre train, valid et test.
+dataset-format = Structure
+dataset-format-imagefolder = ImageFolder (train/valid/test/<label>/)
+dataset-format-roboflow = Roboflow multiclasse (_classes.csv)
+dataset-include-predictions = Inclure aussi les prédictions du modèle avec au moins { $percent } % de confiance
+dataset-export = Exporter le jeu de données…
+dataset-no-samples = Aucune photo étiquetée manuellement à exporter.
+dataset-target-not-empty = Le dossier choisi contient déjà un dossier train, valid ou test.
+dataset-exported = Jeu de données avec { $labels } étiquette(s) enregistré dans { $folder } : { $train } train, { $valid } valid, { $test } test.
 
 roboflow-no-key = L'envoi Roboflow est activé, mais aucune clé API n'est intégrée.
+roboflow-custom-host-no-key = La clé API Roboflow intégrée n'est envoyée qu'à api.roboflow.com. Saisissez votre propre clé API pour ce serveur.
 roboflow-no-dataset = Envoi Roboflow ignoré : dataset manquant.
 roboflow-no-photos = Envoi Roboflow ignoré : aucune photo sélectionnée.
 roboflow-uploading = Téléversement des photo(s) avec labels manuels...
 roboflow-upload-failed = Échec du téléversement des photo(s) avec labels manuels
 roboflow-uploaded-one = Photo avec label manuel téléversée.
 roboflow-uploaded-many = photos avec labels manuels téléversées.
+roboflow-upload-retrying = Échec du téléversement ; la photo reste en file d'attente et sera réessayée automatiquement
+upload-queue-empty = Aucun téléversement en attente.
+upload-queue-status = { $pending } photo(s) en attente de téléversement, { $uploading } en cours.
+upload-queue-failing = { $failing } en échec précédemment ; prochain essai dans { $seconds } s.
+upload-queue-retry = Réessayer maintenant
+upload-queue-clear = Vider la file d'attente
+upload-queue-cleared = File de téléversement vidée.
+upload-review-title = Vérifier avant le partage
+upload-review-consent = Seules les photos que vous confirmez ici sont partagées avec { $destination }. Les métadonnées de localisation, d'appareil et de date (EXIF) sont retirées de la copie envoyée. Retirez tout ce qui montre des personnes, des voisins ou leur propriété.
+upload-review-empty = Aucune photo en attente de vérification.
+upload-review-share = Partager
+upload-review-remove = Retirer
+upload-review-share-all = Tout partager
+upload-review-remove-all = Tout retirer
+upload-review-waiting = { $count } photo(s) en attente de votre vérification.
+upload-review-open = Vérifier…
+upload-review-queued = { $count } photo(s) en attente de vérification avant le partage (Paramètres).
+upload-review-history = Photos partagées
+upload-review-history-empty = Rien n'a encore été partagé.
+upload-review-open-log = Ouvrir le journal d'audit
 
 updates-title = Mises à jour
 updates-check = Vérifier les mises à jour
@@ -128,6 +250,10 @@ updates-app-downloading = Téléchargement de la mise à jour de l'application..
 updates-app-installing = Installation de la mise à jour de l'application...
 updates-app-download-failed = Échec du téléchargement de la mise à jour.
 updates-app-hash-mismatch = La mise à jour téléchargée a échoué au contrôle d'intégrité.
+updates-app-hash-missing = Le manifeste n'indique aucune somme de contrôle pour cette mise à jour ; elle n'a pas été téléchargée.
+updates-signature-no-key = Cette version ne contient pas de clé de mise à jour ; les mises à jour ne peuvent pas être vérifiées et sont refusées.
+updates-signature-missing = Mise à jour refusée : elle n'est pas signée.
+updates-signature-invalid = Mise à jour refusée : la signature n'est pas valide. Le fichier a peut-être été modifié.
 updates-app-updater-missing = L'outil de mise à jour est manquant.
 updates-app-install-failed = Échec de l'installation de la mise à jour.
 updates-open-download = Ouvrir la page de téléchargement
@@ -143,3 +269,48 @@ updates-no-downloads = Aucun téléchargement récent du modèle.
 updates-download-progress = Téléchargement du modèle en cours...
 updates-model-installed = Modèle installé
 updates-download-channel-closed = Canal de téléchargement fermé
+updates-model-staged = Modèle { $version } téléchargé ; vérifiez les changements avant de l'activer.
+updates-model-activate-failed = L'activation du modèle a échoué
+updates-model-discarded = Modèle téléchargé supprimé.
+updates-channel = Canal de mise à jour
+updates-channel-help = Le canal bêta reçoit les versions de test de l'application et des modèles avant tout le monde.
+updates-channel-stable = Stable
+updates-channel-beta = Bêta
+updates-release-notes = Notes de version
+updates-model-needs-app = Le modèle { $version } nécessite une autre version de Feedie ; mettez l'application à jour pour l'utiliser.
+comparison-start = Comparer avec l'analyse actuelle
+comparison-show = Afficher la comparaison
+comparison-running = Comparaison des modèles
+comparison-failed = La comparaison des modèles a échoué
+comparison-title = Modèle { $current } → { $staged }
+comparison-summary = { $changed } prédictions sur { $total } changent
+comparison-no-changes = Le nouveau modèle donne les mêmes étiquettes pour cette analyse.
+comparison-activate = Activer le nouveau modèle
+comparison-discard = Rejeter
+settings-installed-models = Modèles installés
+settings-installed-models-help = Chaque version de modèle téléchargée est conservée. Revenez à une version antérieure si une mise à jour fonctionne moins bien, ou supprimez les versions dont vous n'avez plus besoin.
+settings-installed-models-none = Aucune version de modèle installée.
+models-active = Actif
+models-activate = Utiliser
+models-delete = Supprimer
+models-selected = Le modèle { $version } est maintenant actif. Relancez l'analyse pour mettre à jour les résultats.
+models-select-failed = Le changement de modèle a échoué
+models-deleted = Modèle { $version } supprimé.
+models-delete-failed = La suppression du modèle a échoué
+models-site = Utilisé pour ce dossier
+site-model = Modèle pour ce site
+site-model-default = Par défaut
+site-model-help = Choisissez quel modèle installé (par exemple un pack régional) analyse ce dossier de caméra. Le choix est mémorisé par dossier.
+packs-title = Packs de modèles régionaux
+packs-species = { $count } espèces
+packs-install = Installer
+packs-installed-label = Installé
+packs-incompatible = Non compatible avec cette version de l'application
+packs-downloading = Téléchargement de { $name }...
+packs-installed = { $name } installé. Choisissez-le pour un dossier dans le panneau Dossier photo.
+packs-install-failed = L'installation du pack de modèles a échoué
+model-import = Installer un modèle depuis un fichier...
+model-import-help = Installez un paquet de modèle téléchargé (ZIP) sur un ordinateur sans accès à Internet.
+model-import-running = Installation du paquet de modèle...
+model-import-done = Modèle { $version } installé. Sélectionnez-le dans Modèles installés.
+model-import-failed = Paquet de modèle refusé
@@ -9,10 +9,13 @@ status-scanning = Bezig met scannen...
 status-done = Gereed.
 status-ready = Klaar.
 status-cache-loaded = Gereed: cache geladen
+status-cache-other-model = Klaar: cache geladen, gemaakt met model { $model }; scan opnieuw om het actieve model te gebruiken
 status-marked-as = kaart(en) gemarkeerd als
 
 scan-progress = Scannen
 scan-done = Gereed: Dieren gevonden in { $present } van { $total } frames ({ $seconds } s)
+scan-megadetector-failed = MegaDetector-bestand kon niet worden gelezen
+scan-megadetector-done = MegaDetector: { $empty } frames als leeg gemarkeerd, { $animal } frames met een dier geclassificeerd.
 scan-failed = Map scannen mislukt
 model-load-failed = Model laden mislukt
 classification-failed = Classificatie mislukt
@@ -22,6 +25,9 @@ folder-no-selection = Geen fotomap geselecteerd.
 folder-choose = Map kiezen...
 folder-scan = Scannen
 folder-include-subfolders = Submappen doorzoeken
+folder-megadetector-import = MegaDetector-resultaten gebruiken...
+folder-megadetector-help = Kiest een MegaDetector-batchbestand (JSON, bijvoorbeeld uit AddaxAI) voor deze map. De volgende scan markeert frames zonder dierenkader als leeg en classificeert alleen frames met een dier.
+folder-megadetector-clear = Scannen zonder MegaDetector-resultaten
 folder-read-error = Fout bij lezen van map
 
 results-no-scan = Nog geen scan uitgevoerd.
@@ -29,13 +35,23 @@ results-no-results = Geen resultaten beschikbaar.
 results-no-frames = Geen frames om te tonen in deze weergave.
 results-loading-thumbnails = Thumbnails laden
 results-page = Pagina
+similar-computing = Beeldkenmerken berekenen
+similar-busy = De beeldkenmerken worden nog berekend.
+similar-no-features = Geen beeldkenmerken beschikbaar voor de selectie.
+similar-failed = Zoeken naar vergelijkbare beelden mislukt
+similar-found = { $count } beelden gerangschikt op gelijkenis
 
 tab-present = Aanwezig
 tab-empty = Leeg
 tab-uncertain = Onzeker
+tab-similar = Vergelijkbaar
+results-similar-close = Sluit de weergave met vergelijkbare beelden
 
 context-mark-background = Markeer als Achtergrond (Leeg)
 context-mark-something = Markeer als Iets sp. (Onzeker)
+context-find-similar = Zoek vergelijkbare beelden
+context-write-xmp = XMP-sidecar schrijven
+context-write-xmp-help = Schrijft het label als trefwoorden naar een .xmp-bestand naast de originele foto, voor digiKam en Lightroom.
 context-new = Nieuw...
 context-new-label = Vul een nieuwe soortnaam in:
 context-new-label-placeholder = Nieuwe soort
@@ -58,6 +74,7 @@ label-none-entered = Geen label ingevuld.
 label-invalid = Label is ongeldig.
 
 preview-no-classification = Geen classificatie beschikbaar.
+preview-model-prediction = model zei { $label } ({ $percent }%)
 preview-prev = < Vorige
 preview-next = Volgende >
 preview-image-load-failed = Afbeelding kon niet geladen worden.
@@ -74,9 +91,54 @@ settings-batch-size-status = Nieuwe batchgrootte wordt toegepast bij volgende sc
 settings-background-labels = Achtergrondlabels
 settings-background-updated = Achtergrondlabels bijgewerkt voor huidige resultaten
 settings-improve-recognition = Help de herkenning te verbeteren
-settings-improve-help = Wanneer je handmatig een categorie wijzigt, uploaden we die afbeeldingen op de achtergrond naar Roboflow.
+settings-improve-help = Wanneer je handmatig een categorie wijzigt, uploaden we die afbeeldingen op de achtergrond naar de gekozen annotatie-backend.
 settings-roboflow-dataset = Roboflow dataset (bijv. voederhuiscamera)
 settings-roboflow-note = Uploads gebruiken een ingebouwde Roboflow API-sleutel en draaien volledig op de achtergrond.
+annotation-sink = Annotatie-backend
+annotation-sink-roboflow = Roboflow
+annotation-sink-label-studio = Label Studio
+annotation-sink-http = Eigen HTTP-endpoint
+annotation-url = Server-URL
+annotation-label-studio-project = Project-ID
+annotation-token = API-token
+annotation-token-optional = Token (optioneel)
+annotation-roboflow-key = Eigen API-sleutel (verplicht voor andere servers)
+annotation-label-studio-help = Elke foto wordt een taak met het label al gekozen. Het project heeft een Image met naam "image" en Choices met naam "choice" nodig.
+annotation-http-help = Foto's worden verstuurd als multipart POST met de velden "file", "label" en "filename".
+annotation-secrets-note = Tokens en API-sleutels worden onversleuteld bewaard in het instellingenbestand in de datamap van Feedie.
+annotation-missing-config = Upload niet uitgevoerd: de annotatie-backend is niet volledig ingesteld.
+settings-references = Eigen voorbeelden
+settings-references-help = Handmatig gelabelde foto's worden als voorbeeld bewaard, zodat Feedie na enkele foto's ook je eigen labels herkent.
+settings-references-empty = Nog geen voorbeelden bewaard.
+settings-references-count = { $label }: { $count } voorbeelden
+settings-references-count-pending = { $label }: { $count } voorbeelden (minstens 3 nodig)
+settings-references-clear = Alle voorbeelden vergeten
+settings-references-cleared = Alle voorbeelden zijn verwijderd
+settings-personal-model = Persoonlijk model
+settings-personal-model-help = Traint de laatste laag van het model op je eigen voorbeelden, op deze computer. Nieuwe labels worden meegenomen.
+settings-model-stock = Standaardmodel
+settings-model-personal = Persoonlijk model
+settings-personal-model-train = Persoonlijk model trainen
+settings-personal-model-examples = Laatste training: { $train } trainingsvoorbeelden, { $holdout } testvoorbeelden
+settings-personal-model-accuracy = Nauwkeurigheid op testvoorbeelden: standaard { $stock }% → persoonlijk { $personal }%
+settings-personal-model-no-holdout = Te weinig voorbeelden om te evalueren (minstens 5 per label).
+settings-personal-model-new-labels = Nieuwe labels
+personal-model-selected = Het persoonlijke model wordt gebruikt bij de volgende scan
+stock-model-selected = Het standaardmodel wordt gebruikt bij de volgende scan
+personal-training-running = Persoonlijk model trainen
+personal-training-done = Persoonlijk model getraind
+personal-training-failed = Training mislukt
+personal-training-scan-busy = Wacht tot de scan klaar is
+personal-training-no-examples = Nog geen voorbeelden: label eerst enkele foto's manueel
+settings-evaluation = Modelevaluatie
+settings-evaluation-help = Meet hoe goed het actieve model je eigen foto's herkent: kies een map met per label een submap, of gebruik de manuele labels van deze scan. Een JSON- en HTML-rapport wordt bewaard in een map "feedie-evaluatie".
+settings-evaluation-folder = Map evalueren…
+settings-evaluation-scan = Manuele labels van deze scan evalueren
+settings-evaluation-open = Rapport openen
+evaluation-running = Model evalueren
+evaluation-done = Nauwkeurigheid { $accuracy }% op { $total } beelden
+evaluation-failed = Evaluatie mislukt
+evaluation-no-samples = Geen gelabelde beelden gevonden om te evalueren
 settings-versions = Versies
 settings-app-version = App versie
 settings-model-version = Herkenningsmodel en soortenlijstversie
@@ -87,6 +149,35 @@ export-include-present = Exporteer foto's met aanwezige soorten
 export-include-uncertain = Exporteer foto's met onzekere identificatie
 export-include-background = Exporteer foto's uit Leeg (achtergrond)
 export-include-csv = Exporteer identificatieresultaten als CSV bestand
+export-include-camtrap = Exporteren als Camtrap DP-datapakket
+export-camtrap-help = Schrijft deployments.csv, media.csv, observations.csv en datapackage.json (Camera Trap Data Package 1.0) voor alle gescande foto's, met de cameracoördinaten als deployment.
+export-package-saved-suffix = ; { $path } opgeslagen.
+export-include-dwca = Exporteren als Darwin Core Archive (GBIF)
+export-dwca-help = Schrijft een zip met occurrence.txt, meta.xml en eml.xml: één MachineObservation per foto met een dier, met wetenschappelijke naam, opnametijd, cameracoördinaten en of je het label zelf hebt gecontroleerd.
+export-include-timelapse = Exporteren als Timelapse2-CSV
+export-timelapse-help = Schrijft een CSV met File, RelativePath, DateTime, species, count, notes en DeleteFlag voor elke foto van de scan, om in Timelapse2 te beoordelen.
+export-include-megadetector = Exporteren als MegaDetector-JSON
+export-megadetector-help = Schrijft een MegaDetector-batchbestand met de kaders en Feedie-labels van elke foto van de scan, voor AddaxAI, Timelapse2 en andere cameravalprogramma's.
+export-include-ebird = eBird-checklists exporteren
+export-ebird-help = Schrijft een CSV in eBird Record Format (Extended): één Stationary-checklist per dag op de cameracoördinaten, met het hoogste aantal per soort op één foto en de tijd tussen de eerste en laatste foto als duur. Alleen vogels met een bekende eBird-naam worden opgenomen.
+export-include-xmp = XMP-trefwoorden in geëxporteerde kopieën schrijven
+export-xmp-help = Voegt de soort (vertaald en wetenschappelijk), de zekerheid en of het label handmatig of van het model is toe als dc:subject- en hiërarchische trefwoorden, voor digiKam en Lightroom. Kopieën die geen JPEG zijn krijgen een .xmp-sidecar.
+export-include-records = Fotorecords exporteren
+export-records-help = Schrijft elke foto van de scan met relatief pad, opnametijd, label (canoniek, weergave en wetenschappelijk), zekerheid, top 3 voorspellingen, bron van de beslissing (model, handmatig of voorfilter), modelversie, locatie, bezoek en fout. Elk bestand noemt de schemaversie (een kolom in CSV, de eerste regel in JSON Lines), zodat scripts blijven werken wanneer er kolommen bijkomen.
+xmp-import = Labels herstellen uit XMP-sidecars
+xmp-import-help = Leest Feedie-.xmp-sidecars naast de gescande foto's en zet hun labels terug in deze scan.
+xmp-import-done = { $restored } labels hersteld uit XMP-sidecars.
+xmp-sidecars-written = { $written } XMP-sidecars geschreven, { $skipped } overgeslagen (geen label of sidecar van een ander programma).
+timelapse-import = Timelapse-CSV importeren…
+timelapse-import-help = Leest een bewerkte Timelapse-CSV en slaat gewijzigde soorten en DeleteFlag-markeringen op als handmatige labels, gekoppeld op relatief pad.
+timelapse-import-failed = Timelapse-CSV kon niet gelezen worden
+timelapse-import-done = Timelapse-import: { $updated } foto's opnieuw gelabeld, { $unmatched } rijen niet gevonden in deze scan.
+labels-csv-import = Labels importeren uit CSV…
+labels-csv-import-help = Leest een CSV met de kolommen file, present en species (zoals Feedie's eigen CSV-export) en slaat gewijzigde labels op als handmatige labels, gekoppeld op absoluut of relatief pad.
+labels-csv-import-failed = Label-CSV kon niet gelezen worden
+labels-csv-import-done = CSV-import: { $updated } foto's opnieuw gelabeld, { $unmatched } rijen niet gevonden in deze scan.
+labels-csv-import-unmatched = Niet gevonden: { $files }.
+labels-csv-import-unknown = Labels die niet in het huidige model zitten: { $labels }.
 export-select-category = Selecteer minstens een categorie om te exporteren.
 export-none = Geen foto's om te exporteren.
 export-csv-requires-present = CSV export vereist dat 'aanwezige soorten' wordt meegekopieerd.
@@ -95,7 +186,7 @@ export-none-to = Geen bestanden geexporteerd in
 export-photos-to = foto('s) geexporteerd naar
 export-csv-saved-suffix = ; CSV opgeslagen.
 export-failed = Exporteren mislukt
-export-coords-title = Coordinaten voor CSV
+export-coords-title = Cameracoördinaten
 export-coords-prompt = Plak hier de Google Maps coordinaten:
 export-paste-failed = Plakken mislukt
 export-coords-tip-intro = Tip: open
@@ -105,17 +196,48 @@ export-no-selection = Geen foto's geselecteerd voor export.
 export-no-usable-files = Geen export uitgevoerd: geen bruikbare bestanden gevonden.
 export-copy-failed = Kopieren mislukt
 export-create-folder-failed = Kon map niet aanmaken
-export-coords-missing = Coordinaten ontbreken voor CSV-export
+export-coords-missing = Cameracoördinaten ontbreken voor export
 export-selection-empty = Geen bestanden voldeden aan de huidige selectie.
 export-csv-open-failed = Kon CSV-bestand niet openen
+dataset-title = Trainingsdataset
+dataset-help = Bewaart de handmatig gecorrigeerde foto's van deze scan als dataset om opnieuw te trainen, per soort verdeeld over train, valid en test.
+dataset-format = Indeling
+dataset-format-imagefolder = ImageFolder (train/valid/test/<label>/)
+dataset-format-roboflow = Roboflow multiclass (_classes.csv)
+dataset-include-predictions = Ook modelvoorspellingen met minstens { $percent }% zekerheid meenemen
+dataset-export = Trainingsdataset exporteren…
+dataset-no-samples = Geen handmatig gelabelde foto's om te exporteren.
+dataset-target-not-empty = De gekozen map bevat al een map train, valid of test.
+dataset-exported = Dataset met { $labels } label(s) bewaard in { $folder }: { $train } train, { $valid } valid, { $test } test.
 
 roboflow-no-key = Roboflow upload staat aan, maar er is geen API-sleutel ingebouwd.
+roboflow-custom-host-no-key = De ingebouwde Roboflow API-sleutel gaat alleen naar api.roboflow.com. Vul een eigen API-sleutel in voor deze server.
 roboflow-no-dataset = Roboflow upload niet uitgevoerd: dataset ontbreekt.
 roboflow-no-photos = Roboflow upload niet uitgevoerd: geen foto's geselecteerd.
 roboflow-uploading = Foto('s) met manuele identificatie worden geupload...
 roboflow-upload-failed = Upload van foto('s) met manuele identificatie mislukt
 roboflow-uploaded-one = Foto met manuele identificatie geupload.
 roboflow-uploaded-many = foto's met manuele identificatie geupload.
+roboflow-upload-retrying = Upload mislukt; de foto blijft in de wachtrij en wordt automatisch opnieuw geprobeerd
+upload-queue-empty = Geen uploads in de wachtrij.
+upload-queue-status = { $pending } foto('s) wachten op upload, { $uploading } worden nu geüpload.
+upload-queue-failing = { $failing } eerder mislukt; volgende poging over { $seconds } s.
+upload-queue-retry = Nu opnieuw proberen
+upload-queue-clear = Wachtrij leegmaken
+upload-queue-cleared = Uploadwachtrij leeggemaakt.
+upload-review-title = Controleren voor het delen
+upload-review-consent = Alleen foto's die je hier bevestigt worden gedeeld met { $destination }. Locatie-, camera- en datumgegevens (EXIF) worden uit de geüploade kopie verwijderd. Verwijder alles waarop mensen, buren of hun eigendom te zien zijn.
+upload-review-empty = Geen foto's die op controle wachten.
+upload-review-share = Delen
+upload-review-remove = Verwijderen
+upload-review-share-all = Alles delen
+upload-review-remove-all = Alles verwijderen
+upload-review-waiting = { $count } foto('s) wachten op je controle.
+upload-review-open = Controleren…
+upload-review-queued = { $count } foto('s) wachten op controle voor het delen (Instellingen).
+upload-review-history = Gedeelde foto's
+upload-review-history-empty = Er is nog niets gedeeld.
+upload-review-open-log = Auditlog openen
 
 updates-title = Updates
 updates-check = Controleer op updates
@@ -128,6 +250,10 @@ updates-app-downloading = App-update wordt gedownload...
 updates-app-installing = App-update wordt geinstalleerd...
 updates-app-download-failed = App-update kon niet worden gedownload.
 updates-app-hash-mismatch = Gedownloade update faalde de integriteitscontrole.
+updates-app-hash-missing = Het manifest vermeldt geen controlesom voor deze update, dus ze werd niet gedownload.
+updates-signature-no-key = Deze build bevat geen updatesleutel; updates kunnen niet worden gecontroleerd en worden geweigerd.
+updates-signature-missing = Update geweigerd: deze is niet ondertekend.
+updates-signature-invalid = Update geweigerd: de handtekening is ongeldig. Het bestand is mogelijk gewijzigd.
 updates-app-updater-missing = Update-hulpprogramma ontbreekt.
 updates-app-install-failed = App-update kon niet worden geinstalleerd.
 updates-open-download = Open downloadpagina
@@ -143,3 +269,48 @@ updates-no-downloads = Geen recente modeldownloads uitgevoerd.
 updates-download-progress = Modeldownload wordt uitgevoerd...
 updates-model-installed = Model geinstalleerd
 updates-download-channel-closed = Downloadkanaal verbroken
+updates-model-staged = Model { $version } gedownload; bekijk de wijzigingen voor je het activeert.
+updates-model-activate-failed = Activeren van het model mislukt
+updates-model-discarded = Gedownload model verwijderd.
+updates-channel = Updatekanaal
+updates-channel-help = Bèta krijgt testversies van de app en de modellen vóór iedereen.
+updates-channel-stable = Stabiel
+updates-channel-beta = Bèta
+updates-release-notes = Release-opmerkingen
+updates-model-needs-app = Model { $version } vereist een andere Feedie-versie; werk de app bij om het te gebruiken.
+comparison-start = Vergelijk met huidige scan
+comparison-show = Toon vergelijking
+comparison-running = Modellen vergelijken
+comparison-failed = Modelvergelijking mislukt
+comparison-title = Model { $current } → { $staged }
+comparison-summary = { $changed } van { $total } voorspellingen wijzigen
+comparison-no-changes = Het nieuwe model geeft dezelfde labels voor deze scan.
+comparison-activate = Nieuw model activeren
+comparison-discard = Verwerpen
+settings-installed-models = Geïnstalleerde modellen
+settings-installed-models-help = Elke gedownloade modelversie blijft bewaard. Schakel terug naar een eerdere versie als een update slechter werkt, of verwijder versies die je niet meer nodig hebt.
+settings-installed-models-none = Geen geïnstalleerde modelversies gevonden.
+models-active = Actief
+models-activate = Gebruiken
+models-delete = Verwijderen
+models-selected = Model { $version } is nu actief. Scan opnieuw om de resultaten bij te werken.
+models-select-failed = Wisselen van model mislukt
+models-deleted = Model { $version } verwijderd.
+models-delete-failed = Verwijderen van model mislukt
+models-site = Gebruikt voor deze map
+site-model = Model voor deze locatie
+site-model-default = Standaard
+site-model-help = Kies welk geïnstalleerd model (bijvoorbeeld een regionaal pakket) deze cameramap scant. De keuze wordt per map onthouden.
+packs-title = Regionale modelpakketten
+packs-species = { $count } soorten
+packs-install = Installeren
+packs-installed-label = Geïnstalleerd
+packs-incompatible = Niet compatibel met deze appversie
+packs-downloading = { $name } downloaden...
+packs-installed = { $name } geïnstalleerd. Kies het voor een map in het paneel Fotomap.
+packs-install-failed = Installeren van modelpakket mislukt
+model-import = Model installeren uit bestand...
+model-import-help = Installeer een gedownload modelpakket (ZIP) op een computer zonder internet.
+model-import-running = Modelpakket installeren...
+model-import-done = Model { $version } geïnstalleerd. Kies het onder Geïnstalleerde modellen.
+model-import-failed = Modelpakket geweigerd
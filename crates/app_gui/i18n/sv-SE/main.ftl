@@ -9,10 +9,13 @@ status-scanning = Skannar...
 status-done = Klar.
 status-ready = Klar.
 status-cache-loaded = Klar: cache laddad
+status-cache-other-model = Klar: cache inläst, skapad med modell { $model }; skanna igen för att använda den aktiva modellen
 status-marked-as = objekt markerade som
 
 scan-progress = Skanning
 scan-done = Klar: djur hittades i { $present } av { $total } bilder ({ $seconds } s)
+scan-megadetector-failed = MegaDetector-filen kunde inte läsas
+scan-megadetector-done = MegaDetector: { $empty } bilder markerade som tomma, { $animal } bilder med ett djur klassificerade.
 scan-failed = Misslyckades att skanna mappen
 model-load-failed = Misslyckades att ladda modellen
 classification-failed = Klassificering misslyckades
@@ -22,6 +25,9 @@ folder-no-selection = Ingen fotomapp vald.
 folder-choose = Välj mapp...
 folder-scan = Skanna
 folder-include-subfolders = Inkludera undermappar
+folder-megadetector-import = Använd MegaDetector-resultat...
+folder-megadetector-help = Väljer en JSON-batchfil från MegaDetector (till exempel från AddaxAI) för den här mappen. Nästa skanning markerar bilder utan djurruta som tomma och klassificerar bara bilder med ett djur.
+folder-megadetector-clear = Skanna utan MegaDetector-resultat
 folder-read-error = Misslyckades att läsa mappen
 
 results-no-scan = Ingen skanning har körts ännu.
@@ -29,13 +35,23 @@ results-no-results = Inga resultat tillgängliga.
 results-no-frames = Inga bilder att visa i den här vyn.
 results-loading-thumbnails = Laddar miniatyrer
 results-page = Sida
+similar-computing = Beräknar bildegenskaper
+similar-busy = Bildegenskaperna beräknas fortfarande.
+similar-no-features = Inga bildegenskaper finns för urvalet.
+similar-failed = Sökningen efter liknande bilder misslyckades
+similar-found = { $count } bilder sorterade efter likhet
 
 tab-present = Närvarande
 tab-empty = Tom
 tab-uncertain = Osäker
+tab-similar = Liknande
+results-similar-close = Stäng vyn med liknande bilder
 
 context-mark-background = Markera som Bakgrund (Tom)
 context-mark-something = Markera som Okänd art (Osäker)
+context-find-similar = Hitta liknande bilder
+context-write-xmp = Skriv XMP-sidofil
+context-write-xmp-help = Skriver etiketten som nyckelord till en .xmp-fil bredvid originalfotot, för digiKam och Lightroom.
 context-new = Ny...
 context-new-label = Ange ett nytt artnamn:
 context-new-label-placeholder = Ny art
@@ -58,6 +74,7 @@ label-none-entered = Ingen etikett angiven.
 label-invalid = Etiketten är ogiltig.
 
 preview-no-classification = Ingen klassificering tillgänglig.
+preview-model-prediction = modellen sa { $label } ({ $percent } %)
 preview-prev = < Föregående
 preview-next = Nästa >
 preview-image-load-failed = Bilden kunde inte laddas.
@@ -74,9 +91,54 @@ settings-batch-size-status = Ny batchstorlek används vid nästa skanning
 settings-background-labels = Bakgrundsetiketter
 settings-background-updated = Bakgrundsetiketter uppdaterade för aktuella resultat
 settings-improve-recognition = Hjälp till att förbättra igenkänningen
-settings-improve-help = När du ändrar en kategori manuellt laddar vi upp bilderna till Roboflow i bakgrunden.
+settings-improve-help = När du ändrar en kategori manuellt laddar vi upp bilderna till den valda annoteringstjänsten i bakgrunden.
 settings-roboflow-dataset = Roboflow-datauppsättning (t.ex. voederhuiscamera)
 settings-roboflow-note = Uppladdningar använder en inbyggd Roboflow-API-nyckel och körs helt i bakgrunden.
+annotation-sink = Annoteringstjänst
+annotation-sink-roboflow = Roboflow
+annotation-sink-label-studio = Label Studio
+annotation-sink-http = Egen HTTP-slutpunkt
+annotation-url = Server-URL
+annotation-label-studio-project = Projekt-ID
+annotation-token = API-token
+annotation-token-optional = Token (valfritt)
+annotation-roboflow-key = Egen API-nyckel (krävs för andra servrar)
+annotation-label-studio-help = Varje foto blir en uppgift med etiketten redan vald. Projektet behöver en Image med namnet "image" och Choices med namnet "choice".
+annotation-http-help = Foton skickas som multipart-POST med fälten "file", "label" och "filename".
+annotation-secrets-note = Token och API-nycklar sparas okrypterade i inställningsfilen i Feedies datamapp.
+annotation-missing-config = Uppladdning hoppades över: annoteringstjänsten är inte helt inställd.
+settings-references = Egna exempel
+settings-references-help = Manuellt märkta foton sparas som exempel, så att Feedie efter några foton även känner igen dina egna etiketter.
+settings-references-empty = Inga exempel sparade ännu.
+settings-references-count = { $label }: { $count } exempel
+settings-references-count-pending = { $label }: { $count } exempel (minst 3 behövs)
+settings-references-clear = Glöm alla exempel
+settings-references-cleared = Alla exempel togs bort
+settings-personal-model = Personlig modell
+settings-personal-model-help = Tränar modellens sista lager på dina egna exempel, på den här datorn. Nya etiketter tas med.
+settings-model-stock = Standardmodell
+settings-model-personal = Personlig modell
+settings-personal-model-train = Träna personlig modell
+settings-personal-model-examples = Senaste träning: { $train } träningsexempel, { $holdout } testexempel
+settings-personal-model-accuracy = Träffsäkerhet på testexempel: standard { $stock } % → personlig { $personal } %
+settings-personal-model-no-holdout = För få exempel för utvärdering (minst 5 per etikett).
+settings-personal-model-new-labels = Nya etiketter
+personal-model-selected = Den personliga modellen används vid nästa skanning
+stock-model-selected = Standardmodellen används vid nästa skanning
+personal-training-running = Tränar personlig modell
+personal-training-done = Personlig modell tränad
+personal-training-failed = Träningen misslyckades
+personal-training-scan-busy = Vänta tills skanningen är klar
+personal-training-no-examples = Inga exempel ännu: märk först några foton manuellt
+settings-evaluation = Modellutvärdering
+settings-evaluation-help = Mäter hur väl den aktiva modellen känner igen dina egna foton: välj en mapp med en undermapp per etikett, eller använd de manuella etiketterna från den här skanningen. En JSON- och HTML-rapport sparas i mappen "feedie-evaluatie".
+settings-evaluation-folder = Utvärdera mapp…
+settings-evaluation-scan = Utvärdera manuella etiketter i den här skanningen
+settings-evaluation-open = Öppna rapport
+evaluation-running = Utvärderar modell
+evaluation-done = Träffsäkerhet { $accuracy } % på { $total } bilder
+evaluation-failed = Utvärderingen misslyckades
+evaluation-no-samples = Inga märkta bilder hittades att utvärdera
 settings-versions = Versioner
 settings-app-version = Appversion
 settings-model-version = Modell- och artlistversion
@@ -87,6 +149,35 @@ export-include-present = Exportera foton med närvarande arter
 export-include-uncertain = Exportera foton med osäker identifiering
 export-include-background = Exportera foton från Tom (bakgrund)
 export-include-csv = Exportera identifieringsresultat som CSV
+export-include-camtrap = Exportera som Camtrap DP-datapaket
+export-camtrap-help = Skriver deployments.csv, media.csv, observations.csv och datapackage.json (Camera Trap Data Package 1.0) för alla skannade foton, med kamerans koordinater som deployment.
+export-package-saved-suffix = ; { $path } sparat.
+export-include-dwca = Exportera som Darwin Core Archive (GBIF)
+export-dwca-help = Skriver en zip med occurrence.txt, meta.xml och eml.xml: en MachineObservation per foto med ett djur, med vetenskapligt namn, tagningstid, kamerans koordinater och om du har kontrollerat etiketten.
+export-include-timelapse = Exportera som Timelapse2-CSV
+export-timelapse-help = Skriver en CSV med File, RelativePath, DateTime, species, count, notes och DeleteFlag för varje foto i skanningen, för granskning i Timelapse2.
+export-include-megadetector = Exportera som MegaDetector-JSON
+export-megadetector-help = Skriver en MegaDetector-batchfil med rutorna och Feedie-etiketterna för varje foto i skanningen, för AddaxAI, Timelapse2 och andra viltkameraprogram.
+export-include-ebird = Exportera eBird-checklistor
+export-ebird-help = Skriver en CSV i eBird Record Format (Extended): en Stationary-checklista per dag vid kamerans koordinater, med det högsta antalet per art på ett enskilt foto och tiden mellan första och sista fotot som varaktighet. Endast fåglar med ett känt eBird-namn tas med.
+export-include-xmp = Skriv XMP-nyckelord i exporterade kopior
+export-xmp-help = Bäddar in arten (översatt och vetenskaplig), säkerheten och om etiketten är manuell eller från modellen som dc:subject- och hierarkiska nyckelord, för digiKam och Lightroom. Kopior som inte är JPEG får en .xmp-sidofil.
+export-include-records = Exportera fotoposter
+export-records-help = Skriver varje foto i skanningen med relativ sökväg, tagningstid, etikett (kanonisk, visning och vetenskaplig), säkerhet, de 3 bästa förutsägelserna, beslutets källa (modell, manuell eller förfilter), modellversion, plats, besök och fel. Varje fil anger schemaversionen (en kolumn i CSV, första raden i JSON Lines) så att skript fortsätter fungera när kolumner läggs till.
+xmp-import = Återställ etiketter från XMP-sidofiler
+xmp-import-help = Läser Feedies .xmp-sidofiler bredvid de skannade fotona och återställer deras etiketter i den här skanningen.
+xmp-import-done = { $restored } etiketter återställda från XMP-sidofiler.
+xmp-sidecars-written = { $written } XMP-sidofiler skrivna, { $skipped } överhoppade (ingen etikett eller sidofil från ett annat program).
+timelapse-import = Importera Timelapse-CSV…
+timelapse-import-help = Läser en redigerad Timelapse-CSV och sparar ändrade arter och DeleteFlag-markeringar som manuella etiketter, matchade på relativ sökväg.
+timelapse-import-failed = Timelapse-CSV:n kunde inte läsas
+timelapse-import-done = Timelapse-import: { $updated } foton ometiketterade, { $unmatched } rader hittades inte i den här skanningen.
+labels-csv-import = Importera etiketter från CSV…
+labels-csv-import-help = Läser en CSV med kolumnerna file, present och species (till exempel Feedies egen CSV-export) och sparar ändrade etiketter som manuella etiketter, matchade på absolut eller relativ sökväg.
+labels-csv-import-failed = Etikett-CSV:n kunde inte läsas
+labels-csv-import-done = CSV-import: { $updated } foton ometiketterade, { $unmatched } rader hittades inte i den här skanningen.
+labels-csv-import-unmatched = Hittades inte: { $files }.
+labels-csv-import-unknown = Etiketter som inte finns i den aktuella modellen: { $labels }.
 export-select-category = Välj minst en kategori att exportera.
 export-none = Inga foton att exportera.
 export-csv-requires-present = CSV-export kräver att närvarande arter inkluderas.
@@ -95,7 +186,7 @@ export-none-to = Inga filer exporterades till
 export-photos-to = foto(n) exporterade till
 export-csv-saved-suffix = ; CSV sparad.
 export-failed = Export misslyckades
-export-coords-title = Koordinater för CSV
+export-coords-title = Kamerans koordinater
 export-coords-prompt = Klistra in Google Maps-koordinater här:
 export-paste-failed = Klistra in misslyckades
 export-coords-tip-intro = Tips: öppna
@@ -105,17 +196,48 @@ export-no-selection = Inga foton valda för export.
 export-no-usable-files = Ingen export genomförd: inga användbara filer hittades.
 export-copy-failed = Kopiering misslyckades
 export-create-folder-failed = Kunde inte skapa mapp
-export-coords-missing = Koordinater saknas för CSV-export
+export-coords-missing = Kamerans koordinater saknas för export
 export-selection-empty = Inga filer matchade den aktuella selektionen.
 export-csv-open-failed = Kunde inte öppna CSV-filen
+dataset-title = Träningsdataset
+dataset-help = Sparar de manuellt korrigerade fotona från denna skanning som dataset för omträning, uppdelade per art i train, valid och test.
+dataset-format = Struktur
+dataset-format-imagefolder = ImageFolder (train/valid/test/<label>/)
+dataset-format-roboflow = Roboflow multiclass (_classes.csv)
+dataset-include-predictions = Ta även med modellens förutsägelser med minst { $percent } % säkerhet
+dataset-export = Exportera träningsdataset…
+dataset-no-samples = Inga manuellt märkta foton att exportera.
+dataset-target-not-empty = Den valda mappen innehåller redan en mapp train, valid eller test.
+dataset-exported = Dataset med { $labels } etikett(er) sparat i { $folder }: { $train } train, { $valid } valid, { $test } test.
 
 roboflow-no-key = Roboflow-uppladdning är aktiverad, men ingen API-nyckel är inbäddad.
+roboflow-custom-host-no-key = Den inbäddade Roboflow-API-nyckeln skickas bara till api.roboflow.com. Ange en egen API-nyckel för den här servern.
 roboflow-no-dataset = Roboflow-uppladdning hoppades över: dataset saknas.
 roboflow-no-photos = Roboflow-uppladdning hoppades över: inga foton valda.
 roboflow-uploading = Foto(n) med manuella etiketter laddas upp...
 roboflow-upload-failed = Uppladdning av foto(n) med manuella etiketter misslyckades
 roboflow-uploaded-one = Foto med manuell etikett uppladdad.
 roboflow-uploaded-many = foton med manuella etiketter uppladdade.
+roboflow-upload-retrying = Uppladdningen misslyckades; fotot stannar i kön och försöks igen automatiskt
+upload-queue-empty = Inga uppladdningar i kö.
+upload-queue-status = { $pending } foto(n) väntar på uppladdning, { $uploading } laddas upp nu.
+upload-queue-failing = { $failing } misslyckades tidigare; nästa försök om { $seconds } s.
+upload-queue-retry = Försök igen nu
+upload-queue-clear = Töm kön
+upload-queue-cleared = Uppladdningskön tömd.
+upload-review-title = Granska innan delning
+upload-review-consent = Endast foton som du bekräftar här delas med { $destination }. Plats-, kamera- och datumuppgifter (EXIF) tas bort från den uppladdade kopian. Ta bort allt som visar personer, grannar eller deras egendom.
+upload-review-empty = Inga foton väntar på granskning.
+upload-review-share = Dela
+upload-review-remove = Ta bort
+upload-review-share-all = Dela alla
+upload-review-remove-all = Ta bort alla
+upload-review-waiting = { $count } foto(n) väntar på din granskning.
+upload-review-open = Granska…
+upload-review-queued = { $count } foto(n) väntar på granskning innan delning (Inställningar).
+upload-review-history = Delade foton
+upload-review-history-empty = Inget har delats ännu.
+upload-review-open-log = Öppna granskningslogg
 
 updates-title = Uppdateringar
 updates-check = Sök efter uppdateringar
@@ -128,6 +250,10 @@ updates-app-downloading = Hämtar appuppdatering...
 updates-app-installing = Installerar uppdatering...
 updates-app-download-failed = Det gick inte att ladda ner appuppdateringen.
 updates-app-hash-mismatch = Den nedladdade uppdateringen klarade inte integritetskontrollen.
+updates-app-hash-missing = Manifestet anger ingen kontrollsumma för den här uppdateringen, så den laddades inte ned.
+updates-signature-no-key = Den här versionen saknar uppdateringsnyckel; uppdateringar kan inte verifieras och avvisas.
+updates-signature-missing = Uppdateringen avvisades: den är inte signerad.
+updates-signature-invalid = Uppdateringen avvisades: signaturen är ogiltig. Filen kan ha ändrats.
 updates-app-updater-missing = Uppdateringsverktyget saknas.
 updates-app-install-failed = Det gick inte att installera appuppdateringen.
 updates-open-download = Öppna nedladdningssidan
@@ -143,3 +269,48 @@ updates-no-downloads = Inga senaste modellnedladdningar.
 updates-download-progress = Modellnedladdning pågår...
 updates-model-installed = Modell installerad
 updates-download-channel-closed = Nedladdningskanal stängd
+updates-model-staged = Modell { $version } hämtad; granska ändringarna innan du aktiverar den.
+updates-model-activate-failed = Aktivering av modellen misslyckades
+updates-model-discarded = Hämtad modell kasserad.
+updates-channel = Uppdateringskanal
+updates-channel-help = Beta får testversioner av appen och modellerna före alla andra.
+updates-channel-stable = Stabil
+updates-channel-beta = Beta
+updates-release-notes = Versionsinformation
+updates-model-needs-app = Modell { $version } kräver en annan Feedie-version; uppdatera appen för att använda den.
+comparison-start = Jämför med aktuell skanning
+comparison-show = Visa jämförelse
+comparison-running = Jämför modeller
+comparison-failed = Modelljämförelsen misslyckades
+comparison-title = Modell { $current } → { $staged }
+comparison-summary = { $changed } av { $total } förutsägelser ändras
+comparison-no-changes = Den nya modellen ger samma etiketter för denna skanning.
+comparison-activate = Aktivera ny modell
+comparison-discard = Kassera
+settings-installed-models = Installerade modeller
+settings-installed-models-help = Varje hämtad modellversion sparas. Växla tillbaka till en tidigare version om en uppdatering fungerar sämre, eller ta bort versioner du inte längre behöver.
+settings-installed-models-none = Inga installerade modellversioner hittades.
+models-active = Aktiv
+models-activate = Använd
+models-delete = Ta bort
+models-selected = Modell { $version } är nu aktiv. Skanna igen för att uppdatera resultaten.
+models-select-failed = Byte av modell misslyckades
+models-deleted = Modell { $version } borttagen.
+models-delete-failed = Borttagning av modellen misslyckades
+models-site = Används för denna mapp
+site-model = Modell för denna plats
+site-model-default = Standard
+site-model-help = Välj vilken installerad modell (till exempel ett regionalt paket) som skannar denna kameramapp. Valet sparas per mapp.
+packs-title = Regionala modellpaket
+packs-species = { $count } arter
+packs-install = Installera
+packs-installed-label = Installerat
+packs-incompatible = Inte kompatibelt med denna appversion
+packs-downloading = Hämtar { $name }...
+packs-installed = { $name } installerat. Välj det för en mapp i panelen Fotomapp.
+packs-install-failed = Installation av modellpaketet misslyckades
+model-import = Installera modell från fil...
+model-import-help = Installera ett hämtat modellpaket (ZIP) på en dator utan internetåtkomst.
+model-import-running = Installerar modellpaket...
+model-import-done = Modell { $version } installerad. Välj den under Installerade modeller.
+model-import-failed = Modellpaketet avvisades
@@ -0,0 +1,461 @@
+//! Annotation backends that receive manually labelled photos.
+//!
+//! Each backend implements [`AnnotationSink`]; which one is used, and where it
+//! lives, is configured in settings through [`AnnotationSettings`].
+
+use crate::roboflow::{ROBOFLOW_API_URL, upload_to_roboflow_at};
+use crate::upload_queue::StatusError;
+use anyhow::{Context, anyhow};
+use reqwest::blocking::{Client, multipart};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// Name of the `<Choices>` tag in the Label Studio labeling config.
+const LABEL_STUDIO_CHOICES: &str = "choice";
+/// Name of the `<Image>` tag in the Label Studio labeling config.
+const LABEL_STUDIO_IMAGE: &str = "image";
+
+/// Receives one photo with its manual label.
+pub(crate) trait AnnotationSink: Send + Sync {
+    /// Sends the photo and its label.
+    ///
+    /// Backends that add the label in a second request store the id of the
+    /// uploaded photo in `remote_id` first. When it is already set, only the
+    /// label is sent, so a retry does not upload the photo twice.
+    fn upload(
+        &self,
+        path: &Path,
+        label: &str,
+        remote_id: &mut Option<String>,
+    ) -> anyhow::Result<()>;
+}
+
+/// Backend that manual corrections are shared with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SinkKind {
+    #[default]
+    Roboflow,
+    LabelStudio,
+    Http,
+}
+
+impl SinkKind {
+    pub(crate) const ALL: [SinkKind; 3] =
+        [SinkKind::Roboflow, SinkKind::LabelStudio, SinkKind::Http];
+
+    pub(crate) fn label_key(self) -> &'static str {
+        match self {
+            SinkKind::Roboflow => "annotation-sink-roboflow",
+            SinkKind::LabelStudio => "annotation-sink-label-studio",
+            SinkKind::Http => "annotation-sink-http",
+        }
+    }
+}
+
+/// Persisted choice and configuration of the annotation backend.
+///
+/// Stored with the other settings in `settings.json`; the tokens and the own
+/// Roboflow key are kept there in plain text, not in the OS keyring.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct AnnotationSettings {
+    pub(crate) kind: SinkKind,
+    pub(crate) roboflow_url: String,
+    pub(crate) roboflow_dataset: String,
+    /// Own Roboflow API key; required when `roboflow_url` is not the
+    /// Roboflow cloud, because the embedded key is only sent there.
+    pub(crate) roboflow_key: String,
+    pub(crate) label_studio_url: String,
+    pub(crate) label_studio_project: String,
+    pub(crate) label_studio_token: String,
+    pub(crate) http_url: String,
+    pub(crate) http_token: String,
+}
+
+impl Default for AnnotationSettings {
+    fn default() -> Self {
+        Self {
+            kind: SinkKind::Roboflow,
+            roboflow_url: ROBOFLOW_API_URL.to_string(),
+            roboflow_dataset: "voederhuiscamera".to_string(),
+            roboflow_key: String::new(),
+            label_studio_url: String::new(),
+            label_studio_project: String::new(),
+            label_studio_token: String::new(),
+            http_url: String::new(),
+            http_token: String::new(),
+        }
+    }
+}
+
+impl AnnotationSettings {
+    /// Where uploads go, for the review window and the audit log.
+    pub(crate) fn destination(&self) -> String {
+        match self.kind {
+            SinkKind::Roboflow => format!(
+                "Roboflow {}/{}",
+                self.roboflow_url.trim().trim_end_matches('/'),
+                self.roboflow_dataset.trim().trim_matches('/')
+            ),
+            SinkKind::LabelStudio => format!(
+                "Label Studio {}/projects/{}",
+                self.label_studio_url.trim().trim_end_matches('/'),
+                self.label_studio_project.trim()
+            ),
+            SinkKind::Http => self.http_url.trim().to_string(),
+        }
+    }
+
+    /// Builds the configured backend, or returns the i18n key of what is
+    /// missing. `roboflow_key` is the API key embedded at build time; it is
+    /// only sent to the Roboflow cloud, other Roboflow servers need their own
+    /// key.
+    pub(crate) fn sink(&self, roboflow_key: &str) -> Result<Box<dyn AnnotationSink>, &'static str> {
+        let required = |value: &str| {
+            let value = value.trim();
+            (!value.is_empty()).then(|| value.to_string())
+        };
+        let missing = "annotation-missing-config";
+        match self.kind {
+            SinkKind::Roboflow => {
+                let base_url =
+                    required(&self.roboflow_url).unwrap_or_else(|| ROBOFLOW_API_URL.to_string());
+                let api_key = match required(&self.roboflow_key) {
+                    Some(own) => own,
+                    None if is_roboflow_cloud(&base_url) => {
+                        required(roboflow_key).ok_or("roboflow-no-key")?
+                    }
+                    None => return Err("roboflow-custom-host-no-key"),
+                };
+                let dataset = required(self.roboflow_dataset.trim_matches('/'))
+                    .ok_or("roboflow-no-dataset")?;
+                Ok(Box::new(RoboflowSink {
+                    base_url,
+                    dataset,
+                    api_key,
+                }))
+            }
+            SinkKind::LabelStudio => Ok(Box::new(LabelStudioSink {
+                base_url: required(&self.label_studio_url).ok_or(missing)?,
+                project: required(&self.label_studio_project).ok_or(missing)?,
+                token: required(&self.label_studio_token).ok_or(missing)?,
+            })),
+            SinkKind::Http => Ok(Box::new(HttpSink {
+                url: required(&self.http_url).ok_or(missing)?,
+                token: required(&self.http_token),
+            })),
+        }
+    }
+}
+
+/// True when `url` points at the Roboflow cloud API over HTTPS.
+fn is_roboflow_cloud(url: &str) -> bool {
+    let (Ok(url), Ok(cloud)) = (
+        reqwest::Url::parse(url),
+        reqwest::Url::parse(ROBOFLOW_API_URL),
+    ) else {
+        return false;
+    };
+    url.scheme() == cloud.scheme()
+        && url.host_str() == cloud.host_str()
+        && url.port_or_known_default() == cloud.port_or_known_default()
+}
+
+/// Roboflow dataset upload plus a classification annotation.
+pub(crate) struct RoboflowSink {
+    base_url: String,
+    dataset: String,
+    api_key: String,
+}
+
+impl AnnotationSink for RoboflowSink {
+    fn upload(
+        &self,
+        path: &Path,
+        label: &str,
+        remote_id: &mut Option<String>,
+    ) -> anyhow::Result<()> {
+        upload_to_roboflow_at(
+            &self.base_url,
+            path,
+            label,
+            &self.dataset,
+            &self.api_key,
+            remote_id,
+        )
+    }
+}
+
+/// Label Studio project; each photo is uploaded through the file import
+/// API and the resulting task gets the label as a pre-filled choice.
+///
+/// The project's labeling config needs `<Image name="image" value="$image"/>`
+/// and `<Choices name="choice" toName="image">`.
+pub(crate) struct LabelStudioSink {
+    base_url: String,
+    project: String,
+    token: String,
+}
+
+impl AnnotationSink for LabelStudioSink {
+    fn upload(
+        &self,
+        path: &Path,
+        label: &str,
+        remote_id: &mut Option<String>,
+    ) -> anyhow::Result<()> {
+        let base_url = self.base_url.trim_end_matches('/');
+        let authorization = format!("Token {}", self.token);
+        let client = client()?;
+        let task_id = match remote_id {
+            Some(id) => id,
+            None => remote_id.insert(self.import(&client, &authorization, path)?),
+        };
+
+        let annotation = serde_json::json!({
+            "result": [{
+                "from_name": LABEL_STUDIO_CHOICES,
+                "to_name": LABEL_STUDIO_IMAGE,
+                "type": "choices",
+                "value": {"choices": [label]},
+            }],
+        });
+        let response = client
+            .post(format!(
+                "{base_url}/api/tasks/{}/annotations/",
+                urlencoding::encode(task_id)
+            ))
+            .header("Authorization", &authorization)
+            .json(&annotation)
+            .send()
+            .context("Label Studio-annotatie mislukt")?;
+        check_status(response, "Label Studio-annotatie")
+    }
+}
+
+impl LabelStudioSink {
+    /// Imports the photo as a new task and returns the task id.
+    fn import(&self, client: &Client, authorization: &str, path: &Path) -> anyhow::Result<String> {
+        let base_url = self.base_url.trim_end_matches('/');
+        let form = multipart::Form::new().file("file", path).with_context(|| {
+            format!("Bestand toevoegen aan upload-formulier: {}", path.display())
+        })?;
+        let import_url = format!(
+            "{base_url}/api/projects/{}/import?return_task_ids=true",
+            urlencoding::encode(&self.project)
+        );
+        let response = client
+            .post(&import_url)
+            .header("Authorization", authorization)
+            .multipart(form)
+            .send()
+            .context("Label Studio-import mislukt")?;
+        let json: serde_json::Value = successful(response, "Label Studio-import")?
+            .json()
+            .context("Importantwoord van Label Studio kon niet gelezen worden")?;
+        let task_id = json["task_ids"][0]
+            .as_u64()
+            .ok_or_else(|| anyhow!("Taak-ID ontbreekt in Label Studio-antwoord: {json}"))?;
+        Ok(task_id.to_string())
+    }
+}
+
+/// Generic endpoint that accepts a multipart POST with `file` and `label`.
+pub(crate) struct HttpSink {
+    url: String,
+    token: Option<String>,
+}
+
+impl AnnotationSink for HttpSink {
+    fn upload(&self, path: &Path, label: &str, _: &mut Option<String>) -> anyhow::Result<()> {
+        let form = multipart::Form::new()
+            .text("label", label.to_string())
+            .text("filename", file_name(path))
+            .file("file", path)
+            .with_context(|| {
+                format!("Bestand toevoegen aan upload-formulier: {}", path.display())
+            })?;
+        let mut request = client()?.post(&self.url).multipart(form);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().context("Upload mislukt")?;
+        check_status(response, "Upload")
+    }
+}
+
+fn client() -> anyhow::Result<Client> {
+    Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .context("HTTP client bouwen")
+}
+
+fn check_status(response: reqwest::blocking::Response, what: &str) -> anyhow::Result<()> {
+    successful(response, what).map(|_| ())
+}
+
+/// Passes a successful response through and turns any other into an error
+/// with the status and body.
+fn successful(
+    response: reqwest::blocking::Response,
+    what: &str,
+) -> anyhow::Result<reqwest::blocking::Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+    Err(StatusError::from_response(response, what).into())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "image".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::MockServer;
+
+    fn photo(dir: &Path) -> std::path::PathBuf {
+        let path = dir.join("IMG_0007.JPG");
+        std::fs::write(&path, b"jpeg-bytes").unwrap();
+        path
+    }
+
+    #[test]
+    fn label_studio_gets_an_uploaded_file_and_the_choice() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = MockServer::start(|request| {
+            if request.path.contains("/import") {
+                (201, r#"{"task_count": 1, "task_ids": [42]}"#.to_string())
+            } else {
+                (201, "{}".to_string())
+            }
+        });
+        let settings = AnnotationSettings {
+            kind: SinkKind::LabelStudio,
+            label_studio_url: format!("{}/", server.url),
+            label_studio_project: "3".to_string(),
+            label_studio_token: "secret".to_string(),
+            ..AnnotationSettings::default()
+        };
+        settings
+            .sink("")
+            .unwrap()
+            .upload(&photo(dir.path()), "pimpelmees", &mut None)
+            .unwrap();
+
+        let requests = server.requests();
+        let import = &requests[0];
+        assert_eq!(import.path, "/api/projects/3/import?return_task_ids=true");
+        assert_eq!(import.header("authorization"), Some("Token secret"));
+        assert!(
+            import
+                .header("content-type")
+                .unwrap()
+                .starts_with("multipart/form-data")
+        );
+        let body = import.body_text();
+        assert!(body.contains("filename=\"IMG_0007.JPG\""));
+        assert!(body.contains("jpeg-bytes"));
+
+        let annotation = &requests[1];
+        assert_eq!(annotation.path, "/api/tasks/42/annotations/");
+        assert_eq!(annotation.header("authorization"), Some("Token secret"));
+        let json: serde_json::Value = serde_json::from_slice(&annotation.body).unwrap();
+        let result = &json["result"][0];
+        assert_eq!(result["from_name"], "choice");
+        assert_eq!(result["value"]["choices"][0], "pimpelmees");
+    }
+
+    #[test]
+    fn http_sink_posts_multipart_and_reports_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = MockServer::start(|request| {
+            if request.header("authorization") == Some("Bearer t0ken") {
+                (200, "{}".to_string())
+            } else {
+                (401, "nope".to_string())
+            }
+        });
+        let mut settings = AnnotationSettings {
+            kind: SinkKind::Http,
+            http_url: format!("{}/feedie/upload", server.url),
+            http_token: "t0ken".to_string(),
+            ..AnnotationSettings::default()
+        };
+        let path = photo(dir.path());
+        settings
+            .sink("")
+            .unwrap()
+            .upload(&path, "koolmees", &mut None)
+            .unwrap();
+        let request = &server.requests()[0];
+        assert_eq!(request.path, "/feedie/upload");
+        assert!(
+            request
+                .header("content-type")
+                .unwrap()
+                .starts_with("multipart/form-data")
+        );
+        let body = request.body_text();
+        assert!(body.contains("name=\"label\"\r\n\r\nkoolmees"));
+        assert!(body.contains("filename=\"IMG_0007.JPG\""));
+        assert!(body.contains("jpeg-bytes"));
+
+        settings.http_token.clear();
+        let err = settings
+            .sink("")
+            .unwrap()
+            .upload(&path, "koolmees", &mut None)
+            .unwrap_err();
+        assert!(err.to_string().contains("401"));
+    }
+
+    #[test]
+    fn roboflow_uses_the_configured_base_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let server = MockServer::start(|request| {
+            if request.path.contains("/upload") {
+                (200, r#"{"id": "abc"}"#.to_string())
+            } else {
+                (200, "{}".to_string())
+            }
+        });
+        let mut settings = AnnotationSettings {
+            roboflow_url: server.url.clone(),
+            roboflow_dataset: "/tuin/".to_string(),
+            ..AnnotationSettings::default()
+        };
+        assert_eq!(
+            settings.sink("embedded").err(),
+            Some("roboflow-custom-host-no-key")
+        );
+        settings.roboflow_key = "key".to_string();
+        settings
+            .sink("embedded")
+            .unwrap()
+            .upload(&photo(dir.path()), "merel", &mut None)
+            .unwrap();
+        let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+        assert!(paths[0].starts_with("/dataset/tuin/upload?api_key=key"));
+        assert!(paths[1].starts_with("/dataset/tuin/annotate/abc"));
+
+        let cloud = AnnotationSettings::default();
+        assert_eq!(cloud.sink("").err(), Some("roboflow-no-key"));
+        assert!(cloud.sink("embedded").is_ok());
+        assert!(is_roboflow_cloud("https://api.roboflow.com/"));
+        assert!(!is_roboflow_cloud("http://api.roboflow.com"));
+        assert!(!is_roboflow_cloud("https://api.roboflow.com.example.org"));
+
+        let missing = AnnotationSettings {
+            kind: SinkKind::LabelStudio,
+            ..AnnotationSettings::default()
+        };
+        assert_eq!(missing.sink("key").err(), Some("annotation-missing-config"));
+    }
+}
@@ -1,37 +1,62 @@
 //! Core application state for the Feedie GUI.
 
+use crate::annotation::AnnotationSettings;
 use crate::export::{CoordinatePrompt, PendingExport};
 use crate::i18n::{Language, LanguagePreference};
-use crate::manifest::{AppDownloadStatus, ManifestStatus, ModelDownloadStatus};
+use crate::manifest::{
+    AppDownloadStatus, ManifestStatus, ModelDownloadMsg, ModelDownloadStatus, UpdateChannel,
+};
+use crate::model::InstalledModel;
+use crate::privacy::ShareRecord;
 use crate::settings_store::{AppSettings, load_settings, save_settings};
+use crate::training_export::DatasetFormat;
+use crate::upload_queue::{UploadEvent, UploadQueue};
 use crate::util::canonical_label;
 use eframe::{App, Frame, egui};
-use feeder_core::ImageInfo;
-use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use feeder_core::{
+    EvaluationReport, HeadTrainingReport, ImageInfo, MegaDetectorImport, RecordFormat, ReferenceSet,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::mpsc::{Receiver, Sender};
 
 mod cache;
+mod comparison;
+mod evaluation;
 mod folder;
 mod frame;
+mod installed_models;
+mod packs;
+mod personal;
 mod preview;
+mod references;
 mod results;
 mod selection;
 mod settings;
+mod similar;
 mod thumbnails;
+mod uploads;
 
+use self::cache::ScanModel;
+use self::comparison::{ComparisonMsg, ModelComparison};
+use self::evaluation::EvaluationMsg;
+use self::personal::TrainingMsg;
+pub(crate) use self::personal::{model_id, personal_model_dir_for, trained_on};
 use self::preview::PreviewState;
+use self::similar::{EmbeddingMsg, SimilarView};
 
 /// Determines which subset of images is visible in the results grid.
 ///
 /// Each mode filters the classifier results differently and drives the counters
-/// shown at the top of the “Scanresultaat” panel.
+/// shown at the top of the “Scanresultaat” panel. `Vergelijkbaar` is the
+/// temporary gallery opened through “Zoek vergelijkbare beelden”.
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ViewMode {
     #[default]
     Aanwezig,
     Leeg,
     Onzeker,
+    Vergelijkbaar,
 }
 
 /// Identifies the panel that is currently shown in the top navigation bar.
@@ -105,6 +130,10 @@ pub struct UiApp {
     pub(crate) has_scanned: bool,
     pub(crate) scan_in_progress: bool,
     pub(crate) scan_recursive: bool,
+    /// MegaDetector batch-output file that prefills the next scan.
+    pub(crate) megadetector_file: Option<PathBuf>,
+    /// Summary of the MegaDetector import of the running scan.
+    pub(crate) megadetector_summary: Option<MegaDetectorImport>,
     pub(crate) status: String,
     pub(crate) view: ViewMode,
     pub(crate) panel: Panel,
@@ -127,29 +156,61 @@ pub struct UiApp {
     pub(crate) pending_presence_threshold: f32,
     pub(crate) background_labels: Vec<String>,
     pub(crate) preview: Option<PreviewState>,
+    pub(crate) similar_view: Option<SimilarView>,
+    pub(crate) similar_query: Vec<usize>,
+    pub(crate) embedding_rx: Option<Receiver<EmbeddingMsg>>,
+    pub(crate) references: ReferenceSet,
+    pub(crate) use_personal_model: bool,
+    pub(crate) training_rx: Option<Receiver<TrainingMsg>>,
+    pub(crate) training_report: Option<HeadTrainingReport>,
+    pub(crate) evaluation_rx: Option<Receiver<EvaluationMsg>>,
+    pub(crate) evaluation_result: Option<(EvaluationReport, PathBuf)>,
     pub(crate) label_options: Vec<LabelOption>,
     pub(crate) new_label_buffer: String,
     pub(crate) export_present: bool,
     pub(crate) export_uncertain: bool,
     pub(crate) export_background: bool,
     pub(crate) export_csv: bool,
+    pub(crate) export_camtrap: bool,
+    pub(crate) export_dwca: bool,
+    pub(crate) export_timelapse: bool,
+    pub(crate) export_megadetector: bool,
+    pub(crate) export_ebird: bool,
+    pub(crate) export_xmp: bool,
+    pub(crate) export_records: bool,
+    pub(crate) export_records_format: RecordFormat,
+    pub(crate) dataset_format: DatasetFormat,
+    pub(crate) dataset_include_predictions: bool,
     pub(crate) pending_export: Option<PendingExport>,
     pub(crate) coordinate_prompt: Option<CoordinatePrompt>,
     pub(crate) manifest_status: ManifestStatus,
     pub(crate) update_rx:
         Option<Receiver<Result<crate::manifest::RemoteManifest, crate::manifest::ManifestError>>>,
     pub(crate) model_download_status: ModelDownloadStatus,
-    pub(crate) model_download_rx: Option<Receiver<Result<String, String>>>,
+    pub(crate) model_download_rx: Option<Receiver<ModelDownloadMsg>>,
+    pub(crate) model_comparison: Option<ModelComparison>,
+    pub(crate) comparison_rx: Option<Receiver<ComparisonMsg>>,
     pub(crate) app_download_status: AppDownloadStatus,
     pub(crate) app_download_rx:
         Option<Receiver<Result<PathBuf, crate::manifest::AppDownloadError>>>,
     pub(crate) app_version: String,
     pub(crate) model_version: String,
     pub(crate) model_root: PathBuf,
+    pub(crate) active_model_dir: PathBuf,
+    pub(crate) installed_models: Vec<InstalledModel>,
+    pub(crate) site_models: BTreeMap<String, String>,
+    pub(crate) update_channel: UpdateChannel,
+    pub(crate) pack_download_rx: Option<Receiver<Result<String, String>>>,
+    pub(crate) model_import_rx: Option<Receiver<Result<String, String>>>,
+    pub(crate) scan_model: ScanModel,
     pub(crate) improve_recognition: bool,
-    pub(crate) roboflow_dataset_input: String,
-    pub(crate) upload_status_tx: Sender<String>,
-    pub(crate) upload_status_rx: Receiver<String>,
+    /// Annotation backend as edited in settings.
+    pub(crate) annotation: AnnotationSettings,
+    pub(crate) upload_queue: UploadQueue,
+    pub(crate) upload_events: Receiver<UploadEvent>,
+    pub(crate) upload_review_open: bool,
+    /// Audit log entries shown in the review window, newest first.
+    pub(crate) share_history: Vec<ShareRecord>,
     pub(crate) language_preference: LanguagePreference,
     pub(crate) language: Language,
 }
@@ -158,19 +219,24 @@ impl UiApp {
     /// Creates a new UI instance and kicks off the first manifest refresh.
     pub(crate) fn new() -> Self {
         let mut app = Self::default_internal();
+        app.register_reference_labels();
+        app.detect_staged_model();
         app.request_manifest_refresh();
         app
     }
 
     /// Internal constructor that wires all state defaults together.
     fn default_internal() -> Self {
-        let (model_root, model_version) = Self::prepare_model_dir();
-        let label_options = Self::load_label_options_from(&model_root.join("feeder-labels.csv"));
-        let (upload_status_tx, upload_status_rx) = std::sync::mpsc::channel();
+        let (model_root, active_model_dir, model_version) = Self::prepare_model_dir();
+        let label_options = Self::load_label_options_from(&active_model_dir.join(LABEL_FILE_NAME));
+        let installed_models = crate::model::installed_models(&model_root);
         let (thumb_req_txs, thumb_res_rx) = thumbnails::spawn_thumbnail_worker();
         let settings = load_settings();
+        let (upload_queue, upload_events) = uploads::open_upload_queue();
         let language = settings.language.resolve();
         let background_labels = Self::normalize_background_labels(settings.background_labels);
+        let training_report =
+            HeadTrainingReport::load(personal_model_dir_for(&model_root, &active_model_dir));
         Self {
             gekozen_map: None,
             rijen: Vec::new(),
@@ -179,6 +245,8 @@ impl UiApp {
             has_scanned: false,
             scan_in_progress: false,
             scan_recursive: settings.scan_recursive,
+            megadetector_file: None,
+            megadetector_summary: None,
             status: String::new(),
             view: ViewMode::default(),
             panel: Panel::Folder,
@@ -201,27 +269,57 @@ impl UiApp {
             pending_presence_threshold: 0.5,
             background_labels,
             preview: None,
+            similar_view: None,
+            similar_query: Vec::new(),
+            embedding_rx: None,
+            references: references::load_references(&model_id(&model_root, &active_model_dir)),
+            use_personal_model: settings.use_personal_model,
+            training_rx: None,
+            training_report,
+            evaluation_rx: None,
+            evaluation_result: None,
             label_options,
             new_label_buffer: String::new(),
             export_present: true,
             export_uncertain: false,
             export_background: false,
             export_csv: true,
+            export_camtrap: false,
+            export_dwca: false,
+            export_timelapse: false,
+            export_megadetector: false,
+            export_ebird: false,
+            export_xmp: false,
+            export_records: false,
+            export_records_format: RecordFormat::default(),
+            dataset_format: DatasetFormat::default(),
+            dataset_include_predictions: false,
             pending_export: None,
             coordinate_prompt: None,
             manifest_status: ManifestStatus::Idle,
             update_rx: None,
             model_download_status: ModelDownloadStatus::Idle,
             model_download_rx: None,
+            model_comparison: None,
+            comparison_rx: None,
             app_download_status: AppDownloadStatus::Idle,
             app_download_rx: None,
             app_version: env!("FEEDIE_VERSION").to_string(),
             model_version,
             model_root,
+            active_model_dir,
+            installed_models,
+            site_models: settings.site_models,
+            update_channel: settings.update_channel,
+            pack_download_rx: None,
+            model_import_rx: None,
+            scan_model: ScanModel::default(),
             improve_recognition: false,
-            roboflow_dataset_input: "voederhuiscamera".to_string(),
-            upload_status_tx,
-            upload_status_rx,
+            annotation: settings.annotation,
+            upload_queue,
+            upload_events,
+            upload_review_open: false,
+            share_history: Vec::new(),
             language_preference: settings.language,
             language,
         }
@@ -252,11 +350,15 @@ impl UiApp {
         self.persist_settings();
     }
 
-    fn persist_settings(&self) {
+    pub(crate) fn persist_settings(&self) {
         let settings = AppSettings {
             language: self.language_preference,
             background_labels: self.background_labels.clone(),
             scan_recursive: self.scan_recursive,
+            use_personal_model: self.use_personal_model,
+            site_models: self.site_models.clone(),
+            update_channel: self.update_channel,
+            annotation: self.annotation.clone(),
         };
         if let Err(err) = save_settings(&settings) {
             tracing::warn!("Instellingen konden niet worden opgeslagen: {err}");
@@ -309,6 +411,8 @@ pub(crate) const CARD_HEIGHT: f32 = THUMB_SIZE as f32 + 70.0;
 pub(crate) const PAGE_SIZE: usize = 100;
 /// Roboflow API key for optional uploads (injected at build time; may be empty).
 pub(crate) const ROBOFLOW_API_KEY: &str = env!("ROBOFLOW_API_KEY");
+/// Hex-encoded ed25519 key for signed updates (injected at build time; may be empty).
+pub(crate) const UPDATE_PUBLIC_KEY: &str = env!("UPDATE_PUBLIC_KEY");
 /// Remote manifest location that describes available updates.
 pub(crate) const MANIFEST_URL: &str = "https://github.com/kpauly/feedie/raw/main/manifest.json";
 /// Name of the bundled EfficientViT model weights.
@@ -321,6 +425,24 @@ pub(crate) const BACKGROUND_LABEL: &str = "achtergrond";
 pub(crate) const SOMETHING_LABEL: &str = "iets sp";
 /// Name of the on-disk file that stores the model version that was installed.
 pub(crate) const VERSION_FILE_NAME: &str = "model_version.txt";
+/// Folder (inside the model directory) that holds the personal model.
+pub(crate) const PERSONAL_MODEL_DIR: &str = "personal";
+/// Subfolder of the model root where a downloaded model waits for activation.
+pub(crate) const STAGING_MODEL_DIR: &str = "staging";
+/// Subfolder of the model root holding partially downloaded model packages.
+pub(crate) const MODEL_DOWNLOADS_DIR: &str = "downloads";
+/// Subfolder of the model root holding one folder per installed model version.
+pub(crate) const MODEL_VERSIONS_DIR: &str = "versions";
+/// File in the model root naming the folder of the active model version.
+pub(crate) const ACTIVE_MODEL_FILE_NAME: &str = "active_model.txt";
+/// Optional release notes stored next to an installed model.
+pub(crate) const MODEL_NOTES_FILE_NAME: &str = "notes.txt";
+/// Id of the regional model pack an installed model belongs to, if any.
+pub(crate) const MODEL_PACK_FILE_NAME: &str = "pack.txt";
+/// Optional metadata inside a model package (pack, notes, app versions).
+pub(crate) const MODEL_METADATA_FILE_NAME: &str = "feedie-model.json";
+/// Folder name used for evaluation reports next to the evaluated images.
+pub(crate) const EVALUATION_DIR_NAME: &str = "feedie-evaluatie";
 
 /// Messages that flow back from the background scanning thread.
 ///
@@ -328,6 +450,7 @@ pub(crate) const VERSION_FILE_NAME: &str = "model_version.txt";
 /// progress or replace the entire result set once classification finishes.
 pub(crate) enum ScanMsg {
     Progress(usize, usize),
+    Prefilled(MegaDetectorImport),
     Done(Vec<ImageInfo>, u128),
     Error(String),
 }
@@ -3,7 +3,7 @@
 use crate::app::UiApp;
 use anyhow::Context;
 use directories_next::ProjectDirs;
-use feeder_core::{Classification, ImageInfo};
+use feeder_core::{Classification, Detection, ImageInfo, LabelScore};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -18,18 +18,57 @@ struct CachedFile {
     modified: u64,
     present: bool,
     classification: Option<Classification>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    embedding: Option<Vec<f32>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    embedding_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    detections: Vec<Detection>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    top_k: Vec<LabelScore>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct CachedScan {
     generated_at: u64,
     model_version: String,
+    /// Whether the personal model produced the predictions.
+    #[serde(default)]
+    personal_model: bool,
     #[serde(default)]
     recursive: bool,
     files: Vec<CachedFile>,
     total_files: usize,
 }
 
+/// Identifies the model that produced the predictions of a scan.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ScanModel {
+    pub(crate) version: String,
+    pub(crate) personal: bool,
+}
+
+impl UiApp {
+    /// Returns the model the next scan will use.
+    pub(crate) fn active_scan_model(&self) -> ScanModel {
+        ScanModel {
+            version: self.model_version.clone(),
+            personal: self.use_personal_model && self.personal_model_available(),
+        }
+    }
+
+    /// Formats a scan model as "1.2.0" or "1.2.0 (personal)".
+    pub(crate) fn describe_scan_model(&self, model: &ScanModel) -> String {
+        if model.personal {
+            format!("{} ({})", model.version, self.t("settings-model-personal"))
+        } else {
+            model.version.clone()
+        }
+    }
+}
+
 fn cache_dir() -> Option<PathBuf> {
     ProjectDirs::from("nl", "Feedie", "Feedie").map(|dirs| dirs.data_dir().join("cache"))
 }
@@ -109,6 +148,7 @@ impl UiApp {
 
         // Validate signatures.
         let mut rebuilt: Vec<ImageInfo> = Vec::with_capacity(cached.files.len());
+        let mut migrated = 0usize;
         for entry in &cached.files {
             let Some((abs, size, modified)) = current.get(&entry.rel_path).cloned() else {
                 return Ok(false);
@@ -116,10 +156,21 @@ impl UiApp {
             if size != entry.size || modified != entry.modified {
                 return Ok(false);
             }
+            let mut classification = entry.classification.clone();
+            if let Some(classification) = &mut classification
+                && classification.migrate_legacy_manual()
+            {
+                migrated += 1;
+            }
             rebuilt.push(ImageInfo {
                 file: abs,
                 present: entry.present,
-                classification: entry.classification.clone(),
+                classification,
+                embedding: entry.embedding.clone(),
+                embedding_model: entry.embedding_model.clone(),
+                detections: entry.detections.clone(),
+                top_k: entry.top_k.clone(),
+                error: entry.error.clone(),
             });
         }
 
@@ -128,15 +179,31 @@ impl UiApp {
         self.has_scanned = true;
         self.scan_in_progress = false;
         self.current_page = 0;
-        self.status = format!(
-            "{} ({})",
-            self.t("status-cache-loaded"),
-            cached.model_version
-        );
+        self.scan_model = ScanModel {
+            version: cached.model_version,
+            personal: cached.personal_model,
+        };
+        let produced_by = self.describe_scan_model(&self.scan_model);
+        self.status = if self.scan_model == self.active_scan_model() {
+            format!("{} ({produced_by})", self.t("status-cache-loaded"))
+        } else {
+            let mut args = crate::i18n::Args::new();
+            args.insert("model".into(), produced_by.into());
+            self.t_args("status-cache-other-model", &args)
+        };
         self.reset_thumbnail_cache();
         self.full_images.clear();
         self.full_keys.clear();
         self.reset_selection();
+        if migrated > 0 {
+            tracing::info!(
+                "{migrated} handmatige labels in {} omgezet naar herkomstveld",
+                cache_file.display()
+            );
+            if self.gekozen_map.as_deref() == Some(folder) {
+                self.save_cache_for_current_folder();
+            }
+        }
         Ok(true)
     }
 
@@ -171,12 +238,18 @@ impl UiApp {
                 modified,
                 present: info.present,
                 classification: info.classification.clone(),
+                embedding: info.embedding.clone(),
+                embedding_model: info.embedding_model.clone(),
+                detections: info.detections.clone(),
+                top_k: info.top_k.clone(),
+                error: info.error.clone(),
             });
         }
 
         let payload = CachedScan {
             generated_at: now_secs(),
-            model_version: self.model_version.clone(),
+            model_version: self.scan_model.version.clone(),
+            personal_model: self.scan_model.personal,
             recursive: self.scan_recursive,
             total_files: files.len(),
             files,
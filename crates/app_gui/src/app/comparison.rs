@@ -0,0 +1,345 @@
+//! Side-by-side comparison of a staged model update with the active model.
+//!
+//! A downloaded model is first placed in the staging folder. Before it is
+//! activated, it is run on the current scan and the changed predictions are
+//! grouped per "old label → new label" pair, with example thumbnails.
+
+use super::cache::ScanModel;
+use super::{LABEL_FILE_NAME, MODEL_FILE_NAME, THUMB_SIZE, UiApp};
+use crate::model::installed_model_id;
+use crate::util::canonical_label;
+use eframe::egui;
+use feeder_core::{Classification, Decision, EfficientVitClassifier, ImageInfo};
+use std::collections::HashMap;
+use std::sync::mpsc::{self, TryRecvError};
+use std::thread;
+
+/// Number of example thumbnails shown per change group.
+const EXAMPLES_PER_CHANGE: usize = 8;
+
+/// Rows whose label changes in the same way, e.g. "koolmees → pimpelmees".
+pub(crate) struct ChangeGroup {
+    /// Canonical label of the active model, `None` when there was none.
+    pub(crate) from: Option<String>,
+    /// Canonical label of the staged model, `None` when there is none.
+    pub(crate) to: Option<String>,
+    pub(crate) rows: Vec<usize>,
+}
+
+/// Outcome of running the staged model on the current scan.
+pub(crate) struct ModelComparison {
+    pub(crate) version: String,
+    /// Predictions of the staged model per row index of `rijen`.
+    pub(crate) results: Vec<(usize, ImageInfo)>,
+    /// Change groups, largest first.
+    pub(crate) changes: Vec<ChangeGroup>,
+    pub(crate) unchanged: usize,
+    pub(crate) open: bool,
+}
+
+/// Messages from the background comparison job.
+pub(crate) enum ComparisonMsg {
+    Progress(usize, usize),
+    Done(String, Vec<(usize, ImageInfo)>),
+    Error(String),
+}
+
+impl UiApp {
+    /// Runs the staged model on the current scan in the background.
+    ///
+    /// Manually labelled rows are skipped because activation never changes
+    /// them. The reference set holds embeddings of the active model, which
+    /// cannot be compared with those of the staged model, so the staged model
+    /// runs without it.
+    pub(crate) fn start_model_comparison(&mut self, version: &str) {
+        if self.comparison_rx.is_some() || self.scan_in_progress {
+            return;
+        }
+        let rows: Vec<(usize, ImageInfo)> = self
+            .rijen
+            .iter()
+            .enumerate()
+            .filter(|(_, info)| !is_manual(info))
+            .map(|(idx, info)| (idx, info.clone()))
+            .collect();
+        if rows.is_empty() {
+            return;
+        }
+        let staging = self.staging_model_dir();
+        let mut cfg = self.classifier_config();
+        cfg.model_path = staging.join(MODEL_FILE_NAME);
+        cfg.labels_path = staging.join(LABEL_FILE_NAME);
+        cfg.references = None;
+        cfg.model_id = Some(installed_model_id(&staging));
+        let version = version.to_string();
+        let language = self.language;
+        let (tx, rx) = mpsc::channel();
+        self.comparison_rx = Some(rx);
+        self.model_comparison = None;
+        self.status = self.t("comparison-running");
+        thread::spawn(move || {
+            let classifier = match EfficientVitClassifier::new(&cfg) {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = tx.send(ComparisonMsg::Error(format!(
+                        "{}: {e}",
+                        crate::i18n::t_for(language, "model-load-failed")
+                    )));
+                    return;
+                }
+            };
+            let (indices, mut infos): (Vec<usize>, Vec<ImageInfo>) = rows.into_iter().unzip();
+            for info in &mut infos {
+                info.classification = None;
+                info.embedding = None;
+            }
+            let tx_progress = tx.clone();
+            if let Err(e) = classifier.classify_with_progress(&mut infos, |done, total| {
+                let _ = tx_progress.send(ComparisonMsg::Progress(done, total));
+            }) {
+                let _ = tx.send(ComparisonMsg::Error(format!(
+                    "{}: {e}",
+                    crate::i18n::t_for(language, "comparison-failed")
+                )));
+                return;
+            }
+            let _ = tx.send(ComparisonMsg::Done(
+                version,
+                indices.into_iter().zip(infos).collect(),
+            ));
+        });
+    }
+
+    /// Collects the comparison result and opens the diff window.
+    pub(crate) fn poll_comparison_job(&mut self) {
+        let Some(rx) = self.comparison_rx.take() else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(ComparisonMsg::Progress(done, total)) => {
+                    self.status = format!("{}: {done} / {total}", self.t("comparison-running"));
+                }
+                Ok(ComparisonMsg::Done(version, results)) => {
+                    let comparison = self.build_comparison(version, results);
+                    let mut args = crate::i18n::Args::new();
+                    let changed: usize = comparison.changes.iter().map(|c| c.rows.len()).sum();
+                    args.insert("changed".into(), (changed as u64).into());
+                    args.insert(
+                        "total".into(),
+                        ((changed + comparison.unchanged) as u64).into(),
+                    );
+                    self.status = self.t_args("comparison-summary", &args);
+                    self.model_comparison = Some(comparison);
+                    return;
+                }
+                Ok(ComparisonMsg::Error(message)) => {
+                    self.status = message;
+                    return;
+                }
+                Err(TryRecvError::Empty) => {
+                    self.comparison_rx = Some(rx);
+                    return;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    self.status = self.t("comparison-failed");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Groups the staged predictions by how they differ from the current rows.
+    fn build_comparison(
+        &self,
+        version: String,
+        results: Vec<(usize, ImageInfo)>,
+    ) -> ModelComparison {
+        let mut groups: HashMap<(Option<String>, Option<String>), Vec<usize>> = HashMap::new();
+        let mut unchanged = 0;
+        for (idx, new_info) in &results {
+            let Some(old_info) = self.rijen.get(*idx) else {
+                continue;
+            };
+            let from = label_of(old_info.classification.as_ref());
+            let to = label_of(new_info.classification.as_ref());
+            if from == to {
+                unchanged += 1;
+            } else {
+                groups.entry((from, to)).or_default().push(*idx);
+            }
+        }
+        let mut changes: Vec<ChangeGroup> = groups
+            .into_iter()
+            .map(|((from, to), rows)| ChangeGroup { from, to, rows })
+            .collect();
+        changes.sort_by(|a, b| {
+            b.rows
+                .len()
+                .cmp(&a.rows.len())
+                .then_with(|| a.from.cmp(&b.from))
+                .then_with(|| a.to.cmp(&b.to))
+        });
+        ModelComparison {
+            version,
+            results,
+            changes,
+            unchanged,
+            open: true,
+        }
+    }
+
+    /// Replaces the predictions of the current scan with those of the
+    /// comparison, so activating a model does not require a new scan.
+    pub(crate) fn apply_model_comparison(&mut self) {
+        let Some(comparison) = self.model_comparison.take() else {
+            return;
+        };
+        for (idx, new_info) in comparison.results {
+            if let Some(info) = self.rijen.get_mut(idx)
+                && info.file == new_info.file
+                && !is_manual(info)
+            {
+                info.classification = new_info.classification;
+                info.embedding = new_info.embedding;
+                info.embedding_model = new_info.embedding_model;
+            }
+        }
+        self.scan_model = ScanModel {
+            version: comparison.version,
+            personal: false,
+        };
+        self.apply_presence_threshold();
+        self.save_cache_for_current_folder();
+        self.reset_thumbnail_cache();
+    }
+
+    /// Drops a pending or finished comparison.
+    pub(crate) fn clear_model_comparison(&mut self) {
+        self.model_comparison = None;
+        self.comparison_rx = None;
+    }
+
+    /// Renders the diff window of a finished comparison.
+    pub(crate) fn render_model_comparison_window(&mut self, ctx: &egui::Context) {
+        let Some(comparison) = &self.model_comparison else {
+            return;
+        };
+        if !comparison.open {
+            return;
+        }
+        let mut examples: Vec<usize> = Vec::new();
+        for change in &comparison.changes {
+            examples.extend(change.rows.iter().take(EXAMPLES_PER_CHANGE));
+        }
+        self.queue_thumbnails_for_indices(&examples);
+
+        let Some(comparison) = &self.model_comparison else {
+            return;
+        };
+        let mut open = true;
+        let mut activate = false;
+        let mut discard = false;
+        let thumb = THUMB_SIZE as f32 * 0.6;
+        let mut args = crate::i18n::Args::new();
+        args.insert(
+            "current".into(),
+            self.describe_scan_model(&self.scan_model).into(),
+        );
+        args.insert("staged".into(), comparison.version.clone().into());
+        let title = self.t_args("comparison-title", &args);
+        let changed: usize = comparison.changes.iter().map(|c| c.rows.len()).sum();
+        let mut args = crate::i18n::Args::new();
+        args.insert("changed".into(), (changed as u64).into());
+        args.insert(
+            "total".into(),
+            ((changed + comparison.unchanged) as u64).into(),
+        );
+        let summary = self.t_args("comparison-summary", &args);
+        egui::Window::new(title)
+            .collapsible(false)
+            .default_width(720.0)
+            .default_height(520.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(summary);
+                ui.add_space(6.0);
+                egui::ScrollArea::vertical()
+                    .max_height(420.0)
+                    .show(ui, |ui| {
+                        if comparison.changes.is_empty() {
+                            ui.label(self.t("comparison-no-changes"));
+                        }
+                        for change in &comparison.changes {
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "{} → {}  ({})",
+                                    self.comparison_label(change.from.as_deref()),
+                                    self.comparison_label(change.to.as_deref()),
+                                    change.rows.len()
+                                ))
+                                .strong(),
+                            );
+                            ui.horizontal_wrapped(|ui| {
+                                for &idx in change.rows.iter().take(EXAMPLES_PER_CHANGE) {
+                                    let Some(info) = self.rijen.get(idx) else {
+                                        continue;
+                                    };
+                                    match self.thumb_texture_id(&info.file) {
+                                        Some(id) => {
+                                            ui.add(egui::Image::new((
+                                                id,
+                                                egui::vec2(thumb, thumb),
+                                            )))
+                                            .on_hover_text(info.file.display().to_string());
+                                        }
+                                        None => {
+                                            ui.add_sized([thumb, thumb], egui::Spinner::new());
+                                        }
+                                    }
+                                }
+                            });
+                            ui.add_space(6.0);
+                        }
+                    });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(self.t("comparison-activate")).clicked() {
+                        activate = true;
+                    }
+                    if ui.button(self.t("comparison-discard")).clicked() {
+                        discard = true;
+                    }
+                });
+            });
+
+        if activate {
+            self.activate_staged_model();
+        } else if discard {
+            self.discard_staged_model();
+        } else if !open && let Some(comparison) = self.model_comparison.as_mut() {
+            comparison.open = false;
+        }
+    }
+
+    fn comparison_label(&self, label: Option<&str>) -> String {
+        match label {
+            Some(name) => self.display_for(name),
+            None => self.t("label-empty"),
+        }
+    }
+}
+
+/// Returns the canonical predicted label, `None` for unknown or missing.
+fn label_of(classification: Option<&Classification>) -> Option<String> {
+    match classification.map(|c| &c.decision) {
+        Some(Decision::Label(name)) => Some(canonical_label(name)),
+        _ => None,
+    }
+}
+
+fn is_manual(info: &ImageInfo) -> bool {
+    info.classification
+        .as_ref()
+        .is_some_and(Classification::is_manual)
+}
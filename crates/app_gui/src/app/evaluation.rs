@@ -0,0 +1,183 @@
+//! Model evaluation against a labelled folder or the manual labels of a scan.
+
+use super::{EVALUATION_DIR_NAME, UiApp};
+use crate::util::canonical_label;
+use eframe::egui;
+use feeder_core::{
+    Decision, EfficientVitClassifier, EvalSample, EvaluationReport, evaluate_samples,
+    samples_from_folder,
+};
+use rfd::FileDialog;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, TryRecvError};
+use std::thread;
+
+/// Messages from the background evaluation job.
+pub(crate) enum EvaluationMsg {
+    Progress(usize, usize),
+    Done(Result<(EvaluationReport, PathBuf), String>),
+}
+
+impl UiApp {
+    /// Asks for a `<label>/<images>` folder and evaluates the active model on it.
+    pub(crate) fn start_folder_evaluation(&mut self) {
+        if self.evaluation_rx.is_some() {
+            return;
+        }
+        let Some(dir) = FileDialog::new().set_directory(".").pick_folder() else {
+            return;
+        };
+        let samples = match samples_from_folder(&dir) {
+            Ok(samples) => samples,
+            Err(err) => {
+                self.status = format!("{}: {err}", self.t("evaluation-failed"));
+                return;
+            }
+        };
+        self.run_evaluation(samples, dir.join(EVALUATION_DIR_NAME));
+    }
+
+    /// Evaluates the active model on the manually labelled rows of this scan.
+    pub(crate) fn start_scan_evaluation(&mut self) {
+        if self.evaluation_rx.is_some() {
+            return;
+        }
+        let Some(folder) = self.gekozen_map.clone() else {
+            return;
+        };
+        let samples = self.manual_samples();
+        self.run_evaluation(samples, folder.join(EVALUATION_DIR_NAME));
+    }
+
+    /// Returns the manually labelled rows as evaluation ground truth.
+    pub(crate) fn manual_samples(&self) -> Vec<EvalSample> {
+        self.rijen
+            .iter()
+            .filter_map(|info| match &info.classification {
+                Some(classification) => match &classification.decision {
+                    Decision::Label(name) if classification.is_manual() => Some(EvalSample {
+                        file: info.file.clone(),
+                        truth: canonical_label(name),
+                    }),
+                    _ => None,
+                },
+                None => None,
+            })
+            .collect()
+    }
+
+    fn run_evaluation(&mut self, samples: Vec<EvalSample>, out_dir: PathBuf) {
+        if samples.is_empty() {
+            self.status = self.t("evaluation-no-samples");
+            return;
+        }
+        // Evaluate the model itself, without the few-shot references.
+        let mut cfg = self.classifier_config();
+        cfg.references = None;
+        let background_labels = self.background_labels.clone();
+        let language = self.language;
+        let (tx, rx) = mpsc::channel();
+        self.evaluation_rx = Some(rx);
+        self.status = self.t("evaluation-running");
+        thread::spawn(move || {
+            let fail = |err: anyhow::Error| {
+                format!(
+                    "{}: {err}",
+                    crate::i18n::t_for(language, "evaluation-failed")
+                )
+            };
+            let result = EfficientVitClassifier::new(&cfg)
+                .and_then(|classifier| {
+                    let tx_progress = tx.clone();
+                    evaluate_samples(&classifier, &samples, &background_labels, |done, total| {
+                        let _ = tx_progress.send(EvaluationMsg::Progress(done, total));
+                    })
+                })
+                .and_then(|report| {
+                    let html = report.save(&out_dir)?;
+                    Ok((report, html))
+                })
+                .map_err(fail);
+            let _ = tx.send(EvaluationMsg::Done(result));
+        });
+    }
+
+    /// Reports evaluation progress and keeps the last result for the settings panel.
+    pub(crate) fn poll_evaluation_job(&mut self) {
+        let Some(rx) = self.evaluation_rx.take() else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(EvaluationMsg::Progress(done, total)) => {
+                    self.status = format!("{}: {done} / {total}", self.t("evaluation-running"));
+                }
+                Ok(EvaluationMsg::Done(Ok((report, html)))) => {
+                    let mut args = crate::i18n::Args::new();
+                    args.insert(
+                        "accuracy".into(),
+                        format!("{:.1}", report.accuracy * 100.0).into(),
+                    );
+                    args.insert("total".into(), (report.total as u64).into());
+                    self.status = self.t_args("evaluation-done", &args);
+                    self.evaluation_result = Some((report, html));
+                    return;
+                }
+                Ok(EvaluationMsg::Done(Err(message))) => {
+                    self.status = message;
+                    return;
+                }
+                Err(TryRecvError::Empty) => {
+                    self.evaluation_rx = Some(rx);
+                    return;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    self.status = self.t("evaluation-failed");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Renders the evaluation controls in the settings panel.
+    pub(super) fn render_evaluation_section(&mut self, ui: &mut egui::Ui) {
+        ui.heading(self.t("settings-evaluation"));
+        ui.label(self.t("settings-evaluation-help"));
+        ui.add_space(4.0);
+        let idle = self.evaluation_rx.is_none() && !self.scan_in_progress;
+        let has_manual = self.gekozen_map.is_some() && !self.manual_samples().is_empty();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    idle,
+                    egui::Button::new(self.t("settings-evaluation-folder")),
+                )
+                .clicked()
+            {
+                self.start_folder_evaluation();
+            }
+            if ui
+                .add_enabled(
+                    idle && has_manual,
+                    egui::Button::new(self.t("settings-evaluation-scan")),
+                )
+                .clicked()
+            {
+                self.start_scan_evaluation();
+            }
+        });
+        if let Some((report, html)) = &self.evaluation_result {
+            let mut args = crate::i18n::Args::new();
+            args.insert(
+                "accuracy".into(),
+                format!("{:.1}", report.accuracy * 100.0).into(),
+            );
+            args.insert("total".into(), (report.total as u64).into());
+            ui.label(self.t_args("evaluation-done", &args));
+            ui.hyperlink_to(
+                self.t("settings-evaluation-open"),
+                format!("file://{}", html.display()),
+            );
+        }
+    }
+}
@@ -2,7 +2,10 @@
 
 use super::{Panel, ScanMsg, UiApp, ViewMode};
 use eframe::egui;
-use feeder_core::{EfficientVitClassifier, ImageInfo, ScanOptions, scan_folder_with};
+use feeder_core::{
+    EfficientVitClassifier, ImageInfo, MEGADETECTOR_DEFAULT_THRESHOLD, ScanOptions,
+    import_megadetector_json, scan_folder_with,
+};
 use rfd::FileDialog;
 use std::path::PathBuf;
 use std::sync::mpsc::{self, Receiver, Sender};
@@ -48,6 +51,10 @@ impl UiApp {
                 self.set_selected_folder(dir);
             }
         }
+        if self.gekozen_map.is_some() {
+            self.render_site_model_picker(ui);
+            self.render_megadetector_picker(ui);
+        }
         let can_scan = self.gekozen_map.is_some() && !self.scan_in_progress;
         if ui
             .add_enabled(can_scan, egui::Button::new(self.t("folder-scan")))
@@ -63,6 +70,41 @@ impl UiApp {
         }
     }
 
+    /// Lets the user pick a MegaDetector batch-output file that prefills the
+    /// next scan of the selected folder.
+    fn render_megadetector_picker(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let pick = ui
+                .add_enabled(
+                    !self.scan_in_progress,
+                    egui::Button::new(self.t("folder-megadetector-import")),
+                )
+                .on_hover_text(self.t("folder-megadetector-help"));
+            if pick.clicked()
+                && let Some(file) = FileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .set_directory(self.gekozen_map.clone().unwrap_or_default())
+                    .pick_file()
+            {
+                self.megadetector_file = Some(file);
+            }
+            if let Some(file) = &self.megadetector_file {
+                let name = file
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                ui.label(name).on_hover_text(file.display().to_string());
+                if ui
+                    .add_enabled(!self.scan_in_progress, egui::Button::new("✕").small())
+                    .on_hover_text(self.t("folder-megadetector-clear"))
+                    .clicked()
+                {
+                    self.megadetector_file = None;
+                }
+            }
+        });
+    }
+
     /// Shows a compact progress indicator while a scan is running.
     pub(super) fn render_progress_ui(&self, ui: &mut egui::Ui) {
         let total = self.total_files.max(1);
@@ -79,6 +121,8 @@ impl UiApp {
     /// Updates state when the user chose a new folder to scan.
     pub(super) fn set_selected_folder(&mut self, dir: PathBuf) {
         self.gekozen_map = Some(dir.clone());
+        self.megadetector_file = None;
+        self.reload_active_model();
         self.panel = Panel::Folder;
         self.rijen.clear();
         self.status.clear();
@@ -86,6 +130,9 @@ impl UiApp {
         self.scanned_count = 0;
         self.total_files = 0;
         self.view = ViewMode::Aanwezig;
+        self.similar_view = None;
+        self.similar_query.clear();
+        self.clear_model_comparison();
         self.reset_selection();
         self.current_page = 0;
         self.reset_thumbnail_cache();
@@ -124,8 +171,11 @@ impl UiApp {
         let (tx, rx): (Sender<ScanMsg>, Receiver<ScanMsg>) = mpsc::channel();
         self.rx = Some(rx);
         let cfg = self.classifier_config();
+        self.scan_model = self.active_scan_model();
         let language = self.language;
         let recursive = self.scan_recursive;
+        let megadetector = self.megadetector_file.clone();
+        self.megadetector_summary = None;
         thread::spawn(move || {
             let t0 = Instant::now();
             let mut rows = match scan_folder_with(&dir, ScanOptions { recursive }) {
@@ -152,9 +202,38 @@ impl UiApp {
                 }
             };
             let tx_progress = tx.clone();
-            if let Err(e) = classify_with_auto_batch(&classifier, &mut rows, |done, total| {
-                let _ = tx_progress.send(ScanMsg::Progress(done.min(total), total));
-            }) {
+            let result = if let Some(file) = &megadetector {
+                // Frames MegaDetector found empty are decided already; only
+                // the rest goes through the classifier.
+                let summary = match import_megadetector_json(
+                    &mut rows,
+                    &dir,
+                    file,
+                    MEGADETECTOR_DEFAULT_THRESHOLD,
+                ) {
+                    Ok(summary) => summary,
+                    Err(e) => {
+                        let _ = tx.send(ScanMsg::Error(format!(
+                            "{}: {e:#}",
+                            crate::i18n::t_for(language, "scan-megadetector-failed")
+                        )));
+                        return;
+                    }
+                };
+                let _ = tx.send(ScanMsg::Prefilled(summary));
+                let decided = rows
+                    .iter()
+                    .filter(|info| info.classification.is_some())
+                    .count();
+                classifier.classify_pending_with_progress(&mut rows, |done, _| {
+                    let _ = tx_progress.send(ScanMsg::Progress((decided + done).min(total), total));
+                })
+            } else {
+                classify_with_auto_batch(&classifier, &mut rows, |done, total| {
+                    let _ = tx_progress.send(ScanMsg::Progress(done.min(total), total));
+                })
+            };
+            if let Err(e) = result {
                 let _ = tx.send(ScanMsg::Error(format!(
                     "{}: {e}",
                     crate::i18n::t_for(language, "classification-failed")
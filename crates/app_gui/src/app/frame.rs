@@ -7,17 +7,32 @@ use std::time::Duration;
 impl UiApp {
     /// Processes background channels and keeps long-running tasks responsive.
     pub(super) fn refresh_background_state(&mut self, ctx: &egui::Context) {
-        while let Ok(msg) = self.upload_status_rx.try_recv() {
-            self.status = msg;
-        }
+        self.poll_upload_events();
         self.poll_manifest_updates();
         self.poll_app_download();
         self.poll_model_download();
+        self.poll_embedding_job();
+        self.poll_training_job();
+        self.poll_evaluation_job();
+        self.poll_comparison_job();
+        self.poll_pack_download();
+        self.poll_model_import();
         self.poll_thumbnail_results(ctx);
         self.drain_scan_channel();
-        if self.scan_in_progress || self.rx.is_some() || !self.thumb_inflight.is_empty() {
+        if self.scan_in_progress
+            || self.rx.is_some()
+            || self.embedding_rx.is_some()
+            || self.training_rx.is_some()
+            || self.evaluation_rx.is_some()
+            || self.comparison_rx.is_some()
+            || self.pack_download_rx.is_some()
+            || self.model_import_rx.is_some()
+            || !self.thumb_inflight.is_empty()
+        {
             ctx.request_repaint();
             ctx.request_repaint_after(Duration::from_millis(16));
+        } else if self.upload_queue.status().pending > 0 {
+            ctx.request_repaint_after(Duration::from_secs(1));
         }
     }
 
@@ -86,6 +101,8 @@ impl UiApp {
     pub(super) fn render_overlays(&mut self, ctx: &egui::Context) {
         self.render_preview_window(ctx);
         self.render_coordinate_prompt(ctx);
+        self.render_model_comparison_window(ctx);
+        self.render_upload_review_window(ctx);
     }
 
     /// Displays the persistent status bar at the bottom.
@@ -126,6 +143,7 @@ impl UiApp {
                         self.scan_in_progress = false;
                         self.has_scanned = true;
                         self.rijen = rows;
+                        self.close_similar_view();
                         self.current_page = 0;
                         self.reset_thumbnail_cache();
                         self.presence_threshold = self.pending_presence_threshold;
@@ -142,11 +160,20 @@ impl UiApp {
                             format!("{:.1}", (elapsed_ms as f32) / 1000.0).into(),
                         );
                         self.status = self.t_args("scan-done", &args);
+                        if let Some(summary) = self.megadetector_summary.take() {
+                            let mut args = crate::i18n::Args::new();
+                            args.insert("animal".into(), (summary.animal as u64).into());
+                            args.insert("empty".into(), (summary.empty as u64).into());
+                            let prefilled = self.t_args("scan-megadetector-done", &args);
+                            self.status = format!("{} {prefilled}", self.status);
+                        }
                         keep = false;
                         break;
                     }
+                    ScanMsg::Prefilled(summary) => self.megadetector_summary = Some(summary),
                     ScanMsg::Error(message) => {
                         self.scan_in_progress = false;
+                        self.megadetector_summary = None;
                         self.has_scanned = false;
                         self.status = message;
                         keep = false;
@@ -0,0 +1,248 @@
+//! Switching between, and removing, installed model versions.
+
+use super::{MODEL_FILE_NAME, MODEL_VERSIONS_DIR, UiApp};
+use crate::model::{
+    install_model_package, installed_models, read_model_version_from, resolve_active_model_dir,
+    set_active_model,
+};
+use eframe::egui;
+use rfd::FileDialog;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, TryRecvError};
+use std::thread;
+
+impl UiApp {
+    /// Re-reads the active model pointer and everything derived from it.
+    ///
+    /// A model chosen for the selected folder takes precedence over the
+    /// global choice.
+    pub(crate) fn reload_active_model(&mut self) {
+        self.active_model_dir = self
+            .site_model_dir()
+            .unwrap_or_else(|| resolve_active_model_dir(&self.model_root));
+        self.model_version = read_model_version_from(&self.model_version_path());
+        self.label_options = Self::load_label_options_from(&self.labels_path());
+        self.installed_models = installed_models(&self.model_root);
+        self.reload_personal_model();
+        self.reload_references();
+        self.register_reference_labels();
+    }
+
+    /// Key under which the model choice of the selected folder is stored.
+    fn site_key(&self) -> Option<String> {
+        self.gekozen_map
+            .as_ref()
+            .map(|dir| dir.to_string_lossy().to_string())
+    }
+
+    /// Returns the installed model chosen for the selected folder, if any.
+    fn site_model_dir(&self) -> Option<PathBuf> {
+        let id = self.site_models.get(&self.site_key()?)?;
+        let dir = self.model_root.join(MODEL_VERSIONS_DIR).join(id);
+        dir.join(MODEL_FILE_NAME).exists().then_some(dir)
+    }
+
+    /// Chooses the model for the selected folder; `None` follows the global choice.
+    pub(crate) fn set_site_model(&mut self, id: Option<String>) {
+        let Some(key) = self.site_key() else {
+            return;
+        };
+        match id {
+            Some(id) => self.site_models.insert(key, id),
+            None => self.site_models.remove(&key),
+        };
+        self.persist_settings();
+        self.reload_active_model();
+        let mut args = crate::i18n::Args::new();
+        args.insert("version".into(), self.model_version.clone().into());
+        self.status = self.t_args("models-selected", &args);
+    }
+
+    /// Renders the per-folder model choice in the folder panel.
+    pub(super) fn render_site_model_picker(&mut self, ui: &mut egui::Ui) {
+        let current = self
+            .site_key()
+            .and_then(|key| self.site_models.get(&key).cloned())
+            .filter(|id| self.installed_models.iter().any(|model| &model.id == id));
+        let global = resolve_active_model_dir(&self.model_root);
+        let global_name = self
+            .installed_models
+            .iter()
+            .find(|model| model.dir == global)
+            .map(|model| model.display_name())
+            .unwrap_or_else(|| self.model_version.clone());
+        let default_text = format!("{} ({global_name})", self.t("site-model-default"));
+        let selected_text = current
+            .as_ref()
+            .and_then(|id| self.installed_models.iter().find(|model| &model.id == id))
+            .map(|model| model.display_name())
+            .unwrap_or_else(|| default_text.clone());
+        let mut choice = current.clone();
+        ui.horizontal(|ui| {
+            ui.label(self.t("site-model"))
+                .on_hover_text(self.t("site-model-help"));
+            ui.add_enabled_ui(!self.scan_in_progress, |ui| {
+                egui::ComboBox::from_id_salt("site-model")
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut choice, None, default_text);
+                        for model in &self.installed_models {
+                            ui.selectable_value(
+                                &mut choice,
+                                Some(model.id.clone()),
+                                model.display_name(),
+                            );
+                        }
+                    });
+            });
+        });
+        if choice != current {
+            self.set_site_model(choice);
+        }
+    }
+
+    /// Makes an installed model version active for the next scan.
+    pub(crate) fn select_installed_model(&mut self, id: &str) {
+        if let Err(err) = set_active_model(&self.model_root, id) {
+            self.status = format!("{}: {err}", self.t("models-select-failed"));
+            return;
+        }
+        self.reload_active_model();
+        let mut args = crate::i18n::Args::new();
+        args.insert("version".into(), self.model_version.clone().into());
+        self.status = self.t_args("models-selected", &args);
+        self.request_manifest_refresh();
+    }
+
+    /// Removes an installed model version that is not active.
+    pub(crate) fn delete_installed_model(&mut self, id: &str) {
+        let Some(model) = self.installed_models.iter().find(|model| model.id == id) else {
+            return;
+        };
+        if model.dir == self.active_model_dir
+            || model.dir == resolve_active_model_dir(&self.model_root)
+        {
+            return;
+        }
+        let version = model.version.clone();
+        if let Err(err) = fs::remove_dir_all(&model.dir) {
+            self.status = format!("{}: {err}", self.t("models-delete-failed"));
+            return;
+        }
+        self.installed_models = installed_models(&self.model_root);
+        let mut args = crate::i18n::Args::new();
+        args.insert("version".into(), version.into());
+        self.status = self.t_args("models-deleted", &args);
+    }
+
+    /// Asks for a model ZIP on disk and installs it in the background.
+    ///
+    /// Meant for offline machines; the package layout matches the downloads.
+    pub(crate) fn start_model_import(&mut self) {
+        if self.model_import_rx.is_some() {
+            return;
+        }
+        let Some(path) = FileDialog::new()
+            .add_filter("ZIP", &["zip"])
+            .set_directory(".")
+            .pick_file()
+        else {
+            return;
+        };
+        let model_root = self.model_root.clone();
+        let app_version = self.app_version.clone();
+        let (tx, rx) = mpsc::channel();
+        self.model_import_rx = Some(rx);
+        self.status = self.t("model-import-running");
+        thread::spawn(move || {
+            let result = install_model_package(&path, &model_root, &app_version)
+                .map_err(|err| format!("{err:#}"));
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Polls the import job and refreshes the list of installed models.
+    pub(crate) fn poll_model_import(&mut self) {
+        let Some(rx) = self.model_import_rx.take() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Ok(id)) => {
+                self.installed_models = installed_models(&self.model_root);
+                let mut args = crate::i18n::Args::new();
+                args.insert("version".into(), id.into());
+                self.status = self.t_args("model-import-done", &args);
+            }
+            Ok(Err(err)) => {
+                self.status = format!("{}: {err}", self.t("model-import-failed"));
+            }
+            Err(TryRecvError::Empty) => {
+                self.model_import_rx = Some(rx);
+            }
+            Err(TryRecvError::Disconnected) => {
+                self.status = self.t("model-import-failed");
+            }
+        }
+    }
+
+    /// Renders the list of installed model versions in the settings panel.
+    pub(super) fn render_installed_models_section(&mut self, ui: &mut egui::Ui) {
+        ui.heading(self.t("settings-installed-models"));
+        ui.label(self.t("settings-installed-models-help"));
+        ui.add_space(4.0);
+        if self.installed_models.is_empty() {
+            ui.label(self.t("settings-installed-models-none"));
+            return;
+        }
+        let busy = self.scan_in_progress || self.comparison_rx.is_some();
+        let global = resolve_active_model_dir(&self.model_root);
+        let mut select = None;
+        let mut delete = None;
+        egui::Grid::new("installed-models")
+            .num_columns(4)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                for model in &self.installed_models {
+                    let active = model.dir == global;
+                    if model.dir == self.active_model_dir {
+                        ui.label(egui::RichText::new(model.display_name()).strong());
+                    } else {
+                        ui.label(model.display_name());
+                    }
+                    ui.label(format!(
+                        "{:.1} MB",
+                        model.size_bytes as f64 / (1024.0 * 1024.0)
+                    ));
+                    ui.label(model.notes.as_deref().unwrap_or("-"));
+                    ui.horizontal(|ui| {
+                        if active {
+                            ui.label(self.t("models-active"));
+                        } else if model.dir == self.active_model_dir {
+                            ui.label(self.t("models-site"));
+                        } else {
+                            if ui
+                                .add_enabled(!busy, egui::Button::new(self.t("models-activate")))
+                                .clicked()
+                            {
+                                select = Some(model.id.clone());
+                            }
+                            if ui
+                                .add_enabled(!busy, egui::Button::new(self.t("models-delete")))
+                                .clicked()
+                            {
+                                delete = Some(model.id.clone());
+                            }
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        if let Some(id) = select {
+            self.select_installed_model(&id);
+        }
+        if let Some(id) = delete {
+            self.delete_installed_model(&id);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Browsing and installing the regional model packs from the manifest.
+
+use super::{MODEL_NOTES_FILE_NAME, MODEL_PACK_FILE_NAME, STAGING_MODEL_DIR, UiApp};
+use crate::manifest::{
+    ModelPack, download_and_install_model, partial_download_path, update_error_message,
+};
+use crate::model::{
+    install_model_version, installed_models, normalize_model_version, safe_dir_name,
+};
+use anyhow::Context;
+use eframe::egui;
+use std::fs;
+use std::sync::mpsc::{self, TryRecvError};
+use std::thread;
+
+impl UiApp {
+    /// Downloads a regional pack and installs it next to the other models.
+    ///
+    /// Packs are not activated globally; they are chosen per folder.
+    pub(crate) fn start_pack_download(&mut self, pack: &ModelPack) {
+        if self.pack_download_rx.is_some() || !pack.compatible {
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        self.pack_download_rx = Some(rx);
+        let mut args = crate::i18n::Args::new();
+        args.insert("name".into(), pack.name.clone().into());
+        self.status = self.t_args("packs-downloading", &args);
+        let model_root = self.model_root.clone();
+        let staging = model_root.join(format!("{STAGING_MODEL_DIR}-{}", safe_dir_name(&pack.id)));
+        let partial = partial_download_path(
+            &model_root,
+            &format!("{}-{}", pack.id, pack.version),
+            &pack.checks,
+        );
+        let pack = pack.clone();
+        let app_version = self.app_version.clone();
+        let language = self.language;
+        thread::spawn(move || {
+            let _ = fs::remove_dir_all(&staging);
+            let result = download_and_install_model(
+                &pack.url,
+                &partial,
+                &pack.checks,
+                &staging,
+                &pack.version,
+                &app_version,
+                |_, _| {},
+            )
+            .and_then(|_| {
+                fs::write(staging.join(MODEL_PACK_FILE_NAME), &pack.id)
+                    .context("Kon pakketnaam niet opslaan")?;
+                if let Some(notes) = &pack.notes {
+                    fs::write(staging.join(MODEL_NOTES_FILE_NAME), notes)
+                        .context("Kon modelnotities niet opslaan")?;
+                }
+                install_model_version(&model_root, &staging)
+            })
+            .map(|_| pack.name.clone())
+            .map_err(|err| {
+                let _ = fs::remove_dir_all(&staging);
+                update_error_message(language, &err)
+            });
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Polls the pack download and refreshes the list of installed models.
+    pub(crate) fn poll_pack_download(&mut self) {
+        let Some(rx) = self.pack_download_rx.take() else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Ok(name)) => {
+                self.installed_models = installed_models(&self.model_root);
+                let mut args = crate::i18n::Args::new();
+                args.insert("name".into(), name.into());
+                self.status = self.t_args("packs-installed", &args);
+            }
+            Ok(Err(err)) => {
+                self.status = format!("{}: {err}", self.t("packs-install-failed"));
+            }
+            Err(TryRecvError::Empty) => {
+                self.pack_download_rx = Some(rx);
+            }
+            Err(TryRecvError::Disconnected) => {
+                self.status = self.t("updates-download-channel-closed");
+            }
+        }
+    }
+
+    /// Lists the packs from the manifest with their install state.
+    pub(crate) fn render_model_packs(&mut self, ui: &mut egui::Ui, packs: &[ModelPack]) {
+        ui.label(egui::RichText::new(self.t("packs-title")).strong());
+        let downloading = self.pack_download_rx.is_some();
+        let mut install = None;
+        egui::Grid::new("model-packs")
+            .num_columns(4)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                for pack in packs {
+                    let installed = self.installed_models.iter().any(|model| {
+                        model.pack.as_deref() == Some(pack.id.as_str())
+                            && model.version == normalize_model_version(&pack.version)
+                    });
+                    let mut species = crate::i18n::Args::new();
+                    species.insert("count".into(), (pack.species.len() as u64).into());
+                    ui.label(format!("{} {}", pack.name, pack.version))
+                        .on_hover_text(pack.notes.as_deref().unwrap_or(&pack.name));
+                    ui.label(&pack.region);
+                    ui.label(self.t_args("packs-species", &species))
+                        .on_hover_text(pack.species.join(", "));
+                    ui.horizontal(|ui| {
+                        if let Some(size) = pack.size_mb {
+                            ui.label(format!("{size:.1} MB"));
+                        }
+                        if installed {
+                            ui.label(self.t("packs-installed-label"));
+                        } else if !pack.compatible {
+                            ui.colored_label(egui::Color32::RED, self.t("packs-incompatible"));
+                        } else if ui
+                            .add_enabled(!downloading, egui::Button::new(self.t("packs-install")))
+                            .clicked()
+                        {
+                            install = Some(pack.clone());
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        if let Some(pack) = install {
+            self.start_pack_download(&pack);
+        }
+    }
+}
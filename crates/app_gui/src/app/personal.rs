@@ -0,0 +1,252 @@
+//! Training of, and switching to, the personal classifier head.
+//!
+//! A head only fits the model it was trained on, so each one is kept in
+//! `personal/<base model id>/` and only the one for the active model is used.
+//! Heads of other models stay on disk for when their model is active again.
+
+use super::{
+    LABEL_FILE_NAME, MODEL_FILE_NAME, MODEL_VERSIONS_DIR, PERSONAL_MODEL_DIR, UiApp,
+    VERSION_FILE_NAME,
+};
+use crate::model::read_model_version_from;
+use crate::util::sanitize_for_path;
+use eframe::egui;
+use feeder_core::{HeadTrainingConfig, HeadTrainingReport, train_personal_head};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, TryRecvError};
+use std::thread;
+
+/// File in the personal model folder naming the model it was trained on.
+const BASE_MODEL_FILE_NAME: &str = "base-model.txt";
+
+/// Messages from the background training job.
+pub(crate) enum TrainingMsg {
+    Progress(usize, usize),
+    Done(Result<HeadTrainingReport, String>),
+}
+
+impl UiApp {
+    /// Points to the folder holding the personal model of the active model.
+    pub(crate) fn personal_model_dir(&self) -> PathBuf {
+        personal_model_dir_for(&self.model_root, &self.active_model_dir)
+    }
+
+    /// Identifies the active model; see [`model_id`].
+    pub(crate) fn active_model_id(&self) -> String {
+        model_id(&self.model_root, &self.active_model_dir)
+    }
+
+    /// Returns true when a personal model trained on the active model is
+    /// present on disk.
+    pub(crate) fn personal_model_available(&self) -> bool {
+        let dir = self.personal_model_dir();
+        dir.join(MODEL_FILE_NAME).exists()
+            && dir.join(LABEL_FILE_NAME).exists()
+            && trained_on(&dir, &self.active_model_id())
+    }
+
+    /// Shows the training report of the active model's personal head, if
+    /// it has one.
+    pub(crate) fn reload_personal_model(&mut self) {
+        self.training_report = HeadTrainingReport::load(self.personal_model_dir());
+    }
+
+    /// Returns the model and labels paths used for the next scan.
+    pub(crate) fn active_model_paths(&self) -> (PathBuf, PathBuf) {
+        if self.use_personal_model && self.personal_model_available() {
+            let dir = self.personal_model_dir();
+            (dir.join(MODEL_FILE_NAME), dir.join(LABEL_FILE_NAME))
+        } else {
+            (self.model_file_path(), self.labels_path())
+        }
+    }
+
+    /// Switches between the stock and the personal model.
+    pub(crate) fn set_use_personal_model(&mut self, enabled: bool) {
+        if self.use_personal_model == enabled {
+            return;
+        }
+        self.use_personal_model = enabled;
+        self.persist_settings();
+        self.status = if enabled {
+            self.t("personal-model-selected")
+        } else {
+            self.t("stock-model-selected")
+        };
+    }
+
+    /// Trains a personal head on the stored reference examples in the background.
+    ///
+    /// Training always starts from the stock model; the result replaces the
+    /// previous personal model only once it has been written completely.
+    pub(crate) fn start_personal_training(&mut self) {
+        if self.training_rx.is_some() {
+            return;
+        }
+        if self.scan_in_progress {
+            self.status = self.t("personal-training-scan-busy");
+            return;
+        }
+        if self.references.is_empty() {
+            self.status = self.t("personal-training-no-examples");
+            return;
+        }
+        let mut cfg = self.classifier_config();
+        cfg.model_path = self.model_file_path();
+        cfg.labels_path = self.labels_path();
+        cfg.references = None;
+        let references = self.references.clone();
+        let base = self.active_model_id();
+        let target = self.personal_model_dir();
+        // Not `with_extension`: ids such as "1.2.0" contain dots.
+        let mut staging = target.clone().into_os_string();
+        staging.push(".tmp");
+        let staging = PathBuf::from(staging);
+        let language = self.language;
+        let (tx, rx) = mpsc::channel();
+        self.training_rx = Some(rx);
+        self.status = self.t("personal-training-running");
+        thread::spawn(move || {
+            let _ = fs::remove_dir_all(&staging);
+            let tx_progress = tx.clone();
+            let result = train_personal_head(
+                &cfg,
+                &references,
+                &HeadTrainingConfig::default(),
+                &staging,
+                |epoch, epochs| {
+                    let _ = tx_progress.send(TrainingMsg::Progress(epoch, epochs));
+                },
+            )
+            .and_then(|report| {
+                fs::write(staging.join(BASE_MODEL_FILE_NAME), &base)?;
+                // Only the earlier head of the same base model is replaced.
+                if target.exists() {
+                    fs::remove_dir_all(&target)?;
+                }
+                fs::rename(&staging, &target)?;
+                Ok(report)
+            })
+            .map_err(|err| {
+                let _ = fs::remove_dir_all(&staging);
+                format!(
+                    "{}: {err}",
+                    crate::i18n::t_for(language, "personal-training-failed")
+                )
+            });
+            let _ = tx.send(TrainingMsg::Done(result));
+        });
+    }
+
+    /// Reports training progress and stores the final evaluation.
+    pub(crate) fn poll_training_job(&mut self) {
+        let Some(rx) = self.training_rx.take() else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(TrainingMsg::Progress(epoch, epochs)) => {
+                    self.status = format!(
+                        "{}: {epoch} / {epochs}",
+                        self.t("personal-training-running")
+                    );
+                }
+                Ok(TrainingMsg::Done(Ok(report))) => {
+                    self.training_report = Some(report);
+                    self.status = self.t("personal-training-done");
+                    return;
+                }
+                Ok(TrainingMsg::Done(Err(message))) => {
+                    self.status = message;
+                    return;
+                }
+                Err(TryRecvError::Empty) => {
+                    self.training_rx = Some(rx);
+                    return;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    self.status = self.t("personal-training-failed");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Renders the personal model controls in the settings panel.
+    pub(super) fn render_personal_model_section(&mut self, ui: &mut egui::Ui) {
+        ui.heading(self.t("settings-personal-model"));
+        ui.label(self.t("settings-personal-model-help"));
+        ui.add_space(4.0);
+        let available = self.personal_model_available();
+        let mut use_personal = self.use_personal_model && available;
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut use_personal, false, self.t("settings-model-stock"));
+            ui.add_enabled_ui(available, |ui| {
+                ui.radio_value(&mut use_personal, true, self.t("settings-model-personal"));
+            });
+        });
+        if available && use_personal != self.use_personal_model {
+            self.set_use_personal_model(use_personal);
+        }
+
+        if let Some(report) = &self.training_report {
+            let mut args = crate::i18n::Args::new();
+            args.insert("train".into(), (report.train_examples as u64).into());
+            args.insert("holdout".into(), (report.holdout_examples as u64).into());
+            ui.label(self.t_args("settings-personal-model-examples", &args));
+            match (report.stock_accuracy, report.personal_accuracy) {
+                (Some(stock), Some(personal)) => {
+                    let mut args = crate::i18n::Args::new();
+                    args.insert("stock".into(), format!("{:.0}", stock * 100.0).into());
+                    args.insert("personal".into(), format!("{:.0}", personal * 100.0).into());
+                    ui.label(self.t_args("settings-personal-model-accuracy", &args));
+                }
+                _ => {
+                    ui.label(self.t("settings-personal-model-no-holdout"));
+                }
+            }
+            if !report.new_labels.is_empty() {
+                ui.label(format!(
+                    "{}: {}",
+                    self.t("settings-personal-model-new-labels"),
+                    report.new_labels.join(", ")
+                ));
+            }
+        }
+
+        ui.add_space(4.0);
+        let can_train = self.training_rx.is_none() && !self.references.is_empty();
+        if ui
+            .add_enabled(
+                can_train,
+                egui::Button::new(self.t("settings-personal-model-train")),
+            )
+            .clicked()
+        {
+            self.start_personal_training();
+        }
+    }
+}
+
+/// Folder of the personal head trained on the model in `model_dir`.
+pub(crate) fn personal_model_dir_for(model_root: &Path, model_dir: &Path) -> PathBuf {
+    model_root
+        .join(PERSONAL_MODEL_DIR)
+        .join(sanitize_for_path(&model_id(model_root, model_dir)))
+}
+
+/// Identifies the model in `dir`: its folder name below `versions`, or its
+/// version for a model stored directly in the model root.
+pub(crate) fn model_id(model_root: &Path, dir: &Path) -> String {
+    dir.strip_prefix(model_root.join(MODEL_VERSIONS_DIR))
+        .ok()
+        .filter(|id| !id.as_os_str().is_empty())
+        .map(|id| id.to_string_lossy().to_string())
+        .unwrap_or_else(|| read_model_version_from(&dir.join(VERSION_FILE_NAME)))
+}
+
+/// Whether the personal model in `dir` was trained on the model `base`.
+pub(crate) fn trained_on(dir: &Path, base: &str) -> bool {
+    fs::read_to_string(dir.join(BASE_MODEL_FILE_NAME)).is_ok_and(|id| id.trim() == base)
+}
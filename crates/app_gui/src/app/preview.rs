@@ -67,15 +67,30 @@ impl UiApp {
         let classification = info.classification.clone();
         let status_text = classification
             .as_ref()
-            .map(|classification| match &classification.decision {
-                Decision::Label(name) => {
-                    let mut label = self.display_for(name);
-                    if name.ends_with(" (manueel)") {
-                        label.push_str(&self.t("label-manual-suffix"));
-                    }
-                    format!("{label} ({:.1}%)", classification.confidence * 100.0)
+            .map(|classification| {
+                let label = match &classification.decision {
+                    Decision::Label(name) => self.display_for(name),
+                    Decision::Unknown => self.t("label-empty"),
+                };
+                if !classification.is_manual() {
+                    return format!("{label} ({:.1}%)", classification.confidence * 100.0);
+                }
+                let mut text = format!("{label}{}", self.t("label-manual-suffix"));
+                if let Some(original) = &classification.provenance.original {
+                    let mut args = crate::i18n::Args::new();
+                    let original_label = match &original.decision {
+                        Decision::Label(name) => self.display_for(name),
+                        Decision::Unknown => self.t("label-empty"),
+                    };
+                    args.insert("label".into(), original_label.into());
+                    args.insert(
+                        "percent".into(),
+                        format!("{:.1}", original.confidence * 100.0).into(),
+                    );
+                    text.push_str(" · ");
+                    text.push_str(&self.t_args("preview-model-prediction", &args));
                 }
-                Decision::Unknown => self.t("label-empty"),
+                text
             })
             .unwrap_or_else(|| self.t("preview-no-classification"));
         let full_tex = self.get_or_load_full_image(ctx, &info_path);
@@ -0,0 +1,158 @@
+//! Few-shot reference set built from manually labelled frames.
+
+use super::{LabelOption, SOMETHING_LABEL, UiApp};
+use crate::util::sanitize_for_path;
+use directories_next::ProjectDirs;
+use eframe::egui;
+use feeder_core::{KnnConfig, ReferenceSet};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Location of the reference set of the model `model` in the user's data
+/// directory.
+///
+/// Embeddings of different models cannot be compared, so every model keeps
+/// its own set and switching back restores the earlier examples.
+fn references_path(model: &str) -> Option<PathBuf> {
+    ProjectDirs::from("nl", "Feedie", "Feedie").map(|dirs| {
+        dirs.data_dir()
+            .join("references")
+            .join(format!("{}.json", sanitize_for_path(model)))
+    })
+}
+
+/// Loads the reference set stored for `model`, falling back to an empty set.
+pub(crate) fn load_references(model: &str) -> ReferenceSet {
+    let Some(path) = references_path(model) else {
+        return ReferenceSet::for_model(model);
+    };
+    match ReferenceSet::load(&path) {
+        Ok(set) if set.model.as_deref() == Some(model) => set,
+        Ok(set) => {
+            if !set.is_empty() {
+                tracing::warn!("Referentieset {} hoort bij een ander model", path.display());
+            }
+            ReferenceSet::for_model(model)
+        }
+        Err(err) => {
+            tracing::warn!("Referentieset onleesbaar: {err}");
+            ReferenceSet::for_model(model)
+        }
+    }
+}
+
+impl UiApp {
+    /// Adds the labelled rows as examples so the label can be predicted later.
+    ///
+    /// Rows without an embedding of the active model (older cached scans, or
+    /// scans made with another model) are skipped. The
+    /// catch-all "iets sp" label does not describe a single visitor, so it
+    /// only drops the examples the rows contributed earlier.
+    pub(crate) fn remember_reference_examples(&mut self, indices: &[usize], canonical: &str) {
+        if canonical.is_empty() || canonical == SOMETHING_LABEL {
+            let mut removed = false;
+            for &idx in indices {
+                if let Some(info) = self.rijen.get(idx) {
+                    removed |= self.references.remove_source(&info.file);
+                }
+            }
+            if removed {
+                self.persist_references();
+            }
+            return;
+        }
+        let mut added = false;
+        for &idx in indices {
+            let Some(info) = self.rijen.get(idx) else {
+                continue;
+            };
+            let Some(embedding) = info.embedding.clone() else {
+                continue;
+            };
+            if info.embedding_model != self.references.model {
+                continue;
+            }
+            self.references
+                .add(canonical, embedding, Some(info.file.clone()));
+            added = true;
+        }
+        if added {
+            self.persist_references();
+        }
+    }
+
+    /// Removes every stored example.
+    pub(crate) fn clear_references(&mut self) {
+        self.references = ReferenceSet::for_model(self.active_model_id());
+        self.persist_references();
+    }
+
+    /// Switches to the reference set of the active model.
+    pub(crate) fn reload_references(&mut self) {
+        let model = self.active_model_id();
+        if self.references.model.as_deref() != Some(model.as_str()) {
+            self.references = load_references(&model);
+        }
+    }
+
+    /// Makes labels that only exist in the reference set selectable again.
+    pub(crate) fn register_reference_labels(&mut self) {
+        for (canonical, _) in self.references.label_counts() {
+            if self
+                .label_options
+                .iter()
+                .any(|option| option.canonical == canonical)
+            {
+                continue;
+            }
+            let mut display = canonical.clone();
+            if let Some(first) = display.get_mut(0..1) {
+                first.make_ascii_uppercase();
+            }
+            self.label_options.push(LabelOption {
+                canonical,
+                display,
+                translations: HashMap::new(),
+                scientific: None,
+            });
+        }
+    }
+
+    fn persist_references(&self) {
+        let Some(path) = self.references.model.as_deref().and_then(references_path) else {
+            return;
+        };
+        if let Err(err) = self.references.save(&path) {
+            tracing::warn!("Referentieset kon niet worden opgeslagen: {err}");
+        }
+    }
+
+    /// Renders the overview of stored examples in the settings panel.
+    pub(super) fn render_reference_section(&mut self, ui: &mut egui::Ui) {
+        ui.heading(self.t("settings-references"));
+        ui.label(self.t("settings-references-help"));
+        ui.add_space(4.0);
+        let counts = self.references.label_counts();
+        if counts.is_empty() {
+            ui.label(self.t("settings-references-empty"));
+            return;
+        }
+        let min_examples = KnnConfig::default().min_examples;
+        for (canonical, count) in counts {
+            let mut args = crate::i18n::Args::new();
+            args.insert("label".into(), self.display_for(&canonical).into());
+            args.insert("count".into(), (count as u64).into());
+            let key = if count >= min_examples {
+                "settings-references-count"
+            } else {
+                "settings-references-count-pending"
+            };
+            ui.label(self.t_args(key, &args));
+        }
+        ui.add_space(4.0);
+        if ui.button(self.t("settings-references-clear")).clicked() {
+            self.clear_references();
+            self.status = self.t("settings-references-cleared");
+        }
+    }
+}
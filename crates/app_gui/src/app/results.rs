@@ -26,15 +26,15 @@ impl UiApp {
     pub(super) fn thumbnail_caption(&self, info: &ImageInfo) -> String {
         match &info.classification {
             Some(classification) => {
-                let mut label = match &classification.decision {
+                let label = match &classification.decision {
                     Decision::Label(name) => self.display_for(name),
                     Decision::Unknown => self.t("label-empty"),
                 };
-                if matches!(&classification.decision, Decision::Label(name) if name.ends_with(" (manueel)"))
-                {
-                    label.push_str(&self.t("label-manual-suffix"));
+                if classification.is_manual() {
+                    format!("{label}{}", self.t("label-manual-suffix"))
+                } else {
+                    format!("{label} ({:.1}%)", classification.confidence * 100.0)
                 }
-                format!("{label} ({:.1}%)", classification.confidence * 100.0)
             }
             None => self.t("label-no-classification"),
         }
@@ -54,7 +54,12 @@ impl UiApp {
                 .file_name()
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_else(|| info.file.to_string_lossy().to_string());
-            let caption = self.thumbnail_caption(info);
+            let mut caption = self.thumbnail_caption(info);
+            if self.view == ViewMode::Vergelijkbaar
+                && let Some(score) = self.similarity_for(idx)
+            {
+                caption.push_str(&format!(" · ≈{:.0}%", score.max(0.0) * 100.0));
+            }
             (info.file.clone(), label, caption)
         };
 
@@ -131,6 +136,10 @@ impl UiApp {
         }
 
         let (count_present, count_empty, count_unsure) = self.view_counts();
+        let count_similar = self
+            .similar_view
+            .as_ref()
+            .map(|_| self.similar_indices().len());
         ui.horizontal(|ui| {
             let present_btn = ui.selectable_label(
                 self.view == ViewMode::Aanwezig,
@@ -162,6 +171,25 @@ impl UiApp {
                 self.reset_selection();
                 self.current_page = 0;
             }
+            if let Some(count_similar) = count_similar {
+                let similar_btn = ui.selectable_label(
+                    self.view == ViewMode::Vergelijkbaar,
+                    format!("{} ({count_similar})", self.t("tab-similar")),
+                );
+                if similar_btn.clicked() {
+                    self.view = ViewMode::Vergelijkbaar;
+                    self.reset_thumbnail_cache();
+                    self.reset_selection();
+                    self.current_page = 0;
+                }
+                if ui
+                    .small_button("✕")
+                    .on_hover_text(self.t("results-similar-close"))
+                    .clicked()
+                {
+                    self.close_similar_view();
+                }
+            }
         });
 
         let filtered = self.filtered_indices();
@@ -257,6 +285,18 @@ impl UiApp {
             self.export_selected_images(indices);
             ui.close();
         }
+        if ui.button(self.t("context-find-similar")).clicked() {
+            self.find_similar(indices);
+            ui.close();
+        }
+        if ui
+            .button(self.t("context-write-xmp"))
+            .on_hover_text(self.t("context-write-xmp-help"))
+            .clicked()
+        {
+            self.write_xmp_sidecars(indices);
+            ui.close();
+        }
         ui.separator();
         if ui.button(self.t("context-mark-background")).clicked() {
             self.assign_manual_category(indices, "achtergrond".into(), false);
@@ -6,6 +6,9 @@ use eframe::egui;
 impl UiApp {
     /// Returns the indices that should be shown for the requested view mode.
     pub(super) fn indices_for_view(&self, view: ViewMode) -> Vec<usize> {
+        if view == ViewMode::Vergelijkbaar {
+            return self.similar_indices();
+        }
         self.rijen
             .iter()
             .enumerate()
@@ -133,12 +133,29 @@ impl UiApp {
         ui.checkbox(&mut self.improve_recognition, improve_label);
         ui.label(self.t("settings-improve-help"));
         ui.add_space(6.0);
-        ui.horizontal(|ui| {
-            ui.label(self.t("settings-roboflow-dataset"));
-            ui.text_edit_singleline(&mut self.roboflow_dataset_input);
-        });
+        self.render_annotation_settings(ui);
         ui.add_space(4.0);
-        ui.label(self.t("settings-roboflow-note"));
+        self.render_upload_queue_section(ui);
+
+        ui.add_space(16.0);
+        ui.separator();
+        ui.add_space(6.0);
+        self.render_reference_section(ui);
+
+        ui.add_space(16.0);
+        ui.separator();
+        ui.add_space(6.0);
+        self.render_personal_model_section(ui);
+
+        ui.add_space(16.0);
+        ui.separator();
+        ui.add_space(6.0);
+        self.render_evaluation_section(ui);
+
+        ui.add_space(16.0);
+        ui.separator();
+        ui.add_space(6.0);
+        self.render_installed_models_section(ui);
 
         ui.add_space(16.0);
         ui.separator();
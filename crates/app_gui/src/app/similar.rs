@@ -0,0 +1,172 @@
+//! "Find similar images" ranking and the temporary gallery that shows it.
+
+use super::{Panel, UiApp, ViewMode};
+use feeder_core::{EfficientVitClassifier, ImageInfo, rank_by_similarity};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, TryRecvError};
+use std::thread;
+
+/// Ranked rows shown in the temporary "Vergelijkbaar" gallery.
+pub(crate) struct SimilarView {
+    ranked: Vec<usize>,
+    scores: HashMap<usize, f32>,
+}
+
+/// Messages from the background job that fills in missing embeddings.
+pub(crate) enum EmbeddingMsg {
+    Progress(usize, usize),
+    Done(Vec<ImageInfo>),
+    Error(String),
+}
+
+impl UiApp {
+    /// Ranks all rows by similarity to `indices` and opens the similar gallery.
+    ///
+    /// Rows from older cached scans may lack embeddings, and rows scanned with
+    /// another model hold embeddings that cannot be compared; those are
+    /// computed in the background first and the ranking follows once they
+    /// arrive.
+    pub(crate) fn find_similar(&mut self, indices: &[usize]) {
+        if indices.is_empty() {
+            return;
+        }
+        if self.embedding_rx.is_some() {
+            self.status = self.t("similar-busy");
+            return;
+        }
+        let cfg = self.classifier_config();
+        let mut missing: Vec<ImageInfo> = self
+            .rijen
+            .iter()
+            .filter(|info| info.embedding.is_none() || info.embedding_model != cfg.model_id)
+            .cloned()
+            .collect();
+        if missing.is_empty() {
+            self.show_similar(indices);
+            return;
+        }
+
+        self.similar_query = indices.to_vec();
+        let (tx, rx) = mpsc::channel();
+        self.embedding_rx = Some(rx);
+        self.status = self.t("similar-computing");
+        let language = self.language;
+        thread::spawn(move || {
+            let classifier = match EfficientVitClassifier::new(&cfg) {
+                Ok(c) => c,
+                Err(e) => {
+                    let _ = tx.send(EmbeddingMsg::Error(format!(
+                        "{}: {e}",
+                        crate::i18n::t_for(language, "model-load-failed")
+                    )));
+                    return;
+                }
+            };
+            let tx_progress = tx.clone();
+            if let Err(e) = classifier.embed_with_progress(&mut missing, |done, total| {
+                let _ = tx_progress.send(EmbeddingMsg::Progress(done, total));
+            }) {
+                let _ = tx.send(EmbeddingMsg::Error(format!(
+                    "{}: {e}",
+                    crate::i18n::t_for(language, "similar-failed")
+                )));
+                return;
+            }
+            let _ = tx.send(EmbeddingMsg::Done(missing));
+        });
+    }
+
+    /// Applies embeddings computed in the background and shows the ranking.
+    pub(crate) fn poll_embedding_job(&mut self) {
+        let Some(rx) = self.embedding_rx.take() else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(EmbeddingMsg::Progress(done, total)) => {
+                    self.status = format!("{}: {done} / {total}", self.t("similar-computing"));
+                }
+                Ok(EmbeddingMsg::Done(embedded)) => {
+                    let by_path: HashMap<PathBuf, ImageInfo> = embedded
+                        .into_iter()
+                        .filter(|info| info.embedding.is_some())
+                        .map(|info| (info.file.clone(), info))
+                        .collect();
+                    for info in &mut self.rijen {
+                        if let Some(new_info) = by_path.get(&info.file) {
+                            info.embedding = new_info.embedding.clone();
+                            info.embedding_model = new_info.embedding_model.clone();
+                        }
+                    }
+                    self.save_cache_for_current_folder();
+                    let query = std::mem::take(&mut self.similar_query);
+                    self.show_similar(&query);
+                    return;
+                }
+                Ok(EmbeddingMsg::Error(message)) => {
+                    self.similar_query.clear();
+                    self.status = message;
+                    return;
+                }
+                Err(TryRecvError::Empty) => {
+                    self.embedding_rx = Some(rx);
+                    return;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    self.similar_query.clear();
+                    self.status = self.t("similar-failed");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Switches the results grid to the ranking for `query`.
+    fn show_similar(&mut self, query: &[usize]) {
+        let ranked = rank_by_similarity(&self.rijen, query);
+        if ranked.is_empty() {
+            self.status = self.t("similar-no-features");
+            return;
+        }
+        let mut args = crate::i18n::Args::new();
+        args.insert("count".into(), (ranked.len() as u64).into());
+        self.status = self.t_args("similar-found", &args);
+        self.similar_view = Some(SimilarView {
+            ranked: ranked.iter().map(|(idx, _)| *idx).collect(),
+            scores: ranked.into_iter().collect(),
+        });
+        self.view = ViewMode::Vergelijkbaar;
+        self.panel = Panel::Results;
+        self.current_page = 0;
+        self.reset_thumbnail_cache();
+        self.reset_selection();
+    }
+
+    /// Drops the similar gallery and returns to the regular tabs.
+    pub(crate) fn close_similar_view(&mut self) {
+        self.similar_view = None;
+        self.similar_query.clear();
+        if self.view == ViewMode::Vergelijkbaar {
+            self.view = ViewMode::Aanwezig;
+            self.current_page = 0;
+            self.reset_thumbnail_cache();
+            self.reset_selection();
+        }
+    }
+
+    /// Returns the ranked row indices of the similar gallery.
+    pub(crate) fn similar_indices(&self) -> Vec<usize> {
+        self.similar_view
+            .as_ref()
+            .map(|similar| similar.ranked.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the similarity score of a row in the similar gallery.
+    pub(crate) fn similarity_for(&self, idx: usize) -> Option<f32> {
+        self.similar_view
+            .as_ref()
+            .and_then(|similar| similar.scores.get(&idx).copied())
+    }
+}
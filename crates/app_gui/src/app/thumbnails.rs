@@ -115,7 +115,7 @@ impl UiApp {
         }
     }
 
-    fn queue_thumbnail(&mut self, path: &Path) {
+    pub(crate) fn queue_thumbnail(&mut self, path: &Path) {
         if self.thumbs.contains_key(path)
             || self.thumb_inflight.contains(path)
             || self.thumb_failed.contains(path)
@@ -0,0 +1,379 @@
+//! Queueing manual corrections for upload and showing the queue in settings.
+
+use super::{ROBOFLOW_API_KEY, UiApp};
+use crate::annotation::SinkKind;
+use crate::privacy::{
+    ShareRecord, append_share_record, read_share_log, share_log_path, stripped_copy,
+};
+use crate::upload_queue::{
+    PendingUpload, PermanentError, UploadEvent, UploadQueue, Uploader, upload_queue_path,
+};
+use anyhow::Context;
+use eframe::egui;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver};
+
+/// Audit log entries listed in the review window.
+const SHARE_HISTORY_SHOWN: usize = 20;
+/// Thumbnail edge in the review window.
+const REVIEW_THUMB_SIZE: f32 = 96.0;
+
+/// Opens the persistent queue; confirmed uploads from a previous session resume.
+///
+/// Every upload goes to the backend that was configured when the photo was
+/// queued, so a later settings change does not redirect it. Only a copy
+/// without metadata leaves the machine, and each share is added to the audit
+/// log.
+pub(super) fn open_upload_queue() -> (UploadQueue, Receiver<UploadEvent>) {
+    let uploader: Uploader = Arc::new(move |item: &mut PendingUpload| {
+        let settings = item.confirmed_target()?.clone();
+        let sink = settings
+            .sink(ROBOFLOW_API_KEY)
+            .map_err(|key| PermanentError(format!("Annotatie-backend niet ingesteld ({key})")))?;
+        let temp = tempfile::tempdir().context("Tijdelijke map aanmaken")?;
+        // A photo that already reached the backend only needs its label.
+        let copy = match item.remote_id {
+            Some(_) => item.path.clone(),
+            None => stripped_copy(&item.path, temp.path())?,
+        };
+        sink.upload(&copy, &item.label, &mut item.remote_id)?;
+        let record = ShareRecord {
+            shared_at: chrono::Local::now().to_rfc3339(),
+            file: item.path.clone(),
+            label: item.label.clone(),
+            destination: settings.destination(),
+        };
+        if let Some(log) = share_log_path()
+            && let Err(err) = append_share_record(&log, &record)
+        {
+            tracing::warn!("Auditlog kon niet worden bijgewerkt: {err:#}");
+        }
+        Ok(())
+    });
+    let (tx, rx) = mpsc::channel();
+    (UploadQueue::open(upload_queue_path(), uploader, tx), rx)
+}
+
+impl UiApp {
+    /// Adds manually labelled photos to the upload queue.
+    pub(crate) fn queue_manual_uploads(&mut self, paths: Vec<PathBuf>, canonical: &str) {
+        if let Err(key) = self.annotation.sink(ROBOFLOW_API_KEY) {
+            self.status = self.t(key);
+        } else if paths.is_empty() {
+            self.status = self.t("roboflow-no-photos");
+        } else {
+            self.upload_queue.enqueue(
+                paths.into_iter().map(|path| (path, canonical.to_string())),
+                &self.annotation,
+            );
+            let mut args = crate::i18n::Args::new();
+            args.insert(
+                "count".into(),
+                (self.upload_queue.status().awaiting_review as u64).into(),
+            );
+            self.status = self.t_args("upload-review-queued", &args);
+        }
+    }
+
+    /// Backend choice with the fields that backend needs.
+    pub(super) fn render_annotation_settings(&mut self, ui: &mut egui::Ui) {
+        let before = self.annotation.clone();
+        ui.horizontal(|ui| {
+            ui.label(self.t("annotation-sink"));
+            egui::ComboBox::from_id_salt("annotation_sink")
+                .selected_text(self.t(self.annotation.kind.label_key()))
+                .show_ui(ui, |ui| {
+                    for kind in SinkKind::ALL {
+                        let label = self.t(kind.label_key());
+                        ui.selectable_value(&mut self.annotation.kind, kind, label);
+                    }
+                });
+        });
+        let fields: Vec<(&str, &mut String, bool)> = match self.annotation.kind {
+            SinkKind::Roboflow => vec![
+                ("annotation-url", &mut self.annotation.roboflow_url, false),
+                (
+                    "settings-roboflow-dataset",
+                    &mut self.annotation.roboflow_dataset,
+                    false,
+                ),
+                (
+                    "annotation-roboflow-key",
+                    &mut self.annotation.roboflow_key,
+                    true,
+                ),
+            ],
+            SinkKind::LabelStudio => vec![
+                (
+                    "annotation-url",
+                    &mut self.annotation.label_studio_url,
+                    false,
+                ),
+                (
+                    "annotation-label-studio-project",
+                    &mut self.annotation.label_studio_project,
+                    false,
+                ),
+                (
+                    "annotation-token",
+                    &mut self.annotation.label_studio_token,
+                    true,
+                ),
+            ],
+            SinkKind::Http => vec![
+                ("annotation-url", &mut self.annotation.http_url, false),
+                (
+                    "annotation-token-optional",
+                    &mut self.annotation.http_token,
+                    true,
+                ),
+            ],
+        };
+        let language = self.language;
+        egui::Grid::new("annotation_fields")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (key, value, secret) in fields {
+                    ui.label(crate::i18n::t_for(language, key));
+                    ui.add(egui::TextEdit::singleline(value).password(secret));
+                    ui.end_row();
+                }
+            });
+        ui.label(self.t(match self.annotation.kind {
+            SinkKind::Roboflow => "settings-roboflow-note",
+            SinkKind::LabelStudio => "annotation-label-studio-help",
+            SinkKind::Http => "annotation-http-help",
+        }));
+        ui.label(self.t("annotation-secrets-note"));
+        if self.annotation != before {
+            self.persist_settings();
+        }
+    }
+
+    /// Reports finished and failed uploads in the status bar.
+    pub(crate) fn poll_upload_events(&mut self) {
+        let mut uploaded = 0u64;
+        let mut failure = None;
+        while let Ok(event) = self.upload_events.try_recv() {
+            match event {
+                UploadEvent::Uploaded => uploaded += 1,
+                UploadEvent::Failed(err) => failure = Some(err),
+            }
+        }
+        if let Some(err) = failure {
+            self.status = format!("{}: {err}", self.t("roboflow-upload-retrying"));
+        } else if uploaded == 1 {
+            self.status = self.t("roboflow-uploaded-one");
+        } else if uploaded > 1 {
+            self.status = format!("{uploaded} {}", self.t("roboflow-uploaded-many"));
+        }
+    }
+
+    /// Shows the pending uploads with "retry now" and "clear queue" actions.
+    pub(super) fn render_upload_queue_section(&mut self, ui: &mut egui::Ui) {
+        let status = self.upload_queue.status();
+        if status.awaiting_review > 0 {
+            ui.horizontal(|ui| {
+                let mut args = crate::i18n::Args::new();
+                args.insert("count".into(), (status.awaiting_review as u64).into());
+                ui.label(self.t_args("upload-review-waiting", &args));
+                if ui.button(self.t("upload-review-open")).clicked() {
+                    self.open_upload_review();
+                }
+            });
+        } else if ui.button(self.t("upload-review-history")).clicked() {
+            self.open_upload_review();
+        }
+        if status.pending == 0 {
+            ui.label(self.t("upload-queue-empty"));
+            return;
+        }
+        let mut args = crate::i18n::Args::new();
+        args.insert("pending".into(), (status.pending as u64).into());
+        args.insert("uploading".into(), (status.uploading as u64).into());
+        ui.label(self.t_args("upload-queue-status", &args));
+        if status.failing > 0 {
+            let mut args = crate::i18n::Args::new();
+            args.insert("failing".into(), (status.failing as u64).into());
+            args.insert(
+                "seconds".into(),
+                status.next_retry_in.unwrap_or_default().into(),
+            );
+            let label = ui.colored_label(
+                egui::Color32::from_rgb(200, 120, 0),
+                self.t_args("upload-queue-failing", &args),
+            );
+            if let Some(err) = &status.last_error {
+                label.on_hover_text(err);
+            }
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    status.failing > 0,
+                    egui::Button::new(self.t("upload-queue-retry")),
+                )
+                .clicked()
+            {
+                self.upload_queue.retry_now();
+            }
+            if ui.button(self.t("upload-queue-clear")).clicked() {
+                self.upload_queue.clear();
+                self.status = self.t("upload-queue-cleared");
+            }
+        });
+    }
+
+    fn open_upload_review(&mut self) {
+        self.share_history = share_log_path()
+            .map(|path| read_share_log(&path))
+            .unwrap_or_default();
+        self.share_history.reverse();
+        self.share_history.truncate(SHARE_HISTORY_SHOWN);
+        self.upload_review_open = true;
+    }
+
+    /// Lets the user inspect staged photos and confirm or remove them before
+    /// anything is sent, and lists what was shared recently.
+    pub(crate) fn render_upload_review_window(&mut self, ctx: &egui::Context) {
+        if !self.upload_review_open {
+            return;
+        }
+        let items = self.upload_queue.awaiting_review();
+        for item in &items {
+            self.queue_thumbnail(&item.path);
+        }
+        let mut open = true;
+        let mut confirm = Vec::new();
+        let mut remove = Vec::new();
+        // Each item goes where it was queued for; name every such destination.
+        let mut destinations: Vec<String> = items
+            .iter()
+            .filter_map(PendingUpload::destination)
+            .collect();
+        destinations.sort();
+        destinations.dedup();
+        let several_destinations = destinations.len() > 1;
+        let mut args = crate::i18n::Args::new();
+        let destination = if destinations.is_empty() {
+            self.annotation.destination()
+        } else {
+            destinations.join(", ")
+        };
+        args.insert("destination".into(), destination.into());
+        let consent = self.t_args("upload-review-consent", &args);
+        egui::Window::new(self.t("upload-review-title"))
+            .collapsible(false)
+            .default_width(560.0)
+            .default_height(520.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(consent);
+                ui.add_space(6.0);
+                egui::ScrollArea::vertical()
+                    .id_salt("upload_review_items")
+                    .max_height(340.0)
+                    .show(ui, |ui| {
+                        if items.is_empty() {
+                            ui.label(self.t("upload-review-empty"));
+                        }
+                        for item in &items {
+                            ui.horizontal(|ui| {
+                                let size = egui::vec2(REVIEW_THUMB_SIZE, REVIEW_THUMB_SIZE);
+                                match self.thumb_texture_id(&item.path) {
+                                    Some(id) => {
+                                        ui.add(egui::Image::new((id, size)));
+                                    }
+                                    None => {
+                                        ui.add_sized(size, egui::Spinner::new());
+                                    }
+                                }
+                                ui.vertical(|ui| {
+                                    let name = item
+                                        .path
+                                        .file_name()
+                                        .map(|name| name.to_string_lossy().to_string())
+                                        .unwrap_or_default();
+                                    ui.hyperlink_to(
+                                        name,
+                                        format!("file://{}", item.path.display()),
+                                    )
+                                    .on_hover_text(item.path.display().to_string());
+                                    ui.label(self.display_for(&item.label));
+                                    if several_destinations {
+                                        ui.weak(item.destination().unwrap_or_default());
+                                    }
+                                    ui.horizontal(|ui| {
+                                        if ui.button(self.t("upload-review-share")).clicked() {
+                                            confirm.push(item.id);
+                                        }
+                                        if ui.button(self.t("upload-review-remove")).clicked() {
+                                            remove.push(item.id);
+                                        }
+                                    });
+                                });
+                            });
+                            ui.separator();
+                        }
+                    });
+                ui.horizontal(|ui| {
+                    let any = !items.is_empty();
+                    if ui
+                        .add_enabled(any, egui::Button::new(self.t("upload-review-share-all")))
+                        .clicked()
+                    {
+                        confirm.extend(items.iter().map(|item| item.id));
+                    }
+                    if ui
+                        .add_enabled(any, egui::Button::new(self.t("upload-review-remove-all")))
+                        .clicked()
+                    {
+                        remove.extend(items.iter().map(|item| item.id));
+                    }
+                });
+                ui.add_space(8.0);
+                ui.label(egui::RichText::new(self.t("upload-review-history")).strong());
+                if self.share_history.is_empty() {
+                    ui.label(self.t("upload-review-history-empty"));
+                }
+                egui::Grid::new("upload_review_history")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for record in &self.share_history {
+                            ui.label(record.shared_at.get(..16).unwrap_or(&record.shared_at));
+                            ui.label(
+                                record
+                                    .file
+                                    .file_name()
+                                    .map(|name| name.to_string_lossy().to_string())
+                                    .unwrap_or_default(),
+                            );
+                            ui.label(self.display_for(&record.label));
+                            ui.label(&record.destination);
+                            ui.end_row();
+                        }
+                    });
+                if let Some(log) = share_log_path().filter(|path| path.exists()) {
+                    ui.hyperlink_to(
+                        self.t("upload-review-open-log"),
+                        format!("file://{}", log.display()),
+                    );
+                }
+            });
+        if !confirm.is_empty() {
+            let approved: Vec<(u64, String)> = items
+                .iter()
+                .filter(|item| confirm.contains(&item.id))
+                .map(|item| (item.id, item.destination().unwrap_or_default()))
+                .collect();
+            self.upload_queue.confirm(&approved);
+            self.status = self.t("roboflow-uploading");
+        }
+        if !remove.is_empty() {
+            self.upload_queue.remove(&remove);
+        }
+        self.upload_review_open = open;
+    }
+}
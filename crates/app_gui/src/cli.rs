@@ -0,0 +1,173 @@
+//! Command-line subcommands that run without opening the window.
+//!
+//! `Feedie evaluate <folder> [--out <folder>] [--personal]` evaluates the
+//! installed model on a folder laid out as `<label>/<images>`.
+//! `Feedie install-model <zip> [--activate]` installs a model package from
+//! disk, for machines without network access.
+//!
+//! Release builds on Windows use the GUI subsystem and get no console of
+//! their own, so the subcommands attach to the console of the shell that
+//! started them before printing anything.
+
+use crate::app::{
+    EVALUATION_DIR_NAME, LABEL_FILE_NAME, MODEL_FILE_NAME, UiApp, model_id, personal_model_dir_for,
+    trained_on,
+};
+use crate::model::{install_model_package, set_active_model};
+use crate::settings_store::load_settings;
+use anyhow::{Context, Result, anyhow};
+use feeder_core::{
+    ClassifierConfig, EfficientVitClassifier, evaluate_samples, samples_from_folder,
+};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Runs the subcommand named in `args`, if any, and returns its exit code.
+///
+/// Returns `None` when no known subcommand was given so the GUI starts.
+pub(crate) fn run(args: &[String]) -> Option<i32> {
+    let command: fn(&[String]) -> Result<()> = match args.get(1).map(String::as_str) {
+        Some("evaluate") => evaluate,
+        Some("install-model") => install_model,
+        _ => return None,
+    };
+    attach_parent_console();
+    let result = command(&args[2..]);
+    Some(match result {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("Fout: {err:#}");
+            1
+        }
+    })
+}
+
+fn evaluate(args: &[String]) -> Result<()> {
+    let folder = positional(args)
+        .map(PathBuf::from)
+        .context("Gebruik: Feedie evaluate <map> [--out <map>] [--personal]")?;
+    let out_dir = value_for(args, "--out")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| folder.join(EVALUATION_DIR_NAME));
+
+    let (model_root, active_model_dir, _) = UiApp::prepare_model_dir();
+    let model_dir = if args.iter().any(|arg| arg == "--personal") {
+        let dir = personal_model_dir_for(&model_root, &active_model_dir);
+        let base = model_id(&model_root, &active_model_dir);
+        if dir.exists() && !trained_on(&dir, &base) {
+            return Err(anyhow!(
+                "Het persoonlijke model in {} is niet getraind op het actieve model {base}",
+                dir.display()
+            ));
+        }
+        dir
+    } else {
+        active_model_dir
+    };
+    let model_path = model_dir.join(MODEL_FILE_NAME);
+    if !model_path.exists() {
+        return Err(anyhow!("Model ontbreekt: {}", model_path.display()));
+    }
+    let cfg = ClassifierConfig {
+        model_path,
+        labels_path: model_dir.join(LABEL_FILE_NAME),
+        background_labels: load_settings().background_labels,
+        ..Default::default()
+    };
+
+    let samples = samples_from_folder(&folder)?;
+    if samples.is_empty() {
+        return Err(anyhow!(
+            "Geen afbeeldingen gevonden in submappen van {}",
+            folder.display()
+        ));
+    }
+    let classifier = EfficientVitClassifier::new(&cfg)?;
+    let report = evaluate_samples(
+        &classifier,
+        &samples,
+        &cfg.background_labels,
+        |done, total| {
+            eprint!("\r{done} / {total}");
+            let _ = std::io::stderr().flush();
+        },
+    )?;
+    eprintln!();
+    let html = report.save(&out_dir)?;
+
+    println!(
+        "Nauwkeurigheid: {:.1}% ({} / {})",
+        report.accuracy * 100.0,
+        report.correct,
+        report.total
+    );
+    for class in &report.per_class {
+        let pct = |value: Option<f32>| {
+            value
+                .map(|v| format!("{:.1}%", v * 100.0))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        println!(
+            "  {:<24} n={:<5} precisie={:<7} recall={}",
+            class.label,
+            class.support,
+            pct(class.precision),
+            pct(class.recall)
+        );
+    }
+    println!("Rapport: {}", html.display());
+    Ok(())
+}
+
+fn install_model(args: &[String]) -> Result<()> {
+    let archive = positional(args)
+        .map(PathBuf::from)
+        .context("Gebruik: Feedie install-model <zip> [--activate]")?;
+    let (model_root, _, _) = UiApp::prepare_model_dir();
+    let id = install_model_package(&archive, &model_root, env!("FEEDIE_VERSION"))?;
+    println!("Model geïnstalleerd: {id}");
+    if args.iter().any(|arg| arg == "--activate") {
+        set_active_model(&model_root, &id)?;
+        println!("Actief model: {id}");
+    }
+    Ok(())
+}
+
+/// Connects stdout and stderr to the console of the parent process, such as
+/// `cmd` or PowerShell, when this build has no console of its own.
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    // SAFETY: AttachConsole has no preconditions; it fails harmlessly when
+    // the process already has a console or the parent has none.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}
+
+/// Returns the first argument that is neither a flag nor a flag value.
+fn positional(args: &[String]) -> Option<&str> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--out" {
+            iter.next();
+        } else if !arg.starts_with("--") {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+fn value_for<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|idx| args.get(idx + 1))
+        .map(String::as_str)
+}
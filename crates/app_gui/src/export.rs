@@ -1,21 +1,35 @@
 //! Export workflow for saving selections and CSV data.
 
-use crate::app::{LabelOption, ROBOFLOW_API_KEY, UiApp};
-use crate::roboflow::upload_to_roboflow;
+use crate::app::{LabelOption, UiApp};
 use crate::util::{
     canonical_label, extract_timestamp, fallback_display_label, next_available_export_path,
-    parse_coordinates, sanitize_for_path,
+    now_secs, parse_coordinates, sanitize_for_path,
 };
+use crate::xmp::{XmpLabel, tag_export_copy};
 use anyhow::Context;
 use arboard::Clipboard;
 use chrono::{DateTime, Local};
 use eframe::egui;
-use feeder_core::{Classification, Decision, ImageInfo};
+use feeder_core::{
+    Classification, Decision, FrameRecord, ImageInfo, Provenance, RecordFormat, RecordOptions,
+    capture_time, export_megadetector_json, frame_records, write_records,
+};
 use rfd::FileDialog;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+mod camtrap;
+mod dwca;
+mod ebird;
+mod label_csv;
+mod timelapse;
+
+use self::camtrap::write_camtrap_dp;
+use self::dwca::write_dwca;
+use self::ebird::write_ebird_checklists;
+use self::timelapse::write_timelapse_csv;
+
 /// Controls which subsets of photos will be exported.
 #[derive(Clone)]
 /// User-facing export toggles expanded into actionable options.
@@ -24,6 +38,36 @@ struct ExportOptions {
     include_uncertain: bool,
     include_background: bool,
     include_csv: bool,
+    include_camtrap: bool,
+    include_dwca: bool,
+    include_timelapse: bool,
+    include_megadetector: bool,
+    include_ebird: bool,
+    include_xmp: bool,
+    /// Format of the versioned frame records, when they are written.
+    records: Option<RecordFormat>,
+}
+
+impl ExportOptions {
+    /// Whether any selected output records the camera coordinates.
+    fn needs_coordinates(&self) -> bool {
+        self.include_csv || self.writes_data_package()
+    }
+
+    /// Whether a data package covering the whole scan is written.
+    fn writes_data_package(&self) -> bool {
+        self.include_camtrap || self.include_dwca || self.include_ebird
+    }
+
+    /// Whether any output describes every frame of the scan.
+    fn covers_whole_scan(&self) -> bool {
+        self.writes_data_package() || self.include_timelapse
+    }
+
+    /// Whether any file describing the scan is written besides photo copies.
+    fn writes_scan_files(&self) -> bool {
+        self.covers_whole_scan() || self.include_megadetector || self.records.is_some()
+    }
 }
 
 /// Represents an export that still requires user input before it can run.
@@ -38,6 +82,9 @@ pub(crate) struct PendingExport {
 struct ExportOutcome {
     copied: usize,
     wrote_csv: bool,
+    /// Data packages (Camtrap DP folder, DwC-A zip, Timelapse and eBird CSV)
+    /// that were written.
+    packages: Vec<PathBuf>,
     target_dir: PathBuf,
 }
 
@@ -48,6 +95,8 @@ struct ExportJob {
     folder_label: String,
     canonical_label: Option<String>,
     include_in_csv: bool,
+    /// Keywords to embed in the copy when XMP tagging is enabled.
+    xmp: Option<XmpLabel>,
 }
 
 /// Form state for the CSV coordinate prompt.
@@ -57,6 +106,86 @@ pub(crate) struct CoordinatePrompt {
     pub(crate) error: Option<String>,
 }
 
+/// Camera site of a scan, shared by the data package exports.
+pub(crate) struct ExportSite {
+    /// Lower-case identifier derived from the folder name.
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) latitude: f64,
+    pub(crate) longitude: f64,
+}
+
+/// Name shown in the frame records format picker.
+fn record_format_name(format: RecordFormat) -> &'static str {
+    match format {
+        RecordFormat::Csv => "CSV",
+        RecordFormat::JsonLines => "JSON Lines",
+    }
+}
+
+/// Lower-case identifier with only letters, digits and dashes.
+pub(crate) fn slug(name: &str) -> String {
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+        .collect();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "feedie".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Relative path of `file` below `root` with `/` separators.
+pub(crate) fn relative_key(root: &Path, file: &Path) -> String {
+    file.strip_prefix(root)
+        .unwrap_or(file)
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// What a frame shows, as used by the standard data exports.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ObservationKind {
+    Animal {
+        label: String,
+        scientific: Option<String>,
+    },
+    Blank,
+    Unknown,
+}
+
+/// Classification of one scanned frame, independent of the export format.
+#[derive(Clone, Debug)]
+pub(crate) struct FrameObservation {
+    pub(crate) file: PathBuf,
+    pub(crate) timestamp: DateTime<Local>,
+    pub(crate) kind: ObservationKind,
+    /// Set when the label was assigned by the user.
+    pub(crate) manual: bool,
+    /// Model probability; `None` for manual labels.
+    pub(crate) confidence: Option<f32>,
+    /// Individuals on the frame: the number of imported animal boxes, or 1.
+    pub(crate) count: u32,
+}
+
+/// First and last frame time, or `None` when there are no frames.
+pub(crate) fn date_range(
+    frames: &[FrameObservation],
+) -> Option<(DateTime<Local>, DateTime<Local>)> {
+    let start = frames.iter().map(|frame| frame.timestamp).min()?;
+    let end = frames.iter().map(|frame| frame.timestamp).max()?;
+    Some((start, end))
+}
+
 /// CSV record that mirrors a single exported observation.
 /// In-memory representation of a CSV row.
 struct CsvRecord {
@@ -87,6 +216,42 @@ impl UiApp {
         if csv_checkbox.clicked() && self.export_csv {
             self.export_present = true;
         }
+        let camtrap_label = self.t("export-include-camtrap");
+        ui.checkbox(&mut self.export_camtrap, camtrap_label)
+            .on_hover_text(self.t("export-camtrap-help"));
+        let dwca_label = self.t("export-include-dwca");
+        ui.checkbox(&mut self.export_dwca, dwca_label)
+            .on_hover_text(self.t("export-dwca-help"));
+        let timelapse_label = self.t("export-include-timelapse");
+        ui.checkbox(&mut self.export_timelapse, timelapse_label)
+            .on_hover_text(self.t("export-timelapse-help"));
+        let megadetector_label = self.t("export-include-megadetector");
+        ui.checkbox(&mut self.export_megadetector, megadetector_label)
+            .on_hover_text(self.t("export-megadetector-help"));
+        let ebird_label = self.t("export-include-ebird");
+        ui.checkbox(&mut self.export_ebird, ebird_label)
+            .on_hover_text(self.t("export-ebird-help"));
+        let xmp_label = self.t("export-include-xmp");
+        ui.checkbox(&mut self.export_xmp, xmp_label)
+            .on_hover_text(self.t("export-xmp-help"));
+        ui.horizontal(|ui| {
+            let records_label = self.t("export-include-records");
+            ui.checkbox(&mut self.export_records, records_label)
+                .on_hover_text(self.t("export-records-help"));
+            ui.add_enabled_ui(self.export_records, |ui| {
+                egui::ComboBox::from_id_salt("export_records_format")
+                    .selected_text(record_format_name(self.export_records_format))
+                    .show_ui(ui, |ui| {
+                        for format in [RecordFormat::Csv, RecordFormat::JsonLines] {
+                            ui.selectable_value(
+                                &mut self.export_records_format,
+                                format,
+                                record_format_name(format),
+                            );
+                        }
+                    });
+            });
+        });
 
         ui.add_space(12.0);
         let can_export = self.can_export_from_panel();
@@ -97,6 +262,33 @@ impl UiApp {
         if !can_export {
             ui.label(self.t("export-select-category"));
         }
+        ui.add_space(6.0);
+        if ui
+            .button(self.t("labels-csv-import"))
+            .on_hover_text(self.t("labels-csv-import-help"))
+            .clicked()
+        {
+            self.import_labels_csv();
+        }
+        if ui
+            .button(self.t("timelapse-import"))
+            .on_hover_text(self.t("timelapse-import-help"))
+            .clicked()
+        {
+            self.import_timelapse_csv();
+        }
+        if ui
+            .button(self.t("xmp-import"))
+            .on_hover_text(self.t("xmp-import-help"))
+            .clicked()
+        {
+            self.import_xmp_sidecars();
+        }
+
+        ui.add_space(16.0);
+        ui.separator();
+        ui.add_space(6.0);
+        self.render_training_export_section(ui);
     }
 
     /// Determines whether the export button should be enabled.
@@ -104,7 +296,15 @@ impl UiApp {
     fn can_export_from_panel(&self) -> bool {
         self.has_scanned
             && !self.rijen.is_empty()
-            && (self.export_present || self.export_uncertain || self.export_background)
+            && (self.export_present
+                || self.export_uncertain
+                || self.export_background
+                || self.export_camtrap
+                || self.export_dwca
+                || self.export_timelapse
+                || self.export_megadetector
+                || self.export_ebird
+                || self.export_records)
     }
 
     /// Opens the folder picker and prepares a pending export job.
@@ -133,13 +333,20 @@ impl UiApp {
             include_uncertain: self.export_uncertain,
             include_background: self.export_background,
             include_csv: self.export_csv,
+            include_camtrap: self.export_camtrap,
+            include_dwca: self.export_dwca,
+            include_timelapse: self.export_timelapse,
+            include_megadetector: self.export_megadetector,
+            include_ebird: self.export_ebird,
+            include_xmp: self.export_xmp,
+            records: self.export_records.then_some(self.export_records_format),
         };
         let pending = PendingExport {
             target_dir,
             options,
         };
 
-        if pending.options.include_csv {
+        if pending.options.needs_coordinates() {
             self.pending_export = Some(pending);
             self.coordinate_prompt = Some(CoordinatePrompt::default());
         } else {
@@ -170,6 +377,11 @@ impl UiApp {
                 if summary.wrote_csv {
                     message.push_str(&self.t("export-csv-saved-suffix"));
                 }
+                for package in &summary.packages {
+                    let mut args = crate::i18n::Args::new();
+                    args.insert("path".into(), package.display().to_string().into());
+                    message.push_str(&self.t_args("export-package-saved-suffix", &args));
+                }
                 self.status = message;
             }
             Err(err) => {
@@ -188,7 +400,7 @@ impl UiApp {
         self.coordinate_prompt = None;
     }
 
-    /// Collects GPS coordinates from the user before writing CSV exports.
+    /// Collects GPS coordinates from the user before writing CSV or Camtrap DP exports.
     pub(crate) fn render_coordinate_prompt(&mut self, ctx: &egui::Context) {
         if self.coordinate_prompt.is_none() {
             return;
@@ -432,12 +644,12 @@ impl UiApp {
             target_dir,
             options,
         } = pending;
-        if options.include_csv && coords.is_none() {
+        if options.needs_coordinates() && coords.is_none() {
             return Err(anyhow!(self.t("export-coords-missing")));
         }
 
         let jobs = self.collect_export_jobs(&options);
-        if jobs.is_empty() && !options.include_csv {
+        if jobs.is_empty() && !options.include_csv && !options.writes_scan_files() {
             return Err(anyhow!(self.t("export-selection-empty")));
         }
 
@@ -479,23 +691,34 @@ impl UiApp {
                     dest_path.display()
                 )
             })?;
+            if let Some(label) = &job.xmp
+                && let Err(err) = tag_export_copy(&dest_path, label)
+            {
+                tracing::warn!(
+                    "XMP-trefwoorden niet toegevoegd aan {}: {err:#}",
+                    dest_path.display()
+                );
+            }
 
             if job.include_in_csv {
-                let (date, time) = extract_timestamp(&job.source)?;
-                let canonical = job
-                    .canonical_label
-                    .clone()
-                    .unwrap_or_else(|| canonical_label(&job.folder_label));
-                let scientific = self
-                    .scientific_for(&canonical)
-                    .unwrap_or_else(|| job.folder_label.clone());
-                csv_records.push(CsvRecord {
-                    date,
-                    time,
-                    scientific,
-                    path: dest_path.to_string_lossy().into_owned(),
-                });
-                // coords reused later when writing file
+                match extract_timestamp(&job.source) {
+                    Ok((date, time)) => {
+                        let canonical = job
+                            .canonical_label
+                            .clone()
+                            .unwrap_or_else(|| canonical_label(&job.folder_label));
+                        let scientific = self
+                            .scientific_for(&canonical)
+                            .unwrap_or_else(|| job.folder_label.clone());
+                        csv_records.push(CsvRecord {
+                            date,
+                            time,
+                            scientific,
+                            path: dest_path.to_string_lossy().into_owned(),
+                        });
+                    }
+                    Err(err) => tracing::warn!("{err:#}, niet in CSV opgenomen"),
+                }
             }
 
             copied += 1;
@@ -512,9 +735,84 @@ impl UiApp {
             )?;
         }
 
+        let mut packages = Vec::new();
+        let frames = if options.covers_whole_scan() {
+            self.frame_observations()
+        } else {
+            Vec::new()
+        };
+        let stamp = export_time.format("%y%m%d%H%M");
+        if options.include_timelapse
+            && let Some(root) = &self.gekozen_map
+        {
+            let path =
+                next_available_export_path(&target_dir, &format!("timelapse_{stamp}"), "csv");
+            write_timelapse_csv(&path, root, &frames)?;
+            packages.push(path);
+        }
+        if options.include_megadetector
+            && let Some(root) = &self.gekozen_map
+        {
+            let path =
+                next_available_export_path(&target_dir, &format!("megadetector_{stamp}"), "json");
+            export_megadetector_json(&self.rijen, root, &path, &self.background_labels)?;
+            packages.push(path);
+        }
+        if let Some(format) = options.records
+            && let Some(root) = &self.gekozen_map
+        {
+            let path = next_available_export_path(
+                &target_dir,
+                &format!("feedie_records_{stamp}"),
+                format.extension(),
+            );
+            write_records(&self.scan_records(root), &path, format)?;
+            packages.push(path);
+        }
+        if options.writes_data_package()
+            && let Some((latitude, longitude)) = coords
+            && let Some(root) = &self.gekozen_map
+        {
+            let folder_name = root
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| "feedie".to_string());
+            let site = ExportSite {
+                id: slug(&folder_name),
+                name: folder_name,
+                latitude,
+                longitude,
+            };
+            let classified_by = format!(
+                "Feedie model {}",
+                self.describe_scan_model(&self.scan_model)
+            );
+            if options.include_camtrap {
+                let dir = target_dir.join(format!("camtrap-dp_{stamp}"));
+                write_camtrap_dp(&dir, root, &site, &frames, &classified_by, export_time)?;
+                packages.push(dir);
+            }
+            if options.include_dwca {
+                let path = next_available_export_path(&target_dir, &format!("dwca_{stamp}"), "zip");
+                write_dwca(&path, root, &site, &frames, &classified_by, export_time)?;
+                packages.push(path);
+            }
+            if options.include_ebird {
+                let path =
+                    next_available_export_path(&target_dir, &format!("ebird_{stamp}"), "csv");
+                let checklists = write_ebird_checklists(&path, &site, &frames)?;
+                tracing::info!(
+                    "eBird-export: {checklists} checklists naar {}",
+                    path.display()
+                );
+                packages.push(path);
+            }
+        }
+
         Ok(ExportOutcome {
             copied,
             wrote_csv: options.include_csv,
+            packages,
             target_dir,
         })
     }
@@ -523,6 +821,11 @@ impl UiApp {
     fn collect_export_jobs(&self, options: &ExportOptions) -> Vec<ExportJob> {
         let mut jobs = Vec::new();
         for info in &self.rijen {
+            let xmp = if options.include_xmp {
+                self.xmp_label(info)
+            } else {
+                None
+            };
             if options.include_present
                 && info.present
                 && let Some((display, canonical)) = self.present_label(info)
@@ -532,6 +835,7 @@ impl UiApp {
                     folder_label: display,
                     canonical_label: Some(canonical),
                     include_in_csv: options.include_csv,
+                    xmp: xmp.clone(),
                 });
             }
             if options.include_uncertain && self.is_onzeker(info) {
@@ -540,6 +844,7 @@ impl UiApp {
                     folder_label: self.t("tab-uncertain"),
                     canonical_label: None,
                     include_in_csv: false,
+                    xmp: xmp.clone(),
                 });
             }
             if options.include_background && self.belongs_in_leeg(info) {
@@ -548,12 +853,79 @@ impl UiApp {
                     folder_label: self.t("label-empty"),
                     canonical_label: None,
                     include_in_csv: false,
+                    xmp: xmp.clone(),
                 });
             }
         }
         jobs
     }
 
+    /// Classifies every scanned frame for the data exports. Frames without
+    /// an EXIF or file time are left out.
+    pub(crate) fn frame_observations(&self) -> Vec<FrameObservation> {
+        self.rijen
+            .iter()
+            .filter_map(|info| {
+                let Some(timestamp) = capture_time(&info.file) else {
+                    tracing::warn!("Geen opnametijd voor {}, overgeslagen", info.file.display());
+                    return None;
+                };
+                let (manual, confidence) = match &info.classification {
+                    Some(classification) if classification.is_manual() => (true, None),
+                    Some(classification) => (false, Some(classification.confidence)),
+                    None => (false, None),
+                };
+                Some(FrameObservation {
+                    file: info.file.clone(),
+                    timestamp,
+                    kind: self.observation_kind(info),
+                    manual,
+                    confidence,
+                    count: info
+                        .detections
+                        .iter()
+                        .filter(|detection| detection.is_animal())
+                        .count()
+                        .max(1) as u32,
+                })
+            })
+            .collect()
+    }
+
+    /// Builds the versioned frame records for the scan below `root`, with
+    /// the display and scientific names of the current label set.
+    fn scan_records(&self, root: &Path) -> Vec<FrameRecord> {
+        let site = root.file_name().map(|name| slug(&name.to_string_lossy()));
+        let options = RecordOptions {
+            root: root.to_path_buf(),
+            model_version: Some(self.describe_scan_model(&self.scan_model)),
+            site,
+            ..RecordOptions::default()
+        };
+        let mut records = frame_records(&self.rijen, &options);
+        for record in &mut records {
+            if let Some(canonical) = record.label_canonical.clone() {
+                record.label_display = Some(self.display_for(&canonical));
+                record.label_scientific = self.scientific_for(&canonical);
+            }
+        }
+        records
+    }
+
+    /// Tells what the scan currently says a frame shows.
+    fn observation_kind(&self, info: &ImageInfo) -> ObservationKind {
+        if let Some((_, canonical)) = self.present_label(info).filter(|_| info.present) {
+            ObservationKind::Animal {
+                scientific: self.scientific_for(&canonical),
+                label: canonical,
+            }
+        } else if self.belongs_in_leeg(info) {
+            ObservationKind::Blank
+        } else {
+            ObservationKind::Unknown
+        }
+    }
+
     /// Resolves the display and canonical label for present detections.
     /// Returns the canonical/display label for rows considered present.
     fn present_label(&self, info: &ImageInfo) -> Option<(String, String)> {
@@ -577,7 +949,7 @@ impl UiApp {
 
     /// Looks up the scientific name for a canonical label if known.
     /// Finds the optional scientific name for the provided canonical label.
-    fn scientific_for(&self, canonical: &str) -> Option<String> {
+    pub(crate) fn scientific_for(&self, canonical: &str) -> Option<String> {
         self.label_options
             .iter()
             .find(|option| option.canonical == canonical)
@@ -593,13 +965,15 @@ impl UiApp {
     ) {
         let canonical = canonical_label(&label);
         let display = self.display_for(&canonical);
-        let manual_label = format!("{canonical} (manueel)");
+        let edited_at = now_secs();
         let mut paths: Vec<PathBuf> = Vec::new();
         for &idx in indices {
             if let Some(info) = self.rijen.get_mut(idx) {
+                let provenance = Provenance::manual(info.classification.as_ref(), edited_at);
                 info.classification = Some(Classification {
-                    decision: Decision::Label(manual_label.clone()),
+                    decision: Decision::Label(canonical.clone()),
                     confidence: 1.0,
+                    provenance,
                 });
                 info.present = mark_present && canonical != "achtergrond";
                 paths.push(info.file.clone());
@@ -611,54 +985,11 @@ impl UiApp {
             self.t("status-marked-as"),
             display
         );
+        self.remember_reference_examples(indices, &canonical);
 
-        // Background upload to Roboflow if enabled and configured
+        // Queue the photos for upload to Roboflow if enabled and configured
         if self.improve_recognition {
-            let dataset = self
-                .roboflow_dataset_input
-                .trim()
-                .trim_matches('/')
-                .to_string();
-            let label_for_upload = canonical.clone();
-            let api_key = ROBOFLOW_API_KEY.trim();
-            if api_key.is_empty() {
-                self.status = self.t("roboflow-no-key");
-            } else if dataset.is_empty() {
-                self.status = self.t("roboflow-no-dataset");
-            } else if paths.is_empty() {
-                self.status = self.t("roboflow-no-photos");
-            } else {
-                let upload_count = paths.len();
-                let status_tx = self.upload_status_tx.clone();
-                let language = self.language;
-                self.status = self.t("roboflow-uploading");
-                std::thread::spawn(move || {
-                    let mut last_err: Option<String> = None;
-                    for path in paths {
-                        if let Err(e) =
-                            upload_to_roboflow(&path, &label_for_upload, &dataset, api_key)
-                        {
-                            last_err = Some(e.to_string());
-                            break;
-                        }
-                    }
-                    let message = if let Some(err) = last_err {
-                        format!(
-                            "{}: {err}",
-                            crate::i18n::t_for(language, "roboflow-upload-failed")
-                        )
-                    } else if upload_count == 1 {
-                        crate::i18n::t_for(language, "roboflow-uploaded-one")
-                    } else {
-                        format!(
-                            "{} {}",
-                            upload_count,
-                            crate::i18n::t_for(language, "roboflow-uploaded-many")
-                        )
-                    };
-                    let _ = status_tx.send(message);
-                });
-            }
+            self.queue_manual_uploads(paths, &canonical);
         }
 
         // Persist updated labels to cache if possible
@@ -677,21 +1008,27 @@ impl UiApp {
             self.status = self.t("label-invalid");
             return false;
         }
+        self.ensure_label_option(&new_label);
+        self.assign_manual_category(indices, new_label, true);
+        self.new_label_buffer.clear();
+        true
+    }
+
+    /// Adds a label typed by the user to the label options if it is new.
+    pub(crate) fn ensure_label_option(&mut self, label: &str) {
+        let canonical = canonical_label(label);
         if !self
             .label_options
             .iter()
             .any(|option| option.canonical == canonical)
         {
             self.label_options.push(LabelOption {
-                canonical: canonical.clone(),
-                display: new_label.clone(),
+                canonical,
+                display: label.to_string(),
                 translations: HashMap::new(),
                 scientific: None,
             });
         }
-        self.assign_manual_category(indices, new_label, true);
-        self.new_label_buffer.clear();
-        true
     }
 
     /// Returns the indices that should be affected by a context menu action.
@@ -753,3 +1090,54 @@ fn write_export_csv(
     writer.flush()?;
     Ok(csv_path)
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Time on a day in April 2026.
+    pub(crate) fn at(day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 4, day, hour, minute, second)
+            .unwrap()
+    }
+
+    pub(crate) fn animal(label: &str, scientific: Option<&str>) -> ObservationKind {
+        ObservationKind::Animal {
+            label: label.to_string(),
+            scientific: scientific.map(str::to_string),
+        }
+    }
+
+    /// A single-animal model prediction with probability 0.9 for `file`
+    /// below `/fotos/tuin`.
+    pub(crate) fn frame(
+        file: &str,
+        timestamp: DateTime<Local>,
+        kind: ObservationKind,
+    ) -> FrameObservation {
+        FrameObservation {
+            file: Path::new("/fotos/tuin").join(file),
+            timestamp,
+            kind,
+            manual: false,
+            confidence: Some(0.9),
+            count: 1,
+        }
+    }
+
+    #[test]
+    fn date_range_spans_the_first_and_last_frame() {
+        let frames = [
+            frame("b.jpg", at(12, 9, 0, 0), ObservationKind::Blank),
+            frame("a.jpg", at(12, 7, 0, 0), ObservationKind::Blank),
+            frame("c.jpg", at(13, 8, 0, 0), ObservationKind::Unknown),
+        ];
+        assert_eq!(
+            date_range(&frames),
+            Some((at(12, 7, 0, 0), at(13, 8, 0, 0)))
+        );
+        assert_eq!(date_range(&[]), None);
+    }
+}
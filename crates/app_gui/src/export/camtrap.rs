@@ -0,0 +1,313 @@
+//! Camera Trap Data Package (Camtrap DP 1.0) writer.
+//!
+//! A scan becomes one deployment at the camera site; every frame is a
+//! media record with one media-level observation.
+
+use super::{ExportSite, FrameObservation, ObservationKind, date_range, relative_key};
+use anyhow::Context;
+use chrono::{DateTime, Local};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+const PROFILE_BASE: &str = "https://raw.githubusercontent.com/tdwg/camtrap-dp/1.0";
+
+const DEPLOYMENT_COLUMNS: [&str; 6] = [
+    "deploymentID",
+    "locationName",
+    "latitude",
+    "longitude",
+    "deploymentStart",
+    "deploymentEnd",
+];
+const MEDIA_COLUMNS: [&str; 8] = [
+    "mediaID",
+    "deploymentID",
+    "captureMethod",
+    "timestamp",
+    "filePath",
+    "filePublic",
+    "fileName",
+    "fileMediatype",
+];
+const OBSERVATION_COLUMNS: [&str; 14] = [
+    "observationID",
+    "deploymentID",
+    "mediaID",
+    "eventID",
+    "eventStart",
+    "eventEnd",
+    "observationLevel",
+    "observationType",
+    "scientificName",
+    "count",
+    "classificationMethod",
+    "classifiedBy",
+    "classificationTimestamp",
+    "classificationProbability",
+];
+
+fn timestamp(time: &DateTime<Local>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%S%:z").to_string()
+}
+
+/// Writes `datapackage.json`, `deployments.csv`, `media.csv` and
+/// `observations.csv` into `dir`; media paths are relative to the scan
+/// folder `root`.
+pub(crate) fn write_camtrap_dp(
+    dir: &Path,
+    root: &Path,
+    site: &ExportSite,
+    frames: &[FrameObservation],
+    classified_by: &str,
+    created: DateTime<Local>,
+) -> anyhow::Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Kon {} niet aanmaken", dir.display()))?;
+    let (start, end) = date_range(frames).unwrap_or((created, created));
+
+    let mut writer = csv_writer(&dir.join("deployments.csv"))?;
+    writer.write_record(DEPLOYMENT_COLUMNS)?;
+    writer.write_record([
+        site.id.clone(),
+        site.name.clone(),
+        site.latitude.to_string(),
+        site.longitude.to_string(),
+        timestamp(&start),
+        timestamp(&end),
+    ])?;
+    writer.flush()?;
+
+    let mut media = csv_writer(&dir.join("media.csv"))?;
+    media.write_record(MEDIA_COLUMNS)?;
+    let mut observations = csv_writer(&dir.join("observations.csv"))?;
+    observations.write_record(OBSERVATION_COLUMNS)?;
+    let mut taxa: BTreeMap<String, String> = BTreeMap::new();
+    for (idx, frame) in frames.iter().enumerate() {
+        let media_id = format!("{}-{:05}", site.id, idx + 1);
+        let time = timestamp(&frame.timestamp);
+        let path = relative_key(root, &frame.file);
+        let file_name = frame
+            .file
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mediatype = match frame
+            .file
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("png") => "image/png",
+            _ => "image/jpeg",
+        };
+        media.write_record([
+            media_id.as_str(),
+            site.id.as_str(),
+            "activityDetection",
+            time.as_str(),
+            path.as_str(),
+            "false",
+            file_name.as_str(),
+            mediatype,
+        ])?;
+
+        let (observation_type, scientific, count) = match &frame.kind {
+            ObservationKind::Animal { label, scientific } => {
+                // Without a scientific name the species stays unnamed: the
+                // label is a vernacular name and never a `scientificName`.
+                let name = scientific.clone().unwrap_or_default();
+                if !name.is_empty() {
+                    taxa.entry(name.clone()).or_insert_with(|| label.clone());
+                }
+                ("animal", name, frame.count.to_string())
+            }
+            ObservationKind::Blank => ("blank", String::new(), String::new()),
+            ObservationKind::Unknown => ("unknown", String::new(), String::new()),
+        };
+        let (method, by, probability) = if frame.manual {
+            ("human", String::new(), String::new())
+        } else {
+            (
+                "machine",
+                classified_by.to_string(),
+                frame
+                    .confidence
+                    .map(|confidence| format!("{confidence:.3}"))
+                    .unwrap_or_default(),
+            )
+        };
+        observations.write_record([
+            format!("{media_id}-obs1").as_str(),
+            site.id.as_str(),
+            media_id.as_str(),
+            media_id.as_str(),
+            time.as_str(),
+            time.as_str(),
+            "media",
+            observation_type,
+            scientific.as_str(),
+            count.as_str(),
+            method,
+            by.as_str(),
+            timestamp(&created).as_str(),
+            probability.as_str(),
+        ])?;
+    }
+    media.flush()?;
+    observations.flush()?;
+
+    let taxonomic: Vec<serde_json::Value> = taxa
+        .into_iter()
+        .map(|(scientific, label)| {
+            serde_json::json!({
+                "scientificName": scientific,
+                "vernacularNames": {"nld": label},
+            })
+        })
+        .collect();
+    let resource = |name: &str| {
+        serde_json::json!({
+            "name": name,
+            "path": format!("{name}.csv"),
+            "profile": "tabular-data-resource",
+            "format": "csv",
+            "mediatype": "text/csv",
+            "encoding": "utf-8",
+            "schema": format!("{PROFILE_BASE}/{name}-table-schema.json"),
+        })
+    };
+    let package = serde_json::json!({
+        "profile": format!("{PROFILE_BASE}/camtrap-dp-profile.json"),
+        "name": format!("feedie-{}", site.id),
+        "title": site.name,
+        "created": created.to_rfc3339(),
+        "contributors": [{"title": "Feedie", "role": "contributor"}],
+        "project": {
+            "title": site.name,
+            "samplingDesign": "opportunistic",
+            "captureMethod": ["activityDetection"],
+            "individualAnimals": false,
+            "observationLevel": ["media"],
+        },
+        "spatial": {
+            "type": "Point",
+            "coordinates": [site.longitude, site.latitude],
+        },
+        "temporal": {
+            "start": start.format("%Y-%m-%d").to_string(),
+            "end": end.format("%Y-%m-%d").to_string(),
+        },
+        "taxonomic": taxonomic,
+        "resources": [
+            resource("deployments"),
+            resource("media"),
+            resource("observations"),
+        ],
+    });
+    let path = dir.join("datapackage.json");
+    fs::write(&path, serde_json::to_string_pretty(&package)?)
+        .with_context(|| format!("Kon {} niet schrijven", path.display()))
+}
+
+fn csv_writer(path: &Path) -> anyhow::Result<csv::Writer<fs::File>> {
+    csv::Writer::from_path(path).with_context(|| format!("Kon {} niet openen", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::slug;
+    use crate::export::tests::{animal, at, frame};
+
+    #[test]
+    fn package_links_deployment_media_and_observations() {
+        let dir = tempfile::tempdir().unwrap();
+        let frames = vec![
+            frame(
+                "IMG_0001.JPG",
+                at(12, 7, 5, 0),
+                animal("koolmees", Some("Parus major")),
+            ),
+            frame("IMG_0002.JPG", at(12, 7, 1, 0), ObservationKind::Blank),
+            FrameObservation {
+                manual: true,
+                confidence: None,
+                ..frame("IMG_0003.PNG", at(12, 7, 9, 0), animal("eekhoorn", None))
+            },
+        ];
+        let site = ExportSite {
+            id: slug("Tuin Voorkant"),
+            name: "Tuin Voorkant".to_string(),
+            latitude: 51.05,
+            longitude: 3.72,
+        };
+        let created = at(13, 20, 0, 0);
+        write_camtrap_dp(
+            dir.path(),
+            Path::new("/fotos"),
+            &site,
+            &frames,
+            "Feedie model 1.2.0",
+            created,
+        )
+        .unwrap();
+
+        let read = |name: &str| -> Vec<csv::StringRecord> {
+            csv::Reader::from_path(dir.path().join(name))
+                .unwrap()
+                .records()
+                .map(Result::unwrap)
+                .collect()
+        };
+        let deployments = read("deployments.csv");
+        assert_eq!(&deployments[0][0], "tuin-voorkant");
+        assert!(deployments[0][4].starts_with("2026-04-12T07:01:00"));
+        assert!(deployments[0][5].starts_with("2026-04-12T07:09:00"));
+
+        let media = read("media.csv");
+        assert_eq!(media.len(), 3);
+        assert_eq!(&media[2][0], "tuin-voorkant-00003");
+        assert_eq!(&media[2][4], "tuin/IMG_0003.PNG");
+        assert_eq!(&media[2][7], "image/png");
+
+        let observations = read("observations.csv");
+        let first = &observations[0];
+        assert_eq!(&first[2], "tuin-voorkant-00001");
+        assert_eq!(
+            [
+                &first[7], &first[8], &first[9], &first[10], &first[11], &first[13]
+            ],
+            [
+                "animal",
+                "Parus major",
+                "1",
+                "machine",
+                "Feedie model 1.2.0",
+                "0.900"
+            ]
+        );
+        assert_eq!([&observations[1][7], &observations[1][9]], ["blank", ""]);
+        assert_eq!(
+            [
+                &observations[2][8],
+                &observations[2][10],
+                &observations[2][13]
+            ],
+            ["", "human", ""]
+        );
+
+        let package: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.path().join("datapackage.json")).unwrap())
+                .unwrap();
+        assert_eq!(package["spatial"]["coordinates"][0], 3.72);
+        assert_eq!(package["temporal"]["start"], "2026-04-12");
+        assert_eq!(
+            package["taxonomic"],
+            serde_json::json!([{
+                "scientificName": "Parus major",
+                "vernacularNames": {"nld": "koolmees"}
+            }])
+        );
+        assert_eq!(package["resources"][2]["path"], "observations.csv");
+    }
+}
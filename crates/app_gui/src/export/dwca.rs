@@ -0,0 +1,267 @@
+//! Darwin Core Archive (DwC-A) writer for GBIF-style platforms.
+//!
+//! The archive holds `occurrence.txt` (one record per photo with an animal),
+//! `meta.xml` describing its columns and `eml.xml` with dataset metadata.
+
+use super::{ExportSite, FrameObservation, ObservationKind, date_range, relative_key};
+use anyhow::Context;
+use chrono::{DateTime, Local};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use zip::write::FileOptions;
+
+const DWC_TERMS: &str = "http://rs.tdwg.org/dwc/terms/";
+
+/// Occurrence columns; each is a term in the Darwin Core namespace.
+const OCCURRENCE_COLUMNS: [&str; 15] = [
+    "occurrenceID",
+    "basisOfRecord",
+    "eventDate",
+    "scientificName",
+    "vernacularName",
+    "kingdom",
+    "individualCount",
+    "decimalLatitude",
+    "decimalLongitude",
+    "geodeticDatum",
+    "locality",
+    "identificationVerificationStatus",
+    "identifiedBy",
+    "identificationRemarks",
+    "associatedMedia",
+];
+
+/// Writes the archive as a zip file at `path`; media paths are relative to
+/// the scan folder `root`.
+///
+/// Occurrence IDs use the same `<site>-<number>` scheme as the media IDs of
+/// the Camtrap DP export, so both exports of one scan can be matched.
+pub(crate) fn write_dwca(
+    path: &Path,
+    root: &Path,
+    site: &ExportSite,
+    frames: &[FrameObservation],
+    identified_by: &str,
+    created: DateTime<Local>,
+) -> anyhow::Result<()> {
+    let mut occurrences = OCCURRENCE_COLUMNS.join("\t");
+    occurrences.push('\n');
+    for (idx, frame) in frames.iter().enumerate() {
+        let ObservationKind::Animal { label, scientific } = &frame.kind else {
+            continue;
+        };
+        let (status, by, remarks) = if frame.manual {
+            ("verified", String::new(), String::new())
+        } else {
+            (
+                "unverified",
+                identified_by.to_string(),
+                frame
+                    .confidence
+                    .map(|confidence| format!("model probability {confidence:.3}"))
+                    .unwrap_or_default(),
+            )
+        };
+        let row = [
+            format!("{}-{:05}", site.id, idx + 1),
+            "MachineObservation".to_string(),
+            frame.timestamp.format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+            scientific.clone().unwrap_or_default(),
+            label.clone(),
+            "Animalia".to_string(),
+            frame.count.to_string(),
+            site.latitude.to_string(),
+            site.longitude.to_string(),
+            "WGS84".to_string(),
+            site.name.clone(),
+            status.to_string(),
+            by,
+            remarks,
+            relative_key(root, &frame.file),
+        ];
+        let row: Vec<String> = row.iter().map(|value| clean_field(value)).collect();
+        occurrences.push_str(&row.join("\t"));
+        occurrences.push('\n');
+    }
+
+    let file =
+        fs::File::create(path).with_context(|| format!("Kon {} niet aanmaken", path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in [
+        ("occurrence.txt", occurrences),
+        ("meta.xml", meta_xml()),
+        ("eml.xml", eml_xml(site, frames, created)),
+    ] {
+        zip.start_file(name, options)?;
+        zip.write_all(contents.as_bytes())?;
+    }
+    zip.finish()
+        .with_context(|| format!("Kon {} niet schrijven", path.display()))?;
+    Ok(())
+}
+
+/// The archive is unquoted tab-separated text, so tabs and line breaks
+/// inside values become spaces.
+fn clean_field(value: &str) -> String {
+    value.replace(['\t', '\r', '\n'], " ")
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn meta_xml() -> String {
+    let fields: String = OCCURRENCE_COLUMNS
+        .iter()
+        .enumerate()
+        .map(|(idx, term)| format!("    <field index=\"{idx}\" term=\"{DWC_TERMS}{term}\"/>\n"))
+        .collect();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<archive xmlns="http://rs.tdwg.org/dwc/text/" metadata="eml.xml">
+  <core encoding="UTF-8" fieldsTerminatedBy="\t" linesTerminatedBy="\n" fieldsEnclosedBy="" ignoreHeaderLines="1" rowType="{DWC_TERMS}Occurrence">
+    <files>
+      <location>occurrence.txt</location>
+    </files>
+    <id index="0"/>
+{fields}  </core>
+</archive>
+"#
+    )
+}
+
+fn eml_xml(site: &ExportSite, frames: &[FrameObservation], created: DateTime<Local>) -> String {
+    let (start, end) = date_range(frames).unwrap_or((created, created));
+    let name = xml_escape(&site.name);
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<eml:eml xmlns:eml="eml://ecoinformatics.org/eml-2.1.1" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="eml://ecoinformatics.org/eml-2.1.1 http://rs.gbif.org/schema/eml-gbif-profile/1.1/eml.xsd" packageId="feedie-{id}-{stamp}" system="Feedie" scope="system" xml:lang="en">
+  <dataset>
+    <title>Feedie feeder camera observations: {name}</title>
+    <creator>
+      <organizationName>Feedie</organizationName>
+    </creator>
+    <pubDate>{pub_date}</pubDate>
+    <language>en</language>
+    <abstract>
+      <para>Photos from a feeder camera, identified by the Feedie classification model and checked by the user where marked as verified.</para>
+    </abstract>
+    <coverage>
+      <geographicCoverage>
+        <geographicDescription>{name}</geographicDescription>
+        <boundingCoordinates>
+          <westBoundingCoordinate>{lng}</westBoundingCoordinate>
+          <eastBoundingCoordinate>{lng}</eastBoundingCoordinate>
+          <northBoundingCoordinate>{lat}</northBoundingCoordinate>
+          <southBoundingCoordinate>{lat}</southBoundingCoordinate>
+        </boundingCoordinates>
+      </geographicCoverage>
+      <temporalCoverage>
+        <rangeOfDates>
+          <beginDate>
+            <calendarDate>{start}</calendarDate>
+          </beginDate>
+          <endDate>
+            <calendarDate>{end}</calendarDate>
+          </endDate>
+        </rangeOfDates>
+      </temporalCoverage>
+    </coverage>
+    <contact>
+      <organizationName>Feedie</organizationName>
+    </contact>
+  </dataset>
+</eml:eml>
+"#,
+        id = xml_escape(&site.id),
+        stamp = created.format("%Y%m%d%H%M%S"),
+        pub_date = created.format("%Y-%m-%d"),
+        lat = site.latitude,
+        lng = site.longitude,
+        start = start.format("%Y-%m-%d"),
+        end = end.format("%Y-%m-%d"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::{animal, at, frame};
+    use std::io::Read;
+
+    #[test]
+    fn archive_holds_occurrences_for_animals_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dwca.zip");
+        let site = ExportSite {
+            id: "tuin".to_string(),
+            name: "Tuin & vijver".to_string(),
+            latitude: 51.05,
+            longitude: 3.72,
+        };
+        let time = at(12, 7, 30, 0);
+        let frames = vec![
+            frame("a.jpg", time, animal("koolmees", Some("Parus major"))),
+            frame("b.jpg", time, ObservationKind::Blank),
+            FrameObservation {
+                manual: true,
+                confidence: None,
+                ..frame("c.jpg", time, animal("merel", Some("Turdus merula")))
+            },
+            frame("d.jpg", time, animal("eekhoorn", None)),
+        ];
+        let created = at(13, 9, 0, 0);
+        write_dwca(
+            &path,
+            Path::new("/fotos"),
+            &site,
+            &frames,
+            "Feedie model 1.2.0",
+            created,
+        )
+        .unwrap();
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&path).unwrap()).unwrap();
+        let mut read = |name: &str| {
+            let mut contents = String::new();
+            archive
+                .by_name(name)
+                .unwrap()
+                .read_to_string(&mut contents)
+                .unwrap();
+            contents
+        };
+        let occurrences = read("occurrence.txt");
+        let rows: Vec<Vec<&str>> = occurrences
+            .lines()
+            .map(|line| line.split('\t').collect())
+            .collect();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[0], OCCURRENCE_COLUMNS);
+        assert_eq!(rows[1][0], "tuin-00001");
+        assert_eq!(rows[1][1], "MachineObservation");
+        assert!(rows[1][2].starts_with("2026-04-12T07:30:00"));
+        assert_eq!(rows[1][3], "Parus major");
+        assert_eq!(
+            [rows[1][7], rows[1][8], rows[1][11], rows[1][12]],
+            ["51.05", "3.72", "unverified", "Feedie model 1.2.0"]
+        );
+        assert_eq!(rows[1][14], "tuin/a.jpg");
+        assert_eq!(rows[2][0], "tuin-00003");
+        assert_eq!([rows[2][11], rows[2][12]], ["verified", ""]);
+        assert_eq!([rows[3][3], rows[3][4]], ["", "eekhoorn"]);
+
+        let meta = read("meta.xml");
+        assert_eq!(meta.matches("<field ").count(), OCCURRENCE_COLUMNS.len());
+        assert!(meta.contains("http://rs.tdwg.org/dwc/terms/identificationVerificationStatus"));
+        let eml = read("eml.xml");
+        assert!(eml.contains("Tuin &amp; vijver"));
+        assert!(eml.contains("<calendarDate>2026-04-12</calendarDate>"));
+    }
+}
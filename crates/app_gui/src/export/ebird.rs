@@ -0,0 +1,236 @@
+//! eBird Record Format (Extended) checklist writer.
+//!
+//! Every day with birds becomes one "Stationary" checklist at the camera
+//! site. A species is reported with the highest count seen on a single
+//! frame that day, and the checklist duration runs from the first to the
+//! last frame of that day.
+
+use super::{ExportSite, FrameObservation, ObservationKind};
+use anyhow::Context;
+use chrono::{NaiveDate, Timelike};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// eBird common names for the scientific names in `feeder-labels.csv` and
+/// other garden birds that model packs may add.
+///
+/// Species missing here (mammals, labels without a scientific name) are not
+/// birds eBird accepts and are left out of the checklists.
+const EBIRD_COMMON_NAMES: [(&str, &str); 22] = [
+    ("Aegithalos caudatus", "Long-tailed Tit"),
+    ("Carduelis carduelis", "European Goldfinch"),
+    ("Chloris chloris", "European Greenfinch"),
+    ("Coccothraustes coccothraustes", "Hawfinch"),
+    ("Coloeus monedula", "Eurasian Jackdaw"),
+    ("Columba palumbus", "Common Wood-Pigeon"),
+    ("Cyanistes caeruleus", "Eurasian Blue Tit"),
+    ("Dendrocopos major", "Great Spotted Woodpecker"),
+    ("Erithacus rubecula", "European Robin"),
+    ("Fringilla coelebs", "Common Chaffinch"),
+    ("Fringilla montifringilla", "Brambling"),
+    ("Garrulus glandarius", "Eurasian Jay"),
+    ("Parus major", "Great Tit"),
+    ("Passer domesticus", "House Sparrow"),
+    ("Passer montanus", "Eurasian Tree Sparrow"),
+    ("Periparus ater", "Coal Tit"),
+    ("Pica pica", "Eurasian Magpie"),
+    ("Prunella modularis", "Dunnock"),
+    ("Sitta europaea", "Eurasian Nuthatch"),
+    ("Streptopelia decaocto", "Eurasian Collared-Dove"),
+    ("Turdus merula", "Eurasian Blackbird"),
+    ("Turdus philomelos", "Song Thrush"),
+];
+
+/// Looks up the eBird common name for a scientific name.
+fn ebird_common_name(scientific: &str) -> Option<&'static str> {
+    EBIRD_COMMON_NAMES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(scientific.trim()))
+        .map(|(_, common)| *common)
+}
+
+#[derive(Default)]
+struct DayChecklist<'a> {
+    /// First and last frame of the day as minutes since midnight.
+    first: Option<u32>,
+    last: u32,
+    frames: usize,
+    /// Highest count per species on a single frame, keyed by scientific name.
+    species: BTreeMap<&'a str, (&'static str, u32)>,
+}
+
+/// Writes the checklists as an eBird Record Format (Extended) CSV at `path`.
+///
+/// Returns the number of checklists written; days without birds are skipped.
+/// The file has no header row, as eBird's importer expects.
+pub(crate) fn write_ebird_checklists(
+    path: &Path,
+    site: &ExportSite,
+    frames: &[FrameObservation],
+) -> anyhow::Result<usize> {
+    let mut days: BTreeMap<NaiveDate, DayChecklist> = BTreeMap::new();
+    for frame in frames {
+        let day = days.entry(frame.timestamp.date_naive()).or_default();
+        let minute = frame.timestamp.hour() * 60 + frame.timestamp.minute();
+        day.first = Some(day.first.map_or(minute, |first| first.min(minute)));
+        day.last = day.last.max(minute);
+        day.frames += 1;
+        if let ObservationKind::Animal {
+            scientific: Some(scientific),
+            ..
+        } = &frame.kind
+            && let Some(common) = ebird_common_name(scientific)
+        {
+            let entry = day
+                .species
+                .entry(scientific.as_str())
+                .or_insert((common, 0));
+            entry.1 = entry.1.max(frame.count);
+        }
+    }
+
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_path(path)
+        .with_context(|| format!("Kon {} niet openen", path.display()))?;
+    let latitude = site.latitude.to_string();
+    let longitude = site.longitude.to_string();
+    let mut checklists = 0usize;
+    for (date, day) in &days {
+        if day.species.is_empty() {
+            continue;
+        }
+        let first = day.first.unwrap_or(day.last);
+        let start = format!("{:02}:{:02}", first / 60, first % 60);
+        let duration = (day.last - first).max(1).to_string();
+        let comments = format!(
+            "Feeder camera checklist from Feedie ({} photos)",
+            day.frames
+        );
+        for (scientific, (common, count)) in &day.species {
+            let (genus, species) = scientific.split_once(' ').unwrap_or((scientific, ""));
+            writer.write_record([
+                common,
+                genus,
+                species,
+                count.to_string().as_str(),
+                "",
+                site.name.as_str(),
+                latitude.as_str(),
+                longitude.as_str(),
+                date.format("%m/%d/%Y").to_string().as_str(),
+                start.as_str(),
+                "",
+                "",
+                "Stationary",
+                "1",
+                duration.as_str(),
+                "N",
+                "",
+                "",
+                comments.as_str(),
+            ])?;
+        }
+        checklists += 1;
+    }
+    writer.flush()?;
+    Ok(checklists)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::{animal, at, frame};
+
+    /// Scientific names in `feeder-labels.csv` that are not birds.
+    const NON_BIRDS: [&str; 2] = ["Rattus norvegicus", "Canis familiaris"];
+
+    #[test]
+    fn every_bundled_bird_has_an_ebird_name() {
+        let options =
+            crate::model::parse_label_options(include_str!("../../../../models/feeder-labels.csv"));
+        let birds: Vec<&str> = options
+            .iter()
+            .filter_map(|option| option.scientific.as_deref())
+            .filter(|scientific| !NON_BIRDS.contains(scientific))
+            .collect();
+        assert!(birds.len() > 10);
+        for scientific in birds {
+            assert!(
+                ebird_common_name(scientific).is_some(),
+                "geen eBird-naam voor {scientific}"
+            );
+        }
+    }
+
+    #[test]
+    fn one_checklist_per_day_with_max_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        let sighting = |day: u32, hour: u32, minute: u32, scientific: Option<&str>, count: u32| {
+            let kind = match scientific {
+                Some(_) => animal("vogel", scientific),
+                None => ObservationKind::Blank,
+            };
+            FrameObservation {
+                count,
+                ..frame("frame.jpg", at(day, hour, minute, 0), kind)
+            }
+        };
+        let frames = vec![
+            sighting(12, 6, 45, None, 1),
+            sighting(12, 7, 10, Some("Parus major"), 1),
+            sighting(12, 7, 20, Some("Parus major"), 3),
+            sighting(12, 8, 0, Some("Rattus norvegicus"), 1),
+            sighting(12, 9, 15, Some("Turdus merula"), 1),
+            sighting(13, 12, 0, None, 1),
+            sighting(14, 10, 5, Some("Erithacus rubecula"), 1),
+        ];
+        let site = ExportSite {
+            id: "tuin".to_string(),
+            name: "Tuin".to_string(),
+            latitude: 51.05,
+            longitude: 3.72,
+        };
+        let path = dir.path().join("ebird.csv");
+        let checklists = write_ebird_checklists(&path, &site, &frames).unwrap();
+        assert_eq!(checklists, 2);
+
+        let rows: Vec<csv::StringRecord> = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_path(&path)
+            .unwrap()
+            .records()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0].iter().collect::<Vec<_>>(),
+            [
+                "Great Tit",
+                "Parus",
+                "major",
+                "3",
+                "",
+                "Tuin",
+                "51.05",
+                "3.72",
+                "04/12/2026",
+                "06:45",
+                "",
+                "",
+                "Stationary",
+                "1",
+                "150",
+                "N",
+                "",
+                "",
+                "Feeder camera checklist from Feedie (5 photos)",
+            ]
+        );
+        assert_eq!(&rows[1][0], "Eurasian Blackbird");
+        assert_eq!(
+            [&rows[2][0], &rows[2][9], &rows[2][14]],
+            ["European Robin", "10:05", "1"]
+        );
+    }
+}
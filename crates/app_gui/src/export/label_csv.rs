@@ -0,0 +1,237 @@
+//! Merges a label CSV (Feedie's own export or a hand-edited spreadsheet)
+//! back into the current scan as manual labels.
+
+use crate::app::UiApp;
+use crate::util::canonical_label;
+use feeder_core::{CsvLabel, CsvMatch, Decision, ImageInfo, import_csv, match_csv_labels};
+use rfd::FileDialog;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Species values that stand for "no decision", as written by `export_csv`.
+const UNDECIDED_SPECIES: [&str; 2] = ["unknown", "onbekend"];
+
+/// Unmatched file names shown in the status line; the rest goes to the log.
+const UNMATCHED_EXAMPLES: usize = 3;
+
+/// Manual label and presence a CSV row asks for, or `None` when it carries
+/// no decision.
+///
+/// An empty species never means background: `export_csv` also leaves it
+/// empty for uncertain and "iets sp" frames, so only an explicit background
+/// species marks a frame as empty.
+fn desired_label(label: &CsvLabel) -> Option<(String, bool)> {
+    match &label.species {
+        Some(species) => {
+            let canonical = canonical_label(species);
+            if canonical.is_empty() || UNDECIDED_SPECIES.contains(&canonical.as_str()) {
+                None
+            } else if canonical == "achtergrond" || canonical == "iets sp" {
+                Some((canonical, false))
+            } else {
+                Some((species.trim().to_string(), true))
+            }
+        }
+        None => None,
+    }
+}
+
+impl UiApp {
+    /// Lets the user pick a CSV and applies its labels to the current scan.
+    pub(crate) fn import_labels_csv(&mut self) {
+        let Some(root) = self.gekozen_map.clone() else {
+            return;
+        };
+        let Some(path) = FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_directory(&root)
+            .pick_file()
+        else {
+            return;
+        };
+        let labels = match import_csv(&path) {
+            Ok(labels) => labels,
+            Err(err) => {
+                self.status = format!("{}: {err}", self.t("labels-csv-import-failed"));
+                return;
+            }
+        };
+        let CsvMatch { matched, unmatched } = match_csv_labels(&self.rijen, &root, labels);
+
+        let known: HashSet<&str> = self
+            .label_options
+            .iter()
+            .map(|option| option.canonical.as_str())
+            .collect();
+        let mut groups: BTreeMap<(String, bool), Vec<usize>> = BTreeMap::new();
+        let mut unknown: BTreeSet<String> = BTreeSet::new();
+        for (idx, label) in matched {
+            let Some((name, present)) = desired_label(&label) else {
+                continue;
+            };
+            let canonical = canonical_label(&name);
+            if self.already_labelled(idx, &canonical, present) {
+                continue;
+            }
+            if present && !known.contains(canonical.as_str()) {
+                unknown.insert(name.clone());
+            }
+            groups.entry((name, present)).or_default().push(idx);
+        }
+
+        let updated: usize = groups.values().map(Vec::len).sum();
+        for ((name, present), indices) in groups {
+            if present {
+                self.ensure_label_option(&name);
+            }
+            self.assign_manual_category(&indices, name, present);
+        }
+        for label in &unmatched {
+            tracing::warn!("CSV-rij zonder foto in deze scan: {}", label.file.display());
+        }
+
+        let mut args = crate::i18n::Args::new();
+        args.insert("updated".into(), updated.into());
+        args.insert("unmatched".into(), unmatched.len().into());
+        let mut message = self.t_args("labels-csv-import-done", &args);
+        if !unmatched.is_empty() {
+            let examples: Vec<String> = unmatched
+                .iter()
+                .take(UNMATCHED_EXAMPLES)
+                .map(|label| label.file.display().to_string())
+                .collect();
+            let mut args = crate::i18n::Args::new();
+            args.insert("files".into(), examples.join(", ").into());
+            message.push(' ');
+            message.push_str(&self.t_args("labels-csv-import-unmatched", &args));
+        }
+        if !unknown.is_empty() {
+            let names: Vec<String> = unknown.into_iter().collect();
+            let mut args = crate::i18n::Args::new();
+            args.insert("labels".into(), names.join(", ").into());
+            message.push(' ');
+            message.push_str(&self.t_args("labels-csv-import-unknown", &args));
+        }
+        self.status = message;
+    }
+
+    /// True when the row already has this label, so re-importing an
+    /// unedited export does not turn predictions into manual labels.
+    fn already_labelled(&self, idx: usize, canonical: &str, present: bool) -> bool {
+        let Some(info) = self.rijen.get(idx) else {
+            return false;
+        };
+        if canonical == "achtergrond" && self.belongs_in_leeg(info) {
+            return true;
+        }
+        has_label(info, canonical, present)
+    }
+}
+
+/// True when `info` is labelled `canonical` with the given presence.
+fn has_label(info: &ImageInfo, canonical: &str, present: bool) -> bool {
+    matches!(
+        info.classification.as_ref().map(|c| &c.decision),
+        Some(Decision::Label(name)) if canonical_label(name) == canonical
+    ) && info.present == present
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use feeder_core::{Classification, Provenance, export_csv};
+    use std::path::{Path, PathBuf};
+
+    fn label(present: Option<bool>, species: Option<&str>) -> CsvLabel {
+        CsvLabel {
+            file: PathBuf::from("a.jpg"),
+            present,
+            species: species.map(str::to_string),
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn rows_map_to_manual_labels() {
+        assert_eq!(
+            desired_label(&label(Some(true), Some(" Koolmees "))),
+            Some(("Koolmees".to_string(), true))
+        );
+        assert_eq!(desired_label(&label(Some(false), None)), None);
+        assert_eq!(
+            desired_label(&label(Some(false), Some("Achtergrond"))),
+            Some(("achtergrond".to_string(), false))
+        );
+        assert_eq!(
+            desired_label(&label(None, Some("Iets sp."))),
+            Some(("iets sp".to_string(), false))
+        );
+        assert_eq!(desired_label(&label(Some(true), Some("Unknown"))), None);
+        assert_eq!(desired_label(&label(Some(true), None)), None);
+        assert_eq!(desired_label(&label(None, None)), None);
+    }
+    fn row(root: &Path, name: &str, present: bool, decision: Decision, manual: bool) -> ImageInfo {
+        ImageInfo {
+            file: root.join(name),
+            present,
+            classification: Some(Classification {
+                decision,
+                confidence: if manual { 1.0 } else { 0.4 },
+                provenance: if manual {
+                    Provenance::manual(None, 0)
+                } else {
+                    Provenance::default()
+                },
+            }),
+            embedding: None,
+            embedding_model: None,
+            detections: Vec::new(),
+            top_k: Vec::new(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn reimporting_an_export_changes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let rows = vec![
+            row(
+                root,
+                "mees.jpg",
+                true,
+                Decision::Label("Koolmees".into()),
+                false,
+            ),
+            row(
+                root,
+                "onzeker.jpg",
+                false,
+                Decision::Label("Merel".into()),
+                false,
+            ),
+            row(
+                root,
+                "iets.jpg",
+                false,
+                Decision::Label("iets sp".into()),
+                true,
+            ),
+            row(root, "leeg.jpg", false, Decision::Unknown, false),
+        ];
+        let csv_path = root.join("labels.csv");
+        export_csv(&rows, &csv_path).unwrap();
+
+        let CsvMatch { matched, unmatched } =
+            match_csv_labels(&rows, root, import_csv(&csv_path).unwrap());
+        assert!(unmatched.is_empty());
+        assert_eq!(matched.len(), rows.len());
+        for (idx, label) in matched {
+            match desired_label(&label) {
+                Some((name, present)) => {
+                    assert!(has_label(&rows[idx], &canonical_label(&name), present));
+                }
+                None => assert!(!rows[idx].present, "{}", rows[idx].file.display()),
+            }
+        }
+    }
+}
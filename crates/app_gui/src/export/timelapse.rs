@@ -0,0 +1,289 @@
+//! Timelapse2-compatible CSV export and import.
+//!
+//! Timelapse identifies an image by `RelativePath` (the backslash-separated
+//! folder below the image set root) plus `File`. Species that volunteers
+//! changed in Timelapse come back into the scan as manual labels.
+
+use super::{FrameObservation, ObservationKind, relative_key};
+use crate::app::UiApp;
+use crate::util::canonical_label;
+use anyhow::Context;
+use rfd::FileDialog;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Species value for frames without an animal.
+pub(crate) const EMPTY_SPECIES: &str = "empty";
+
+const COLUMNS: [&str; 7] = [
+    "File",
+    "RelativePath",
+    "DateTime",
+    "species",
+    "count",
+    "notes",
+    "DeleteFlag",
+];
+
+/// Species values that mean "nothing on this frame" on import.
+const EMPTY_ALIASES: [&str; 4] = [EMPTY_SPECIES, "blank", "none", "leeg"];
+
+/// Row read back from an edited Timelapse CSV.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TimelapseRow {
+    /// Path below the scan folder with `/` separators.
+    pub(crate) relative: String,
+    pub(crate) species: String,
+    pub(crate) delete: bool,
+}
+
+/// Change an imported row asks for.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TimelapseEdit {
+    Empty,
+    Species(String),
+}
+
+/// Species value written for a frame; uncertain frames stay blank so the
+/// reviewer fills them in.
+pub(crate) fn species_for(kind: &ObservationKind) -> &str {
+    match kind {
+        ObservationKind::Animal { label, .. } => label,
+        ObservationKind::Blank => EMPTY_SPECIES,
+        ObservationKind::Unknown => "",
+    }
+}
+
+/// Writes one Timelapse row per frame, with paths relative to `root`.
+pub(crate) fn write_timelapse_csv(
+    path: &Path,
+    root: &Path,
+    frames: &[FrameObservation],
+) -> anyhow::Result<()> {
+    let mut writer = csv::Writer::from_path(path)
+        .with_context(|| format!("Kon {} niet openen", path.display()))?;
+    writer.write_record(COLUMNS)?;
+    for frame in frames {
+        let relative = relative_key(root, &frame.file);
+        let (folder, file) = match relative.rsplit_once('/') {
+            Some((folder, file)) => (folder.replace('/', "\\"), file.to_string()),
+            None => (String::new(), relative),
+        };
+        let count = match frame.kind {
+            ObservationKind::Animal { .. } => frame.count,
+            _ => 0,
+        };
+        let notes = frame
+            .confidence
+            .filter(|_| !matches!(frame.kind, ObservationKind::Blank))
+            .map(|confidence| format!("Feedie {:.0}%", confidence * 100.0))
+            .unwrap_or_default();
+        writer.write_record([
+            file.as_str(),
+            folder.as_str(),
+            frame
+                .timestamp
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+                .as_str(),
+            species_for(&frame.kind),
+            count.to_string().as_str(),
+            notes.as_str(),
+            "false",
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads an edited Timelapse CSV; column order and extra columns do not
+/// matter, only `File` is required.
+pub(crate) fn read_timelapse_csv(path: &Path) -> anyhow::Result<Vec<TimelapseRow>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(path)
+        .with_context(|| format!("Kon {} niet openen", path.display()))?;
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header.trim().eq_ignore_ascii_case(name))
+    };
+    let file_column =
+        column("File").with_context(|| format!("Kolom File ontbreekt in {}", path.display()))?;
+    let folder_column = column("RelativePath");
+    let species_column = column("species");
+    let delete_column = column("DeleteFlag");
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let field = |idx: Option<usize>| idx.and_then(|idx| record.get(idx)).unwrap_or("").trim();
+        let file = field(Some(file_column));
+        if file.is_empty() {
+            continue;
+        }
+        let folder = field(folder_column).replace('\\', "/");
+        let folder = folder.trim_matches('/');
+        let relative = if folder.is_empty() {
+            file.to_string()
+        } else {
+            format!("{folder}/{file}")
+        };
+        rows.push(TimelapseRow {
+            relative,
+            species: field(species_column).to_string(),
+            delete: field(delete_column).eq_ignore_ascii_case("true"),
+        });
+    }
+    Ok(rows)
+}
+
+/// Compares an imported row with the species the scan currently has.
+///
+/// Rows that still match (including the values Feedie exported itself) and
+/// rows without a species are left alone, so only real edits become manual
+/// labels. A set `DeleteFlag` marks the frame as empty.
+pub(crate) fn edit_for(row: &TimelapseRow, current: &str) -> Option<TimelapseEdit> {
+    let species = row.species.trim();
+    let wants_empty = row.delete
+        || EMPTY_ALIASES
+            .iter()
+            .any(|alias| species.eq_ignore_ascii_case(alias));
+    if wants_empty {
+        return (current != EMPTY_SPECIES).then_some(TimelapseEdit::Empty);
+    }
+    let canonical = canonical_label(species);
+    if canonical.is_empty() || canonical == current {
+        return None;
+    }
+    Some(TimelapseEdit::Species(species.to_string()))
+}
+
+impl UiApp {
+    /// Merges an edited Timelapse CSV into the current scan as manual labels.
+    pub(crate) fn import_timelapse_csv(&mut self) {
+        let Some(root) = self.gekozen_map.clone() else {
+            return;
+        };
+        let Some(path) = FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_directory(&root)
+            .pick_file()
+        else {
+            return;
+        };
+        let rows = match read_timelapse_csv(&path) {
+            Ok(rows) => rows,
+            Err(err) => {
+                self.status = format!("{}: {err}", self.t("timelapse-import-failed"));
+                return;
+            }
+        };
+
+        let by_path: HashMap<String, usize> = self
+            .rijen
+            .iter()
+            .enumerate()
+            .map(|(idx, info)| (relative_key(&root, &info.file), idx))
+            .collect();
+        let mut groups: BTreeMap<(String, bool), Vec<usize>> = BTreeMap::new();
+        let mut unmatched = 0usize;
+        for row in &rows {
+            let Some(&idx) = by_path.get(&row.relative) else {
+                unmatched += 1;
+                continue;
+            };
+            let current = self.observation_kind(&self.rijen[idx]);
+            match edit_for(row, species_for(&current)) {
+                Some(TimelapseEdit::Empty) => groups
+                    .entry(("achtergrond".to_string(), false))
+                    .or_default()
+                    .push(idx),
+                Some(TimelapseEdit::Species(species)) => {
+                    groups.entry((species, true)).or_default().push(idx)
+                }
+                None => {}
+            }
+        }
+
+        let updated: usize = groups.values().map(Vec::len).sum();
+        for ((label, present), indices) in groups {
+            if present {
+                self.ensure_label_option(&label);
+            }
+            self.assign_manual_category(&indices, label, present);
+        }
+        tracing::info!(
+            "Timelapse-import {}: {updated} aangepast, {unmatched} niet gevonden",
+            path.display()
+        );
+        let mut args = crate::i18n::Args::new();
+        args.insert("updated".into(), updated.into());
+        args.insert("unmatched".into(), unmatched.into());
+        self.status = self.t_args("timelapse-import-done", &args);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::tests::{animal, at, frame};
+    use std::fs;
+
+    #[test]
+    fn export_roundtrips_and_only_edits_become_labels() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = Path::new("/fotos/tuin");
+        let time = at(12, 7, 30, 5);
+        let frames = vec![
+            frame("a.jpg", time, animal("koolmees", None)),
+            frame("cam2/dag1/b.jpg", time, ObservationKind::Blank),
+            frame("c.jpg", time, ObservationKind::Unknown),
+        ];
+        let path = dir.path().join("timelapse.csv");
+        write_timelapse_csv(&path, root, &frames).unwrap();
+
+        let written = fs::read_to_string(&path).unwrap();
+        let mut lines = written.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            "File,RelativePath,DateTime,species,count,notes,DeleteFlag"
+        );
+        assert_eq!(
+            lines.next().unwrap(),
+            "a.jpg,,2026-04-12 07:30:05,koolmees,1,Feedie 90%,false"
+        );
+        assert_eq!(
+            lines.next().unwrap(),
+            "b.jpg,cam2\\dag1,2026-04-12 07:30:05,empty,0,,false"
+        );
+
+        let rows = read_timelapse_csv(&path).unwrap();
+        assert_eq!(rows[1].relative, "cam2/dag1/b.jpg");
+        let current: Vec<&str> = frames.iter().map(|f| species_for(&f.kind)).collect();
+        for (row, current) in rows.iter().zip(&current) {
+            assert_eq!(edit_for(row, current), None);
+        }
+
+        let edited = |species: &str, delete: bool| TimelapseRow {
+            relative: "a.jpg".to_string(),
+            species: species.to_string(),
+            delete,
+        };
+        assert_eq!(edit_for(&edited("Koolmees", false), "koolmees"), None);
+        assert_eq!(
+            edit_for(&edited("Pimpelmees", false), "koolmees"),
+            Some(TimelapseEdit::Species("Pimpelmees".to_string()))
+        );
+        assert_eq!(
+            edit_for(&edited("Blank", false), "koolmees"),
+            Some(TimelapseEdit::Empty)
+        );
+        assert_eq!(
+            edit_for(&edited("koolmees", true), "koolmees"),
+            Some(TimelapseEdit::Empty)
+        );
+        assert_eq!(edit_for(&edited("", false), ""), None);
+    }
+}
@@ -1,14 +1,23 @@
 #![cfg_attr(all(windows, not(debug_assertions)), windows_subsystem = "windows")]
 //! Entry point for the Feedie egui desktop application.
 
+mod annotation;
 mod app;
+mod cli;
 mod export;
 mod i18n;
 mod manifest;
 mod model;
+mod privacy;
 mod roboflow;
 mod settings_store;
+mod signing;
+#[cfg(test)]
+mod test_support;
+mod training_export;
+mod upload_queue;
 mod util;
+mod xmp;
 
 use app::UiApp;
 use eframe::{NativeOptions, egui};
@@ -74,6 +83,11 @@ fn main() {
     #[cfg(debug_assertions)]
     tracing_subscriber::fmt::init();
 
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+
     #[cfg(target_os = "linux")]
     apply_crostini_x11_workaround();
 
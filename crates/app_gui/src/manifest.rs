@@ -1,27 +1,37 @@
 //! Handling of remote manifests and model downloads.
 
-use crate::app::{LABEL_FILE_NAME, MANIFEST_URL, MODEL_FILE_NAME, UiApp, VERSION_FILE_NAME};
-use crate::model::{normalize_model_version, read_model_version_from};
+use crate::app::{
+    LABEL_FILE_NAME, MANIFEST_URL, MODEL_DOWNLOADS_DIR, MODEL_FILE_NAME, MODEL_NOTES_FILE_NAME,
+    UiApp, VERSION_FILE_NAME,
+};
+use crate::model::{
+    install_model_version, normalize_model_version, read_model_version_from, safe_dir_name,
+    set_active_model, unpack_model_archive, validate_model_dir,
+};
+use crate::signing::{SignatureError, update_public_key, verify_file_signature, verify_signature};
 use anyhow::{Context, anyhow};
+use ed25519_dalek::VerifyingKey;
 use eframe::egui;
 use hex::encode as hex_encode;
+use reqwest::StatusCode;
 use reqwest::blocking::Client;
+use reqwest::header::RANGE;
 use semver::Version;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
 use std::env;
 use std::fs;
-use std::io;
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, TryRecvError};
 use std::thread;
 use std::time::Duration;
-use tempfile::tempdir;
-use zip::ZipArchive;
 
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 use std::process::Command;
 #[cfg(target_os = "windows")]
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -37,15 +47,77 @@ const CREATE_NO_WINDOW: u32 = 0x08000000;
 pub(crate) struct UpdateSummary {
     pub(crate) latest_app: String,
     pub(crate) app_url: String,
-    pub(crate) app_windows_url: Option<String>,
-    pub(crate) app_windows_sha256: Option<String>,
-    pub(crate) app_windows_size_mb: Option<f32>,
+    /// Installer or AppImage for this platform, when it can update itself.
+    pub(crate) app_asset: Option<PlatformAsset>,
     pub(crate) latest_model: String,
     pub(crate) model_url: String,
     pub(crate) app_update_available: bool,
     pub(crate) model_update_available: bool,
     pub(crate) model_size_mb: Option<f32>,
+    pub(crate) model_checks: PackageChecks,
     pub(crate) model_notes: Option<String>,
+    /// Notes of every app version after the running one, newest first.
+    pub(crate) app_release_notes: Vec<ReleaseNote>,
+    /// Notes of every model version after the installed one, newest first.
+    pub(crate) model_release_notes: Vec<ReleaseNote>,
+    /// Newest model on the channel when the running app cannot load it.
+    pub(crate) incompatible_model: Option<String>,
+    pub(crate) packs: Vec<ModelPack>,
+}
+
+/// Release channel the updater follows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    pub(crate) const ALL: [UpdateChannel; 2] = [UpdateChannel::Stable, UpdateChannel::Beta];
+
+    /// Key under `channels` in the manifest; stable uses the top-level entries.
+    fn manifest_key(self) -> Option<&'static str> {
+        match self {
+            UpdateChannel::Stable => None,
+            UpdateChannel::Beta => Some("beta"),
+        }
+    }
+
+    pub(crate) fn label_key(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "updates-channel-stable",
+            UpdateChannel::Beta => "updates-channel-beta",
+        }
+    }
+}
+
+/// Expected checksum, exact size and signature of a model package from the
+/// manifest.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PackageChecks {
+    /// Hex SHA256 of the package; a package without one is never installed.
+    pub(crate) sha256: String,
+    pub(crate) size_bytes: Option<u64>,
+    /// Hex-encoded ed25519 signature over the package file.
+    pub(crate) signature: Option<String>,
+}
+
+/// Regional model pack offered in the manifest next to the default model.
+#[derive(Clone)]
+pub(crate) struct ModelPack {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) region: String,
+    pub(crate) version: String,
+    pub(crate) url: String,
+    pub(crate) species: Vec<String>,
+    pub(crate) size_mb: Option<f32>,
+    pub(crate) checks: PackageChecks,
+    pub(crate) notes: Option<String>,
+    /// False when the running app is outside the pack's supported range.
+    pub(crate) compatible: bool,
 }
 
 /// Status for fetching the remote manifest.
@@ -54,7 +126,7 @@ pub(crate) enum ManifestStatus {
     #[default]
     Idle,
     Checking,
-    Ready(UpdateSummary),
+    Ready(Box<UpdateSummary>),
     Error(ManifestError),
 }
 
@@ -62,12 +134,15 @@ pub(crate) enum ManifestStatus {
 #[derive(Clone, Copy)]
 pub(crate) enum ManifestError {
     CheckFailed,
+    /// The manifest was fetched but its signature could not be verified.
+    Signature(SignatureError),
 }
 
 impl ManifestError {
     fn message_key(self) -> &'static str {
         match self {
             ManifestError::CheckFailed => "updates-check-failed",
+            ManifestError::Signature(err) => err.message_key(),
         }
     }
 }
@@ -77,7 +152,10 @@ impl ManifestError {
 pub(crate) enum ModelDownloadStatus {
     #[default]
     Idle,
-    Downloading,
+    /// Bytes received so far and the expected total, when known.
+    Downloading(u64, Option<u64>),
+    /// A model has been downloaded into the staging folder and awaits review.
+    Staged(String),
     Success(String),
     Error(String),
 }
@@ -96,18 +174,22 @@ pub(crate) enum AppDownloadStatus {
 #[derive(Clone, Copy)]
 pub(crate) enum AppDownloadError {
     DownloadFailed,
+    HashMissing,
     HashMismatch,
     UpdaterMissing,
     InstallFailed,
+    Signature(SignatureError),
 }
 
 impl AppDownloadError {
     fn message_key(self) -> &'static str {
         match self {
             AppDownloadError::DownloadFailed => "updates-app-download-failed",
+            AppDownloadError::HashMissing => "updates-app-hash-missing",
             AppDownloadError::HashMismatch => "updates-app-hash-mismatch",
             AppDownloadError::UpdaterMissing => "updates-app-updater-missing",
             AppDownloadError::InstallFailed => "updates-app-install-failed",
+            AppDownloadError::Signature(err) => err.message_key(),
         }
     }
 }
@@ -124,10 +206,14 @@ impl UiApp {
         self.update_rx = Some(rx);
         self.manifest_status = ManifestStatus::Checking;
         thread::spawn(move || {
-            let result = fetch_remote_manifest().map_err(|err| {
-                tracing::warn!("Manifest fetch failed: {err}");
-                ManifestError::CheckFailed
-            });
+            let result = fetch_remote_manifest(&manifest_url(), update_public_key().as_ref())
+                .map_err(|err| {
+                    tracing::warn!("Manifest fetch failed: {err:#}");
+                    match err.downcast_ref::<SignatureError>() {
+                        Some(signature) => ManifestError::Signature(*signature),
+                        None => ManifestError::CheckFailed,
+                    }
+                });
             let _ = tx.send(result);
         });
     }
@@ -147,36 +233,83 @@ impl UiApp {
     }
 
     /// Applies the newly fetched manifest to the UI state and stamps the change.
+    ///
+    /// Only entries of the selected channel are considered, and the model is
+    /// the newest one whose app version range includes the running app.
     pub(crate) fn apply_manifest(&mut self, manifest: RemoteManifest) {
-        let latest_app = manifest.app.latest.clone();
-        let latest_model = manifest.model.latest.clone();
+        let channel = self.update_channel;
+        let app = manifest.select_app(channel);
+        let latest_app = app.latest.clone();
+        let app_asset = platform_asset(
+            app,
+            env::consts::OS,
+            env::consts::ARCH,
+            env::var_os("APPIMAGE").is_some(),
+        )
+        .cloned();
+        let (selected, incompatible_model) = manifest.select_model(channel, &self.app_version);
+        let model = selected.unwrap_or(&manifest.model);
+        let latest_model = model.latest.clone();
         let normalized_latest_model = normalize_model_version(&latest_model);
         let normalized_current_model = normalize_model_version(&self.model_version);
-        let (app_windows_url, app_windows_sha256, app_windows_size_mb) =
-            match manifest.app.windows.as_ref() {
-                Some(asset) => (Some(asset.url.clone()), asset.sha256.clone(), asset.size_mb),
-                None => (None, None, None),
-            };
-        let mut summary = UpdateSummary {
+        let model_update_available = selected.is_some()
+            && version_is_newer(&normalized_latest_model, &normalized_current_model);
+        let summary = UpdateSummary {
             latest_app: latest_app.clone(),
-            app_url: manifest.app.url.clone(),
-            app_windows_url,
-            app_windows_sha256,
-            app_windows_size_mb,
+            app_url: app.url.clone(),
+            app_asset,
             latest_model: latest_model.clone(),
-            model_url: manifest.model.url.clone(),
+            model_url: model.url.clone(),
             app_update_available: version_is_newer(&latest_app, &self.app_version),
-            model_update_available: version_is_newer(
-                &normalized_latest_model,
-                &normalized_current_model,
+            model_update_available,
+            model_size_mb: model.size_mb,
+            model_checks: PackageChecks {
+                sha256: model.sha256.clone().unwrap_or_default(),
+                size_bytes: model.size_bytes,
+                signature: model.signature.clone(),
+            },
+            model_notes: model.notes.clone(),
+            app_release_notes: release_notes(
+                manifest.app_notes(channel),
+                &self.app_version,
+                &latest_app,
             ),
-            model_size_mb: manifest.model.size_mb,
-            model_notes: manifest.model.notes.clone(),
+            model_release_notes: if model_update_available {
+                release_notes(
+                    manifest.model_notes(channel),
+                    &self.model_version,
+                    &latest_model,
+                )
+            } else {
+                Vec::new()
+            },
+            incompatible_model: incompatible_model.map(|entry| entry.latest.clone()),
+            packs: manifest
+                .packs
+                .into_iter()
+                .map(|pack| ModelPack {
+                    compatible: app_version_in_range(
+                        &self.app_version,
+                        pack.min_app_version.as_deref(),
+                        pack.max_app_version.as_deref(),
+                    ),
+                    id: pack.id,
+                    name: pack.name,
+                    region: pack.region,
+                    version: pack.version,
+                    url: pack.url,
+                    species: pack.species,
+                    size_mb: pack.size_mb,
+                    checks: PackageChecks {
+                        sha256: pack.sha256.unwrap_or_default(),
+                        size_bytes: pack.size_bytes,
+                        signature: pack.signature,
+                    },
+                    notes: pack.notes,
+                })
+                .collect(),
         };
-        if !summary.app_update_available && !summary.model_update_available {
-            summary.model_notes = manifest.model.notes;
-        }
-        self.manifest_status = ManifestStatus::Ready(summary);
+        self.manifest_status = ManifestStatus::Ready(Box::new(summary));
     }
 
     /// Renders the update information inside the settings panel.
@@ -188,6 +321,17 @@ impl UiApp {
         ui.separator();
         ui.add_space(6.0);
         ui.heading(self.t("updates-title"));
+        self.render_update_channel(ui);
+        if ui
+            .add_enabled(
+                self.model_import_rx.is_none(),
+                egui::Button::new(self.t("model-import")),
+            )
+            .on_hover_text(self.t("model-import-help"))
+            .clicked()
+        {
+            self.start_model_import();
+        }
         match &self.manifest_status {
             ManifestStatus::Idle => {
                 if ui.button(self.t("updates-check")).clicked() {
@@ -211,10 +355,16 @@ impl UiApp {
                         self.t("updates-app-available"),
                         summary.latest_app
                     ));
-                    if let Some(size) = summary.app_windows_size_mb.filter(|size| *size > 0.0) {
+                    if let Some(size) = summary
+                        .app_asset
+                        .as_ref()
+                        .and_then(|asset| asset.size_mb)
+                        .filter(|size| *size > 0.0)
+                    {
                         ui.label(format!("{}: {:.1} MB", self.t("updates-app-size"), size));
                     }
-                    if cfg!(target_os = "windows") && summary.app_windows_url.is_some() {
+                    self.render_release_notes(ui, "app", &summary.app_release_notes);
+                    if summary.app_asset.is_some() {
                         self.render_app_download_actions(ui, &summary);
                     } else {
                         ui.hyperlink_to(self.t("updates-open-download"), &summary.app_url);
@@ -232,14 +382,24 @@ impl UiApp {
                     if let Some(size) = summary.model_size_mb {
                         ui.label(format!("{}: {:.1} MB", self.t("updates-model-size"), size));
                     }
-                    if let Some(notes) = &summary.model_notes {
-                        ui.label(notes);
-                    }
+                    self.render_release_notes(ui, "model", &summary.model_release_notes);
                     self.render_model_download_actions(ui, &summary);
                 } else {
                     ui.label(self.t("updates-model-latest"));
                     self.render_model_download_feedback(ui);
                 }
+                if let Some(version) = &summary.incompatible_model {
+                    let mut args = crate::i18n::Args::new();
+                    args.insert("version".into(), version.clone().into());
+                    ui.colored_label(
+                        egui::Color32::from_rgb(200, 120, 0),
+                        self.t_args("updates-model-needs-app", &args),
+                    );
+                }
+                if !summary.packs.is_empty() {
+                    ui.add_space(4.0);
+                    self.render_model_packs(ui, &summary.packs);
+                }
                 ui.add_space(6.0);
                 if ui.button(self.t("updates-check-again")).clicked() {
                     self.request_manifest_refresh();
@@ -248,6 +408,44 @@ impl UiApp {
         }
     }
 
+    /// Lets the user follow the stable or the beta channel.
+    fn render_update_channel(&mut self, ui: &mut egui::Ui) {
+        let mut channel = self.update_channel;
+        ui.horizontal(|ui| {
+            ui.label(self.t("updates-channel"))
+                .on_hover_text(self.t("updates-channel-help"));
+            egui::ComboBox::from_id_salt("update-channel")
+                .selected_text(self.t(channel.label_key()))
+                .show_ui(ui, |ui| {
+                    for option in UpdateChannel::ALL {
+                        ui.selectable_value(&mut channel, option, self.t(option.label_key()));
+                    }
+                });
+        });
+        if channel != self.update_channel {
+            self.update_channel = channel;
+            self.persist_settings();
+            self.request_manifest_refresh();
+        }
+    }
+
+    /// Shows the notes of the versions between the installed and the latest one.
+    fn render_release_notes(&self, ui: &mut egui::Ui, id: &str, notes: &[ReleaseNote]) {
+        if notes.is_empty() {
+            return;
+        }
+        egui::CollapsingHeader::new(self.t("updates-release-notes"))
+            .id_salt(("release-notes", id))
+            .default_open(notes.len() == 1)
+            .show(ui, |ui| {
+                for note in notes {
+                    ui.label(egui::RichText::new(&note.version).strong());
+                    ui.label(&note.notes);
+                    ui.add_space(2.0);
+                }
+            });
+    }
+
     /// Shows the call-to-action buttons for downloading the new app version.
     pub(crate) fn render_app_download_actions(
         &mut self,
@@ -287,8 +485,12 @@ impl UiApp {
                     self.start_model_download(summary);
                 }
             }
-            ModelDownloadStatus::Downloading => {
+            ModelDownloadStatus::Downloading(done, total) => {
                 ui.label(self.t("updates-model-downloading"));
+                render_byte_progress(ui, *done, *total);
+            }
+            ModelDownloadStatus::Staged(_) => {
+                self.render_staged_model_actions(ui);
             }
             ModelDownloadStatus::Error(err) => {
                 ui.colored_label(egui::Color32::RED, err);
@@ -306,13 +508,17 @@ impl UiApp {
     }
 
     /// Displays feedback about the last download attempt when no update is available.
-    pub(crate) fn render_model_download_feedback(&self, ui: &mut egui::Ui) {
+    pub(crate) fn render_model_download_feedback(&mut self, ui: &mut egui::Ui) {
         match &self.model_download_status {
             ModelDownloadStatus::Idle => {
                 ui.label(self.t("updates-no-downloads"));
             }
-            ModelDownloadStatus::Downloading => {
+            ModelDownloadStatus::Downloading(done, total) => {
                 ui.label(self.t("updates-download-progress"));
+                render_byte_progress(ui, *done, *total);
+            }
+            ModelDownloadStatus::Staged(_) => {
+                self.render_staged_model_actions(ui);
             }
             ModelDownloadStatus::Error(err) => {
                 ui.colored_label(egui::Color32::RED, err);
@@ -331,16 +537,20 @@ impl UiApp {
         ) {
             return;
         }
-        let Some(url) = summary.app_windows_url.clone() else {
+        let Some(asset) = summary.app_asset.clone() else {
             return;
         };
-        let expected_hash = summary.app_windows_sha256.clone();
         let version = summary.latest_app.clone();
         let (tx, rx) = mpsc::channel();
         self.app_download_rx = Some(rx);
         self.app_download_status = AppDownloadStatus::Downloading;
         thread::spawn(move || {
-            let result = download_app_installer(&url, expected_hash.as_deref(), &version);
+            let result = download_app_installer(
+                &asset.url,
+                asset.sha256.as_deref().unwrap_or_default(),
+                asset.signature.as_deref(),
+                &version,
+            );
             let _ = tx.send(result);
         });
     }
@@ -420,7 +630,29 @@ impl UiApp {
             std::process::exit(0);
         }
 
-        #[cfg(not(target_os = "windows"))]
+        #[cfg(target_os = "linux")]
+        {
+            let Some(target) = env::var_os("APPIMAGE").map(PathBuf::from) else {
+                tracing::warn!("APPIMAGE is niet gezet; zelf-update niet mogelijk");
+                return Err(AppDownloadError::InstallFailed);
+            };
+            let backup = replace_appimage(installer_path, &target).map_err(|err| {
+                tracing::warn!("Kon AppImage {} niet vervangen: {err:#}", target.display());
+                AppDownloadError::InstallFailed
+            })?;
+            if let Some(parent) = installer_path.parent() {
+                let _ = fs::remove_file(installer_path);
+                let _ = fs::remove_dir(parent);
+            }
+            if let Err(err) = Command::new(&target).spawn() {
+                tracing::warn!("Nieuwe AppImage kon niet starten: {err}; vorige versie teruggezet");
+                let _ = fs::rename(&backup, &target);
+                return Err(AppDownloadError::InstallFailed);
+            }
+            std::process::exit(0);
+        }
+
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
         {
             let _ = installer_path;
             Err(AppDownloadError::InstallFailed)
@@ -429,49 +661,192 @@ impl UiApp {
 
     /// Initiates the download of a new model in the background.
     pub(crate) fn start_model_download(&mut self, summary: &UpdateSummary) {
-        if matches!(self.model_download_status, ModelDownloadStatus::Downloading) {
+        if matches!(
+            self.model_download_status,
+            ModelDownloadStatus::Downloading(..)
+        ) {
             return;
         }
+        self.clear_model_comparison();
         let (tx, rx) = mpsc::channel();
         self.model_download_rx = Some(rx);
-        self.model_download_status = ModelDownloadStatus::Downloading;
+        self.model_download_status = ModelDownloadStatus::Downloading(0, None);
         let url = summary.model_url.clone();
-        let target_root = self.model_root.clone();
+        let staging = self.staging_model_dir();
+        let checks = summary.model_checks.clone();
         let version = summary.latest_model.clone();
+        let partial = partial_download_path(&self.model_root, &version, &checks);
+        let notes = summary.model_notes.clone();
+        let app_version = self.app_version.clone();
+        let language = self.language;
         thread::spawn(move || {
-            let result = download_and_install_model(&url, &target_root, &version)
-                .map(|_| version.clone())
-                .map_err(|e| e.to_string());
-            let _ = tx.send(result);
+            let _ = fs::remove_dir_all(&staging);
+            let tx_progress = tx.clone();
+            let result = download_and_install_model(
+                &url,
+                &partial,
+                &checks,
+                &staging,
+                &version,
+                &app_version,
+                |done, total| {
+                    let _ = tx_progress.send(ModelDownloadMsg::Progress(done, total));
+                },
+            )
+            .and_then(|_| {
+                if let Some(notes) = notes {
+                    fs::write(staging.join(MODEL_NOTES_FILE_NAME), notes)
+                        .context("Kon modelnotities niet opslaan")?;
+                }
+                Ok(())
+            })
+            .map(|_| version.clone())
+            .map_err(|e| update_error_message(language, &e));
+            let _ = tx.send(ModelDownloadMsg::Done(result));
         });
     }
 
     /// Polls the download task and updates the UI with the result.
+    ///
+    /// A finished download is kept in the staging folder and immediately
+    /// compared with the active model on the current scan.
     pub(crate) fn poll_model_download(&mut self) {
-        if let Some(rx) = self.model_download_rx.take() {
+        let Some(rx) = self.model_download_rx.take() else {
+            return;
+        };
+        loop {
             match rx.try_recv() {
-                Ok(Ok(version)) => {
-                    let normalized = normalize_model_version(&version);
-                    self.model_download_status = ModelDownloadStatus::Success(format!(
-                        "{} {normalized}.",
-                        self.t("updates-model-installed")
-                    ));
-                    self.model_version = read_model_version_from(&self.model_version_path());
-                    self.label_options = Self::load_label_options_from(&self.labels_path());
-                    self.request_manifest_refresh();
+                Ok(ModelDownloadMsg::Progress(done, total)) => {
+                    self.model_download_status = ModelDownloadStatus::Downloading(done, total);
                 }
-                Ok(Err(err)) => {
+                Ok(ModelDownloadMsg::Done(Ok(_))) => {
+                    let version =
+                        read_model_version_from(&self.staging_model_dir().join(VERSION_FILE_NAME));
+                    self.start_model_comparison(&version);
+                    self.model_download_status = ModelDownloadStatus::Staged(version);
+                    return;
+                }
+                Ok(ModelDownloadMsg::Done(Err(err))) => {
                     self.model_download_status = ModelDownloadStatus::Error(err);
+                    return;
                 }
                 Err(TryRecvError::Empty) => {
                     self.model_download_rx = Some(rx);
+                    return;
                 }
                 Err(TryRecvError::Disconnected) => {
                     self.model_download_status =
                         ModelDownloadStatus::Error(self.t("updates-download-channel-closed"));
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Restores the staged state when a downloaded model was left unreviewed.
+    pub(crate) fn detect_staged_model(&mut self) {
+        let staging = self.staging_model_dir();
+        if staging.join(MODEL_FILE_NAME).exists() && staging.join(LABEL_FILE_NAME).exists() {
+            let version = read_model_version_from(&staging.join(VERSION_FILE_NAME));
+            self.model_download_status = ModelDownloadStatus::Staged(version);
+        }
+    }
+
+    /// Shows the review, activate and discard buttons for a staged model.
+    fn render_staged_model_actions(&mut self, ui: &mut egui::Ui) {
+        let ModelDownloadStatus::Staged(version) = &self.model_download_status else {
+            return;
+        };
+        let version = version.clone();
+        let mut args = crate::i18n::Args::new();
+        args.insert("version".into(), normalize_model_version(&version).into());
+        ui.label(self.t_args("updates-model-staged", &args));
+        let comparing = self.comparison_rx.is_some();
+        if comparing {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(self.t("comparison-running"));
+            });
+        }
+        ui.horizontal(|ui| {
+            if self.model_comparison.is_some() {
+                if ui.button(self.t("comparison-show")).clicked()
+                    && let Some(comparison) = self.model_comparison.as_mut()
+                {
+                    comparison.open = true;
                 }
+            } else if ui
+                .add_enabled(
+                    !comparing && !self.scan_in_progress && !self.rijen.is_empty(),
+                    egui::Button::new(self.t("comparison-start")),
+                )
+                .clicked()
+            {
+                self.start_model_comparison(&version);
+            }
+            if ui
+                .add_enabled(!comparing, egui::Button::new(self.t("comparison-activate")))
+                .clicked()
+            {
+                self.activate_staged_model();
+            }
+            if ui
+                .add_enabled(!comparing, egui::Button::new(self.t("comparison-discard")))
+                .clicked()
+            {
+                self.discard_staged_model();
             }
+        });
+    }
+
+    /// Installs the staged model as a new version and makes it the active one.
+    ///
+    /// Earlier versions stay installed so they can be selected again.
+    /// Predictions from a finished comparison are taken over for the current
+    /// scan, unless the personal model is in use.
+    pub(crate) fn activate_staged_model(&mut self) {
+        if self.comparison_rx.is_some() {
+            return;
+        }
+        let staging = self.staging_model_dir();
+        let installed = install_model_version(&self.model_root, &staging)
+            .and_then(|id| set_active_model(&self.model_root, &id));
+        if let Err(err) = installed {
+            self.model_download_status = ModelDownloadStatus::Error(format!(
+                "{}: {err}",
+                self.t("updates-model-activate-failed")
+            ));
+            return;
+        }
+        self.reload_active_model();
+        if self.use_personal_model && self.personal_model_available() {
+            self.clear_model_comparison();
+        } else {
+            self.apply_model_comparison();
+        }
+        self.model_download_status = ModelDownloadStatus::Success(format!(
+            "{} {}.",
+            self.t("updates-model-installed"),
+            normalize_model_version(&self.model_version)
+        ));
+        self.request_manifest_refresh();
+    }
+
+    /// Removes the staged model and keeps the active one.
+    pub(crate) fn discard_staged_model(&mut self) {
+        if self.comparison_rx.is_some() {
+            return;
+        }
+        let staging = self.staging_model_dir();
+        if let Err(err) = fs::remove_dir_all(&staging) {
+            tracing::warn!(
+                "Kon stagingmap {} niet verwijderen: {err}",
+                staging.display()
+            );
         }
+        self.clear_model_comparison();
+        self.model_download_status = ModelDownloadStatus::Idle;
+        self.status = self.t("updates-model-discarded");
     }
 }
 
@@ -480,6 +855,152 @@ impl UiApp {
 pub(crate) struct RemoteManifest {
     app: ManifestEntry,
     model: ModelManifestEntry,
+    #[serde(default)]
+    packs: Vec<ModelPackEntry>,
+    /// Pre-release entries per channel (e.g. `beta`); stable uses `app` and
+    /// `model` above.
+    #[serde(default)]
+    channels: BTreeMap<String, ChannelEntry>,
+}
+
+/// App and model entries published on a non-stable channel.
+#[derive(Debug, Default, Deserialize)]
+struct ChannelEntry {
+    #[serde(default)]
+    app: Option<ManifestEntry>,
+    #[serde(default)]
+    model: Option<ModelManifestEntry>,
+}
+
+impl RemoteManifest {
+    fn channel(&self, channel: UpdateChannel) -> Option<&ChannelEntry> {
+        channel
+            .manifest_key()
+            .and_then(|key| self.channels.get(key))
+    }
+
+    /// Returns the newest app entry on `channel`; beta falls back to stable
+    /// once stable has caught up.
+    fn select_app(&self, channel: UpdateChannel) -> &ManifestEntry {
+        match self.channel(channel).and_then(|entry| entry.app.as_ref()) {
+            Some(app) if compare_versions(&app.latest, &self.app.latest) == Ordering::Greater => {
+                app
+            }
+            _ => &self.app,
+        }
+    }
+
+    /// Model entries on `channel`, including their history.
+    fn model_entries(&self, channel: UpdateChannel) -> Vec<&ModelManifestEntry> {
+        let mut tops = vec![&self.model];
+        tops.extend(self.channel(channel).and_then(|entry| entry.model.as_ref()));
+        tops.into_iter()
+            .flat_map(|entry| std::iter::once(entry).chain(entry.history.iter()))
+            .collect()
+    }
+
+    /// Picks the newest downloadable model the running app can load.
+    ///
+    /// The second value is the newest model on the channel when it is newer
+    /// than the pick but requires another app version.
+    fn select_model(
+        &self,
+        channel: UpdateChannel,
+        app_version: &str,
+    ) -> (Option<&ModelManifestEntry>, Option<&ModelManifestEntry>) {
+        let entries: Vec<&ModelManifestEntry> = self
+            .model_entries(channel)
+            .into_iter()
+            .filter(|entry| !entry.url.trim().is_empty())
+            .collect();
+        let compatible = newest_model(
+            entries
+                .iter()
+                .copied()
+                .filter(|entry| entry.is_compatible(app_version)),
+        );
+        let blocked = newest_model(entries.iter().copied()).filter(|entry| {
+            !entry.is_compatible(app_version)
+                && compatible.is_none_or(|pick| {
+                    compare_versions(&entry.latest, &pick.latest) == Ordering::Greater
+                })
+        });
+        (compatible, blocked)
+    }
+
+    /// Version and notes of every app release on `channel`.
+    fn app_notes(&self, channel: UpdateChannel) -> Vec<(&str, Option<&str>)> {
+        let mut tops = vec![&self.app];
+        tops.extend(self.channel(channel).and_then(|entry| entry.app.as_ref()));
+        tops.into_iter()
+            .flat_map(|entry| {
+                std::iter::once((entry.latest.as_str(), entry.notes.as_deref())).chain(
+                    entry
+                        .history
+                        .iter()
+                        .map(|note| (note.version.as_str(), note.notes.as_deref())),
+                )
+            })
+            .collect()
+    }
+
+    /// Version and notes of every model release on `channel`.
+    fn model_notes(&self, channel: UpdateChannel) -> Vec<(&str, Option<&str>)> {
+        self.model_entries(channel)
+            .into_iter()
+            .map(|entry| (entry.latest.as_str(), entry.notes.as_deref()))
+            .collect()
+    }
+}
+
+fn newest_model<'a>(
+    entries: impl Iterator<Item = &'a ModelManifestEntry>,
+) -> Option<&'a ModelManifestEntry> {
+    entries.max_by(|a, b| compare_versions(&a.latest, &b.latest))
+}
+
+/// Release notes of one app or model version.
+#[derive(Clone, Debug)]
+pub(crate) struct ReleaseNote {
+    pub(crate) version: String,
+    pub(crate) notes: String,
+}
+
+/// Collects the notes of the versions after `installed` up to and including
+/// `latest`, newest first.
+fn release_notes(
+    entries: Vec<(&str, Option<&str>)>,
+    installed: &str,
+    latest: &str,
+) -> Vec<ReleaseNote> {
+    let mut seen = HashSet::new();
+    let mut notes: Vec<ReleaseNote> = entries
+        .into_iter()
+        .filter(|(version, _)| {
+            compare_versions(version, installed) == Ordering::Greater
+                && compare_versions(version, latest) != Ordering::Greater
+        })
+        .filter_map(|(version, notes)| {
+            let notes = notes?.trim();
+            (!notes.is_empty() && seen.insert(normalize_model_version(version))).then(|| {
+                ReleaseNote {
+                    version: version.to_string(),
+                    notes: notes.to_string(),
+                }
+            })
+        })
+        .collect();
+    notes.sort_by(|a, b| compare_versions(&b.version, &a.version));
+    notes
+}
+
+/// Orders app or model versions, ignoring `model-`/`v` prefixes.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parse = |value: &str| Version::parse(&normalize_model_version(value)).ok();
+    match (parse(a), parse(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => normalize_model_version(a).cmp(&normalize_model_version(b)),
+    }
 }
 
 /// Manifest subsection describing the application binary.
@@ -488,47 +1009,170 @@ struct ManifestEntry {
     latest: String,
     url: String,
     #[serde(default)]
+    notes: Option<String>,
+    /// Earlier releases, used for their notes.
+    #[serde(default)]
+    history: Vec<HistoryNote>,
+    #[serde(default)]
     windows: Option<PlatformAsset>,
+    /// Linux AppImages keyed by CPU architecture (`x86_64`, `aarch64`).
+    #[serde(default)]
+    linux: BTreeMap<String, PlatformAsset>,
 }
 
+/// Earlier app release listed under `app.history`.
 #[derive(Debug, Deserialize)]
-struct PlatformAsset {
-    url: String,
+struct HistoryNote {
+    version: String,
     #[serde(default)]
-    sha256: Option<String>,
+    notes: Option<String>,
+}
+
+/// Downloadable app build for one platform.
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct PlatformAsset {
+    pub(crate) url: String,
     #[serde(default)]
-    size_mb: Option<f32>,
+    pub(crate) sha256: Option<String>,
+    #[serde(default)]
+    pub(crate) signature: Option<String>,
+    #[serde(default)]
+    pub(crate) size_mb: Option<f32>,
+}
+
+/// Picks the app build that can be installed from inside the app.
+///
+/// Windows uses the installer; on Linux only a running AppImage can replace
+/// itself. Other setups get the download link instead.
+fn platform_asset<'a>(
+    app: &'a ManifestEntry,
+    os: &str,
+    arch: &str,
+    appimage: bool,
+) -> Option<&'a PlatformAsset> {
+    match os {
+        "windows" => app.windows.as_ref(),
+        "linux" if appimage => app.linux.get(arch),
+        _ => None,
+    }
 }
 
 /// Manifest subsection describing the downloadable recognition model.
+///
+/// Entries under `history` use the same layout with `version` instead of
+/// `latest`; without `url` they only contribute release notes.
 #[derive(Debug, Deserialize)]
 struct ModelManifestEntry {
+    #[serde(alias = "version")]
     latest: String,
+    #[serde(default)]
     url: String,
     #[serde(default)]
     _labels_hash: Option<String>,
     #[serde(default)]
     size_mb: Option<f32>,
     #[serde(default)]
+    sha256: Option<String>,
+    #[serde(default)]
+    size_bytes: Option<u64>,
+    #[serde(default)]
+    signature: Option<String>,
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    min_app_version: Option<String>,
+    #[serde(default)]
+    max_app_version: Option<String>,
+    #[serde(default)]
+    history: Vec<ModelManifestEntry>,
+}
+
+impl ModelManifestEntry {
+    fn is_compatible(&self, app_version: &str) -> bool {
+        app_version_in_range(
+            app_version,
+            self.min_app_version.as_deref(),
+            self.max_app_version.as_deref(),
+        )
+    }
+}
+
+/// Manifest subsection describing a regional model pack.
+#[derive(Debug, Deserialize)]
+struct ModelPackEntry {
+    id: String,
+    name: String,
+    region: String,
+    version: String,
+    url: String,
+    #[serde(default)]
+    species: Vec<String>,
+    #[serde(default)]
+    size_mb: Option<f32>,
+    #[serde(default)]
+    sha256: Option<String>,
+    #[serde(default)]
+    size_bytes: Option<u64>,
+    #[serde(default)]
+    signature: Option<String>,
+    #[serde(default)]
     notes: Option<String>,
+    #[serde(default)]
+    min_app_version: Option<String>,
+    #[serde(default)]
+    max_app_version: Option<String>,
 }
 
-/// Downloads and parses the JSON manifest that describes available updates.
-fn fetch_remote_manifest() -> anyhow::Result<RemoteManifest> {
+/// Downloads, verifies and parses the JSON manifest that describes available
+/// updates.
+///
+/// The detached signature is fetched from `<url>.sig`; a manifest without a
+/// valid signature from `key` is refused before it is parsed.
+fn fetch_remote_manifest(url: &str, key: Option<&VerifyingKey>) -> anyhow::Result<RemoteManifest> {
     let client = Client::builder()
         .timeout(Duration::from_secs(5))
         .build()
         .context("HTTP-client kon niet worden opgebouwd")?;
-    let response = client
-        .get(manifest_url())
+    let body = client
+        .get(url)
         .send()
         .context("Manifest kon niet worden opgehaald")?
         .error_for_status()
-        .context("Manifest gaf een foutstatus terug")?;
-    let manifest = response
-        .json::<RemoteManifest>()
-        .context("Manifest kon niet worden geparseerd")?;
-    Ok(manifest)
+        .context("Manifest gaf een foutstatus terug")?
+        .bytes()
+        .context("Manifest kon niet worden gelezen")?;
+    let signature = client
+        .get(signature_url(url))
+        .send()
+        .context("Handtekening van het manifest kon niet worden opgehaald")?;
+    let signature = if signature.status().is_success() {
+        Some(
+            signature
+                .text()
+                .context("Handtekening van het manifest kon niet worden gelezen")?,
+        )
+    } else {
+        None
+    };
+    verify_signature(key, &body, signature.as_deref())?;
+    serde_json::from_slice(&body).context("Manifest kon niet worden geparseerd")
+}
+
+/// Location of the detached signature that belongs to `url`.
+fn signature_url(url: &str) -> String {
+    match url.split_once('?') {
+        Some((path, query)) => format!("{path}.sig?{query}"),
+        None => format!("{url}.sig"),
+    }
+}
+
+/// Turns a failed update into a message for the UI; signature problems are
+/// localized, other errors keep their technical detail.
+pub(crate) fn update_error_message(language: crate::i18n::Language, err: &anyhow::Error) -> String {
+    match err.downcast_ref::<SignatureError>() {
+        Some(signature) => crate::i18n::t_for(language, signature.message_key()),
+        None => format!("{err:#}"),
+    }
 }
 
 fn manifest_url() -> String {
@@ -546,12 +1190,37 @@ fn version_is_newer(latest: &str, current: &str) -> bool {
     }
 }
 
-/// Downloads the app installer from `url` into a temporary location.
+/// Checks whether `app_version` lies within the optional inclusive range.
+///
+/// Versions that cannot be parsed are treated as compatible.
+pub(crate) fn app_version_in_range(
+    app_version: &str,
+    min: Option<&str>,
+    max: Option<&str>,
+) -> bool {
+    let Ok(app) = Version::parse(app_version.trim_start_matches('v')) else {
+        return true;
+    };
+    let parse =
+        |value: Option<&str>| value.and_then(|v| Version::parse(v.trim_start_matches('v')).ok());
+    parse(min).is_none_or(|min| app >= min) && parse(max).is_none_or(|max| app <= max)
+}
+
+/// Downloads the app installer or AppImage from `url` into a temporary
+/// location.
+///
+/// `expected_sha256` is required: without it nothing is downloaded.
 fn download_app_installer(
     url: &str,
-    expected_sha256: Option<&str>,
+    expected_sha256: &str,
+    signature: Option<&str>,
     version: &str,
 ) -> Result<PathBuf, AppDownloadError> {
+    let expected_sha256 = expected_sha256.trim();
+    if expected_sha256.is_empty() {
+        tracing::warn!("App-update zonder SHA256 in het manifest geweigerd");
+        return Err(AppDownloadError::HashMissing);
+    }
     let client = Client::builder()
         .timeout(Duration::from_secs(300))
         .build()
@@ -572,7 +1241,13 @@ fn download_app_installer(
             AppDownloadError::DownloadFailed
         })?;
 
-    let file_name = file_name_from_url(url).unwrap_or_else(|| format!("FeedieSetup-{version}.exe"));
+    let file_name = file_name_from_url(url).unwrap_or_else(|| {
+        if cfg!(target_os = "windows") {
+            format!("FeedieSetup-{version}.exe")
+        } else {
+            format!("Feedie-{version}.AppImage")
+        }
+    });
     let target_dir = env::temp_dir().join("FeedieUpdate").join(version);
     fs::create_dir_all(&target_dir).map_err(|err| {
         tracing::warn!("Kon tijdelijke update-map niet maken: {err}");
@@ -590,29 +1265,66 @@ fn download_app_installer(
         })?;
     }
 
-    let expected_sha256 = expected_sha256.and_then(|value| {
-        let trimmed = value.trim();
-        if trimmed.is_empty() {
-            None
-        } else {
-            Some(trimmed)
-        }
-    });
+    let actual = sha256_file(&installer_path).map_err(|err| {
+        tracing::warn!("Kon SHA256 niet berekenen: {err}");
+        AppDownloadError::DownloadFailed
+    })?;
+    if !actual.eq_ignore_ascii_case(expected_sha256) {
+        tracing::warn!("SHA256 mismatch: expected {expected_sha256}, actual {actual}");
+        let _ = fs::remove_file(&installer_path);
+        return Err(AppDownloadError::HashMismatch);
+    }
 
-    if let Some(expected) = expected_sha256 {
-        let actual = sha256_file(&installer_path).map_err(|err| {
-            tracing::warn!("Kon SHA256 niet berekenen: {err}");
-            AppDownloadError::DownloadFailed
-        })?;
-        if !actual.eq_ignore_ascii_case(expected) {
-            tracing::warn!("SHA256 mismatch: expected {expected}, actual {actual}");
-            return Err(AppDownloadError::HashMismatch);
-        }
+    if let Err(err) =
+        verify_file_signature(update_public_key().as_ref(), &installer_path, signature)
+    {
+        tracing::warn!("Installer geweigerd: {err}");
+        let _ = fs::remove_file(&installer_path);
+        return Err(AppDownloadError::Signature(err));
     }
 
     Ok(installer_path)
 }
 
+/// Replaces the AppImage at `target` with the verified download at `new`.
+///
+/// The new file is first copied next to the target so the final step is a
+/// single rename on the same file system. The previous AppImage is kept as
+/// `<name>.bak` and its path returned so a failed relaunch can restore it.
+#[cfg(target_os = "linux")]
+fn replace_appimage(new: &Path, target: &Path) -> anyhow::Result<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let name = target
+        .file_name()
+        .context("AppImage-pad heeft geen bestandsnaam")?
+        .to_string_lossy()
+        .to_string();
+    let staged = target.with_file_name(format!(".{name}.new"));
+    let backup = target.with_file_name(format!("{name}.bak"));
+    fs::copy(new, &staged).with_context(|| {
+        format!(
+            "Kon nieuwe AppImage niet naar {} kopiëren",
+            staged.display()
+        )
+    })?;
+    fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))
+        .context("Kon nieuwe AppImage niet uitvoerbaar maken")?;
+    fs::File::open(&staged)
+        .and_then(|file| file.sync_all())
+        .context("Kon nieuwe AppImage niet wegschrijven")?;
+    let _ = fs::remove_file(&backup);
+    if fs::hard_link(target, &backup).is_err() {
+        fs::copy(target, &backup)
+            .with_context(|| format!("Kon reservekopie {} niet maken", backup.display()))?;
+    }
+    if let Err(err) = fs::rename(&staged, target) {
+        let _ = fs::remove_file(&staged);
+        return Err(err).context("Kon AppImage niet vervangen");
+    }
+    Ok(backup)
+}
+
 fn file_name_from_url(url: &str) -> Option<String> {
     url.split('/')
         .next_back()
@@ -629,71 +1341,493 @@ fn sha256_file(path: &Path) -> anyhow::Result<String> {
     Ok(hex_encode(digest))
 }
 
-/// Downloads the model ZIP from `url` and installs it into `target_root`.
-fn download_and_install_model(url: &str, target_root: &Path, version: &str) -> anyhow::Result<()> {
+/// Messages from the background model download.
+pub(crate) enum ModelDownloadMsg {
+    Progress(u64, Option<u64>),
+    Done(Result<String, String>),
+}
+
+/// Shows a byte progress bar for a running download.
+fn render_byte_progress(ui: &mut egui::Ui, done: u64, total: Option<u64>) {
+    const MB: f32 = 1024.0 * 1024.0;
+    let done_mb = done as f32 / MB;
+    match total.filter(|total| *total > 0) {
+        Some(total) => {
+            ui.add(
+                egui::ProgressBar::new(done as f32 / total as f32)
+                    .text(format!("{done_mb:.1} / {:.1} MB", total as f32 / MB)),
+            );
+        }
+        None => {
+            ui.label(format!("{done_mb:.1} MB"));
+        }
+    }
+}
+
+/// Location of the partially downloaded package `name` (a model version or
+/// pack id plus version), kept across restarts so an interrupted download
+/// can resume.
+///
+/// The file is keyed on the name and the expected SHA256, so two packages
+/// published under the same file name never resume into each other.
+pub(crate) fn partial_download_path(
+    model_root: &Path,
+    name: &str,
+    checks: &PackageChecks,
+) -> PathBuf {
+    let digest = match checks.sha256.trim() {
+        "" => "zonder-sha256".to_string(),
+        sha => sha.to_ascii_lowercase(),
+    };
+    model_root.join(MODEL_DOWNLOADS_DIR).join(format!(
+        "{}-{}.zip.part",
+        safe_dir_name(name),
+        safe_dir_name(&digest)
+    ))
+}
+
+/// Downloads the model ZIP from `url`, verifies it, unpacks it into
+/// `target_root` and validates the unpacked model like an imported package.
+///
+/// The download is written to `partial` and resumed from there when a
+/// previous attempt was interrupted. The partial file is removed once the
+/// package has been unpacked, or when it fails verification or unpacking.
+pub(crate) fn download_and_install_model(
+    url: &str,
+    partial: &Path,
+    checks: &PackageChecks,
+    target_root: &Path,
+    version: &str,
+    app_version: &str,
+    progress: impl FnMut(u64, Option<u64>),
+) -> anyhow::Result<()> {
     let client = Client::builder()
-        .timeout(Duration::from_secs(60))
+        .connect_timeout(Duration::from_secs(30))
+        .timeout(Duration::from_secs(30 * 60))
         .build()
         .context("HTTP-client kon niet worden opgebouwd")?;
-    let mut response = client
-        .get(url)
+    expected_sha256(checks)?;
+    fetch_with_resume(&client, url, partial, checks.size_bytes, progress)?;
+    if let Err(err) = verify_package(partial, checks, update_public_key().as_ref()) {
+        let _ = fs::remove_file(partial);
+        return Err(err);
+    }
+    let unpacked = unpack_model_archive(partial, target_root, Some(version));
+    let _ = fs::remove_file(partial);
+    unpacked?;
+    validate_model_dir(target_root, app_version)
+}
+
+/// Downloads `url` into `partial`, continuing after the bytes already present
+/// with an HTTP range request when the server supports it.
+fn fetch_with_resume(
+    client: &Client,
+    url: &str,
+    partial: &Path,
+    expected_size: Option<u64>,
+    mut progress: impl FnMut(u64, Option<u64>),
+) -> anyhow::Result<()> {
+    if let Some(parent) = partial.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Kon map {} niet aanmaken", parent.display()))?;
+    }
+    let mut offset = fs::metadata(partial).map(|meta| meta.len()).unwrap_or(0);
+    if expected_size.is_some_and(|size| offset > size) {
+        offset = 0;
+    }
+    if offset > 0 && expected_size == Some(offset) {
+        progress(offset, expected_size);
+        return Ok(());
+    }
+    let mut request = client.get(url);
+    if offset > 0 {
+        tracing::info!("Download van {url} hervat vanaf byte {offset}");
+        request = request.header(RANGE, format!("bytes={offset}-"));
+    }
+    let response = request
         .send()
-        .context("Modelupdate kon niet worden opgehaald")?
+        .context("Modelupdate kon niet worden opgehaald")?;
+    if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        // Everything was already received; verification decides.
+        return Ok(());
+    }
+    let mut response = response
         .error_for_status()
         .context("Server gaf een foutstatus terug")?;
-    let temp_dir = tempdir().context("Kon tijdelijke map niet aanmaken")?;
-    let archive_path = temp_dir.path().join("model_update.zip");
-    {
-        let mut file =
-            fs::File::create(&archive_path).context("Kon tijdelijk downloadbestand niet openen")?;
-        io::copy(&mut response, &mut file).context("Download kon niet worden opgeslagen")?;
-    }
-    let extract_dir = temp_dir.path().join("extracted");
-    fs::create_dir_all(&extract_dir).context("Kon tijdelijke uitpakmap niet aanmaken")?;
-    let reader = fs::File::open(&archive_path).context("Kon gedownload bestand niet openen")?;
-    let mut archive = ZipArchive::new(reader).context("Modelupdate is geen geldig ZIP-bestand")?;
-    for i in 0..archive.len() {
-        let mut file = archive
-            .by_index(i)
-            .context("ZIP-bestand kon niet worden gelezen")?;
-        let outpath = extract_dir.join(file.mangled_name());
-        if file.name().ends_with('/') {
-            fs::create_dir_all(&outpath)?;
-        } else {
-            if let Some(parent) = outpath.parent() {
-                fs::create_dir_all(parent)?;
-            }
-            let mut outfile = fs::File::create(&outpath)?;
-            io::copy(&mut file, &mut outfile)?;
-        }
-    }
-    fs::create_dir_all(target_root).context("Kon doelmap voor model niet aanmaken")?;
-    for name in [MODEL_FILE_NAME, LABEL_FILE_NAME] {
-        let src = extract_dir.join(name);
-        if !src.exists() {
-            return Err(anyhow!("Bestand {name} ontbreekt in modelupdate."));
-        }
-        let dest = target_root.join(name);
-        fs::copy(&src, &dest).with_context(|| {
-            format!(
-                "Kopi\u{EB}ren van {} naar {} mislukt",
-                src.display(),
-                dest.display()
-            )
-        })?;
+    let resumed = offset > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+    if !resumed {
+        offset = 0;
     }
-    let version_src = extract_dir.join(VERSION_FILE_NAME);
-    if version_src.exists() {
-        let dest = target_root.join(VERSION_FILE_NAME);
-        fs::copy(&version_src, &dest).with_context(|| {
-            format!(
-                "Kon modelversie niet bijwerken vanuit {}",
-                version_src.display()
-            )
-        })?;
+    let total = expected_size.or_else(|| response.content_length().map(|len| len + offset));
+    let mut file = if resumed {
+        fs::OpenOptions::new().append(true).open(partial)
     } else {
-        fs::write(target_root.join(VERSION_FILE_NAME), version)
-            .context("Kon modelversie niet opslaan")?;
+        fs::File::create(partial)
+    }
+    .context("Kon tijdelijk downloadbestand niet openen")?;
+    let mut buf = vec![0u8; 64 * 1024];
+    progress(offset, total);
+    loop {
+        let read = response
+            .read(&mut buf)
+            .context("Download werd onderbroken; probeer opnieuw om te hervatten")?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buf[..read])
+            .context("Download kon niet worden opgeslagen")?;
+        offset += read as u64;
+        progress(offset, total);
     }
+    file.sync_all()
+        .context("Download kon niet worden opgeslagen")?;
     Ok(())
 }
+
+/// Returns the SHA256 the manifest lists for a package.
+///
+/// Packages without one are refused, so a manifest entry that lacks the
+/// checksum can never be installed unchecked.
+fn expected_sha256(checks: &PackageChecks) -> anyhow::Result<&str> {
+    match checks.sha256.trim() {
+        "" => Err(anyhow!(
+            "Het manifest vermeldt geen controlesom voor dit modelpakket; installatie geweigerd."
+        )),
+        sha => Ok(sha),
+    }
+}
+
+/// Checks the size, SHA256 and signature of a downloaded package against the
+/// manifest.
+fn verify_package(
+    path: &Path,
+    checks: &PackageChecks,
+    key: Option<&VerifyingKey>,
+) -> anyhow::Result<()> {
+    if let Some(expected) = checks.size_bytes {
+        let actual = fs::metadata(path)
+            .context("Kon downloadbestand niet lezen")?
+            .len();
+        if actual != expected {
+            return Err(anyhow!(
+                "Modelpakket heeft een onverwachte grootte ({actual} in plaats van {expected} bytes)."
+            ));
+        }
+    }
+    let expected = expected_sha256(checks)?;
+    let actual = sha256_file(path)?;
+    if !actual.eq_ignore_ascii_case(expected) {
+        tracing::warn!("SHA256 mismatch: expected {expected}, actual {actual}");
+        return Err(anyhow!("Controlesom van het modelpakket klopt niet."));
+    }
+    verify_file_signature(key, path, checks.signature.as_deref())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signing::tests::{sign_hex, test_key};
+    use crate::test_support::MockServer;
+
+    #[test]
+    fn packs_are_optional_and_carry_app_version_range() {
+        let manifest: RemoteManifest = serde_json::from_str(
+            r#"{
+                "app": {"latest": "1.3.1", "url": "https://example.org/app"},
+                "model": {"latest": "1.2.0", "url": "https://example.org/model.zip"},
+                "packs": [{
+                    "id": "scandinavia",
+                    "name": "Skandinavien",
+                    "region": "SE/NO/FI",
+                    "version": "1.0.0",
+                    "url": "https://example.org/scandinavia.zip",
+                    "species": ["Talgoxe", "Blåmes"],
+                    "min_app_version": "1.3.0"
+                }]
+            }"#,
+        )
+        .unwrap();
+        let pack = &manifest.packs[0];
+        assert_eq!(pack.species.len(), 2);
+        assert!(app_version_in_range(
+            "1.3.1",
+            pack.min_app_version.as_deref(),
+            None
+        ));
+        assert!(!app_version_in_range(
+            "1.2.9",
+            pack.min_app_version.as_deref(),
+            None
+        ));
+        assert!(!app_version_in_range("2.0.0", None, Some("1.9.9")));
+
+        let without: RemoteManifest = serde_json::from_str(
+            r#"{"app": {"latest": "1", "url": "u"}, "model": {"latest": "1", "url": "u"}}"#,
+        )
+        .unwrap();
+        assert!(without.packs.is_empty());
+    }
+
+    #[test]
+    fn interrupted_downloads_resume_and_are_verified() {
+        let dir = tempfile::tempdir().unwrap();
+        let body: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+        let key = test_key().verifying_key();
+        let checks = PackageChecks {
+            sha256: hex_encode(Sha256::digest(&body)),
+            size_bytes: Some(body.len() as u64),
+            signature: Some(sign_hex(&body)),
+        };
+        let partial = partial_download_path(dir.path(), "model-v1.2.0", &checks);
+        assert!(partial.ends_with(format!("downloads/model-v1.2.0-{}.zip.part", checks.sha256)));
+        let other = PackageChecks {
+            sha256: "ab".repeat(32),
+            ..checks.clone()
+        };
+        assert_ne!(
+            partial_download_path(dir.path(), "model-v1.2.0", &other),
+            partial
+        );
+        fs::create_dir_all(partial.parent().unwrap()).unwrap();
+        fs::write(&partial, &body[..80_000]).unwrap();
+
+        let client = Client::new();
+        let mut seen = Vec::new();
+        let server = MockServer::files(vec![("/model.zip", body.clone())]);
+        let server = &server.url;
+        fetch_with_resume(
+            &client,
+            &format!("{server}/model.zip"),
+            &partial,
+            checks.size_bytes,
+            |done, total| seen.push((done, total)),
+        )
+        .unwrap();
+        assert_eq!(seen.first(), Some(&(80_000, checks.size_bytes)));
+        assert_eq!(seen.last(), Some(&(200_000, checks.size_bytes)));
+        assert_eq!(fs::read(&partial).unwrap(), body);
+        verify_package(&partial, &checks, Some(&key)).unwrap();
+
+        let wrong = PackageChecks {
+            sha256: "00".repeat(32),
+            ..checks.clone()
+        };
+        assert!(verify_package(&partial, &wrong, Some(&key)).is_err());
+        let without_checksum = PackageChecks {
+            sha256: String::new(),
+            ..checks.clone()
+        };
+        assert!(verify_package(&partial, &without_checksum, Some(&key)).is_err());
+        let short = PackageChecks {
+            size_bytes: Some(1),
+            ..checks.clone()
+        };
+        assert!(verify_package(&partial, &short, Some(&key)).is_err());
+        let unsigned = PackageChecks {
+            signature: None,
+            ..checks.clone()
+        };
+        let err = verify_package(&partial, &unsigned, Some(&key)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SignatureError>(),
+            Some(&SignatureError::Missing)
+        );
+        let forged = PackageChecks {
+            signature: Some(sign_hex(b"other package")),
+            ..checks
+        };
+        let err = verify_package(&partial, &forged, Some(&key)).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SignatureError>(),
+            Some(&SignatureError::Invalid)
+        );
+    }
+
+    #[test]
+    fn channels_and_app_ranges_decide_which_versions_are_offered() {
+        let manifest: RemoteManifest = serde_json::from_str(
+            r#"{
+                "app": {
+                    "latest": "1.4.0",
+                    "url": "https://example.org/releases",
+                    "notes": "Stabiele release",
+                    "history": [
+                        {"version": "1.3.2", "notes": "Kleine fixes"},
+                        {"version": "1.3.1", "notes": "Al geïnstalleerd"}
+                    ]
+                },
+                "model": {
+                    "latest": "model-v1.3.0",
+                    "url": "https://example.org/model-1.3.0.zip",
+                    "notes": "Vereist nieuwe app",
+                    "min_app_version": "1.4.0",
+                    "history": [
+                        {"version": "model-v1.2.0", "url": "https://example.org/model-1.2.0.zip", "notes": "Meer soorten"},
+                        {"version": "model-v1.1.0", "notes": "Betere nachtbeelden"}
+                    ]
+                },
+                "channels": {
+                    "beta": {
+                        "app": {"latest": "1.5.0-beta.1", "url": "https://example.org/beta", "notes": "Bèta"},
+                        "model": {"latest": "model-v1.2.5", "url": "https://example.org/model-1.2.5.zip", "max_app_version": "1.9.9"}
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let (stable, blocked) = manifest.select_model(UpdateChannel::Stable, "1.3.1");
+        assert_eq!(stable.map(|m| m.latest.as_str()), Some("model-v1.2.0"));
+        assert_eq!(blocked.map(|m| m.latest.as_str()), Some("model-v1.3.0"));
+        let (beta, _) = manifest.select_model(UpdateChannel::Beta, "1.3.1");
+        assert_eq!(beta.map(|m| m.latest.as_str()), Some("model-v1.2.5"));
+        let (newer_app, blocked) = manifest.select_model(UpdateChannel::Stable, "1.4.0");
+        assert_eq!(newer_app.map(|m| m.latest.as_str()), Some("model-v1.3.0"));
+        assert!(blocked.is_none());
+
+        assert_eq!(manifest.select_app(UpdateChannel::Stable).latest, "1.4.0");
+        assert_eq!(
+            manifest.select_app(UpdateChannel::Beta).latest,
+            "1.5.0-beta.1"
+        );
+
+        let notes = release_notes(
+            manifest.model_notes(UpdateChannel::Stable),
+            "1.0.0",
+            "model-v1.2.0",
+        );
+        let versions: Vec<&str> = notes.iter().map(|n| n.version.as_str()).collect();
+        assert_eq!(versions, ["model-v1.2.0", "model-v1.1.0"]);
+        let notes = release_notes(
+            manifest.app_notes(UpdateChannel::Beta),
+            "1.3.1",
+            "1.5.0-beta.1",
+        );
+        let versions: Vec<&str> = notes.iter().map(|n| n.version.as_str()).collect();
+        assert_eq!(versions, ["1.5.0-beta.1", "1.4.0", "1.3.2"]);
+    }
+
+    #[test]
+    fn app_assets_are_picked_per_platform() {
+        let manifest: RemoteManifest = serde_json::from_str(
+            r#"{
+                "app": {
+                    "latest": "1.4.0",
+                    "url": "https://example.org/releases",
+                    "windows": {"url": "https://example.org/FeedieSetup-1.4.0.exe"},
+                    "linux": {
+                        "x86_64": {"url": "https://example.org/Feedie-linux-x86_64-1.4.0.AppImage", "sha256": "ab"}
+                    }
+                },
+                "model": {"latest": "1.2.0", "url": "u"}
+            }"#,
+        )
+        .unwrap();
+        let url = |os, arch, appimage| {
+            platform_asset(&manifest.app, os, arch, appimage).map(|asset| asset.url.as_str())
+        };
+        assert_eq!(
+            url("windows", "x86_64", false),
+            Some("https://example.org/FeedieSetup-1.4.0.exe")
+        );
+        assert_eq!(
+            url("linux", "x86_64", true),
+            Some("https://example.org/Feedie-linux-x86_64-1.4.0.AppImage")
+        );
+        assert_eq!(url("linux", "x86_64", false), None);
+        assert_eq!(url("linux", "aarch64", true), None);
+        assert_eq!(url("macos", "aarch64", false), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn appimage_is_replaced_with_a_backup() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("Feedie.AppImage");
+        let download = dir.path().join("download.AppImage");
+        fs::write(&target, b"old").unwrap();
+        fs::write(&download, b"new").unwrap();
+
+        let backup = replace_appimage(&download, &target).unwrap();
+        assert_eq!(fs::read(&target).unwrap(), b"new");
+        assert_eq!(fs::read(&backup).unwrap(), b"old");
+        assert_eq!(backup, dir.path().join("Feedie.AppImage.bak"));
+        let mode = fs::metadata(&target).unwrap().permissions().mode();
+        assert_eq!(mode & 0o111, 0o111);
+        assert!(!dir.path().join(".Feedie.AppImage.new").exists());
+    }
+
+    #[test]
+    fn app_downloads_need_a_matching_sha256() {
+        let server = MockServer::files(vec![("/Feedie.AppImage", b"appimage".to_vec())]);
+        let url = format!("{}/Feedie.AppImage", server.url);
+        let result = download_app_installer(&url, " ", None, "0.0.0-test-missing");
+        assert!(matches!(result, Err(AppDownloadError::HashMissing)));
+        assert!(server.requests().is_empty());
+
+        let result = download_app_installer(&url, &"00".repeat(32), None, "0.0.0-test-wrong");
+        assert!(matches!(result, Err(AppDownloadError::HashMismatch)));
+    }
+
+    #[test]
+    fn manifests_need_a_valid_detached_signature() {
+        let key = test_key().verifying_key();
+        let manifest = br#"{"app": {"latest": "1.3.1", "url": "u"}, "model": {"latest": "1.2.0", "url": "u"}}"#;
+        let tampered = String::from_utf8_lossy(manifest).replace("1.2.0", "9.9.9");
+        let server = MockServer::files(vec![
+            ("/manifest.json", manifest.to_vec()),
+            ("/manifest.json.sig", sign_hex(manifest).into_bytes()),
+            ("/tampered.json", tampered.into_bytes()),
+            ("/tampered.json.sig", sign_hex(manifest).into_bytes()),
+            ("/unsigned.json", manifest.to_vec()),
+        ]);
+        let server = &server.url;
+        let signature_error = |name: &str, key: Option<&VerifyingKey>| {
+            fetch_remote_manifest(&format!("{server}/{name}"), key)
+                .unwrap_err()
+                .downcast_ref::<SignatureError>()
+                .copied()
+        };
+
+        let parsed = fetch_remote_manifest(&format!("{server}/manifest.json"), Some(&key)).unwrap();
+        assert_eq!(parsed.model.latest, "1.2.0");
+        assert_eq!(
+            signature_error("tampered.json", Some(&key)),
+            Some(SignatureError::Invalid)
+        );
+        assert_eq!(
+            signature_error("unsigned.json", Some(&key)),
+            Some(SignatureError::Missing)
+        );
+        assert_eq!(
+            signature_error("manifest.json", None),
+            Some(SignatureError::NoKey)
+        );
+        assert_eq!(
+            signature_url("https://example.org/manifest.json?raw=1"),
+            "https://example.org/manifest.json.sig?raw=1"
+        );
+    }
+
+    #[test]
+    fn the_unsigned_repository_manifest_is_refused() {
+        let manifest = include_bytes!("../../../manifest.json");
+        let server = MockServer::files(vec![("/manifest.json", manifest.to_vec())]);
+        let url = format!("{}/manifest.json", server.url);
+        let key = test_key().verifying_key();
+
+        for key in [Some(&key), None] {
+            let err = fetch_remote_manifest(&url, key).unwrap_err();
+            let expected = if key.is_some() {
+                SignatureError::Missing
+            } else {
+                SignatureError::NoKey
+            };
+            assert_eq!(err.downcast_ref::<SignatureError>(), Some(&expected));
+        }
+        serde_json::from_slice::<RemoteManifest>(manifest).expect("manifest.json parses");
+    }
+}
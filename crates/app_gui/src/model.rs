@@ -1,17 +1,24 @@
 //! Model installation helpers and confidence heuristics.
 
 use crate::app::{
-    LABEL_FILE_NAME, LabelOption, MODEL_FILE_NAME, SOMETHING_LABEL, UiApp, VERSION_FILE_NAME,
+    ACTIVE_MODEL_FILE_NAME, LABEL_FILE_NAME, LabelOption, MODEL_FILE_NAME,
+    MODEL_METADATA_FILE_NAME, MODEL_NOTES_FILE_NAME, MODEL_PACK_FILE_NAME, MODEL_VERSIONS_DIR,
+    SOMETHING_LABEL, STAGING_MODEL_DIR, UiApp, VERSION_FILE_NAME,
 };
 use crate::i18n::Language;
+use crate::manifest::app_version_in_range;
 use crate::util::canonical_label;
 use anyhow::{Context, anyhow};
 use directories_next::ProjectDirs;
 use feeder_core::{ClassifierConfig, Decision, ImageInfo};
+use semver::Version;
+use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fs;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
+use zip::ZipArchive;
 
 impl UiApp {
     /// Recomputes the `present` flag for every row based on the current threshold.
@@ -62,28 +69,36 @@ impl UiApp {
 
     /// Builds the classifier configuration for the next scan job.
     pub(crate) fn classifier_config(&self) -> ClassifierConfig {
+        let (model_path, labels_path) = self.active_model_paths();
         ClassifierConfig {
-            model_path: self.model_file_path(),
-            labels_path: self.labels_path(),
+            model_path,
+            labels_path,
             presence_threshold: self.pending_presence_threshold,
             background_labels: self.background_labels.clone(),
+            references: Some(self.references.clone()),
+            model_id: Some(self.active_model_id()),
             ..Default::default()
         }
     }
 
-    /// Points to the on-disk EfficientViT model weights.
+    /// Points to the on-disk EfficientViT model weights of the active model.
     pub(crate) fn model_file_path(&self) -> PathBuf {
-        self.model_root.join(MODEL_FILE_NAME)
+        self.active_model_dir.join(MODEL_FILE_NAME)
     }
 
-    /// Points to the CSV file containing all known labels.
+    /// Points to the CSV file containing all labels of the active model.
     pub(crate) fn labels_path(&self) -> PathBuf {
-        self.model_root.join(LABEL_FILE_NAME)
+        self.active_model_dir.join(LABEL_FILE_NAME)
     }
 
-    /// Points to the file where the downloaded model version is stored.
+    /// Points to the folder where a downloaded model waits for activation.
+    pub(crate) fn staging_model_dir(&self) -> PathBuf {
+        self.model_root.join(STAGING_MODEL_DIR)
+    }
+
+    /// Points to the file where the active model version is stored.
     pub(crate) fn model_version_path(&self) -> PathBuf {
-        self.model_root.join(VERSION_FILE_NAME)
+        self.active_model_dir.join(VERSION_FILE_NAME)
     }
 
     /// Loads label metadata from disk and filters duplicates.
@@ -129,14 +144,16 @@ impl UiApp {
         options
     }
 
-    /// Ensures the model directory exists and returns its path and version.
-    pub(crate) fn prepare_model_dir() -> (PathBuf, String) {
+    /// Ensures the model directory exists and returns its path, the folder of
+    /// the active model and that model's version.
+    pub(crate) fn prepare_model_dir() -> (PathBuf, PathBuf, String) {
         if let Some(proj_dirs) = ProjectDirs::from("nl", "Feedie", "Feedie") {
             let models_dir = proj_dirs.data_dir().join("models");
             match Self::ensure_models_present(&models_dir) {
                 Ok(()) => {
-                    let version = read_model_version_from(&models_dir.join(VERSION_FILE_NAME));
-                    return (models_dir, version);
+                    let active = resolve_active_model_dir(&models_dir);
+                    let version = read_model_version_from(&active.join(VERSION_FILE_NAME));
+                    return (models_dir, active, version);
                 }
                 Err(err) => {
                     tracing::warn!("Kon modelmap niet voorbereiden in AppData: {err}");
@@ -145,23 +162,403 @@ impl UiApp {
         }
         let bundled = bundled_models_dir();
         let version = read_model_version_from(&bundled.join(VERSION_FILE_NAME));
-        (bundled, version)
+        (bundled.clone(), bundled, version)
     }
 
-    /// Copies the bundled models into the writable directory when missing.
+    /// Copies the bundled models into the writable directory when no model
+    /// version is installed yet.
     pub(crate) fn ensure_models_present(target: &Path) -> anyhow::Result<()> {
-        if target.exists()
-            && target.join(MODEL_FILE_NAME).exists()
-            && target.join(LABEL_FILE_NAME).exists()
-        {
+        migrate_single_model_layout(target)?;
+        if !installed_models(target).is_empty() {
             return Ok(());
         }
-        if let Some(parent) = target.parent() {
-            fs::create_dir_all(parent)
-                .with_context(|| format!("Kon map {} niet aanmaken", parent.display()))?;
+        let bundled = bundled_models_dir();
+        let id = model_dir_name(&read_model_version_from(&bundled.join(VERSION_FILE_NAME)));
+        copy_dir_recursive(&bundled, &target.join(MODEL_VERSIONS_DIR).join(&id))?;
+        set_active_model(target, &id)
+    }
+}
+
+/// Model version installed in its own folder below `models/versions`.
+#[derive(Clone)]
+pub(crate) struct InstalledModel {
+    /// Folder name, used to select or delete the model.
+    pub(crate) id: String,
+    pub(crate) version: String,
+    /// Regional pack id, `None` for the default model.
+    pub(crate) pack: Option<String>,
+    pub(crate) dir: PathBuf,
+    pub(crate) notes: Option<String>,
+    pub(crate) size_bytes: u64,
+}
+
+impl InstalledModel {
+    /// Returns "1.2.0" for the default model and "scandinavia 1.0.0" for packs.
+    pub(crate) fn display_name(&self) -> String {
+        match &self.pack {
+            Some(pack) => format!("{pack} {}", self.version),
+            None => self.version.clone(),
+        }
+    }
+}
+
+/// Lists the installed model versions, newest first.
+pub(crate) fn installed_models(model_root: &Path) -> Vec<InstalledModel> {
+    let Ok(entries) = fs::read_dir(model_root.join(MODEL_VERSIONS_DIR)) else {
+        return Vec::new();
+    };
+    let mut models: Vec<InstalledModel> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            // Leftovers of an interrupted install.
+            if name.ends_with(".old") || name.ends_with(".tmp") {
+                return None;
+            }
+            if !dir.join(MODEL_FILE_NAME).exists() || !dir.join(LABEL_FILE_NAME).exists() {
+                return None;
+            }
+            Some(InstalledModel {
+                id: name,
+                version: read_model_version_from(&dir.join(VERSION_FILE_NAME)),
+                pack: read_trimmed(&dir.join(MODEL_PACK_FILE_NAME)),
+                notes: read_trimmed(&dir.join(MODEL_NOTES_FILE_NAME)),
+                size_bytes: dir_size(&dir),
+                dir,
+            })
+        })
+        .collect();
+    models.sort_by(|a, b| {
+        a.pack.cmp(&b.pack).then_with(|| {
+            match (Version::parse(&a.version), Version::parse(&b.version)) {
+                (Ok(va), Ok(vb)) => vb.cmp(&va),
+                _ => b.version.cmp(&a.version),
+            }
+        })
+    });
+    models
+}
+
+/// Returns the folder of the active model.
+///
+/// Falls back to the newest installed version when the pointer is missing or
+/// stale, and to `model_root` itself for the bundled single-model layout.
+pub(crate) fn resolve_active_model_dir(model_root: &Path) -> PathBuf {
+    if let Ok(id) = fs::read_to_string(model_root.join(ACTIVE_MODEL_FILE_NAME)) {
+        let dir = model_root.join(MODEL_VERSIONS_DIR).join(id.trim());
+        if !id.trim().is_empty() && dir.join(MODEL_FILE_NAME).exists() {
+            return dir;
+        }
+    }
+    installed_models(model_root)
+        .into_iter()
+        .next()
+        .map(|model| model.dir)
+        .unwrap_or_else(|| model_root.to_path_buf())
+}
+
+/// Makes the installed model with folder name `id` the active one.
+pub(crate) fn set_active_model(model_root: &Path, id: &str) -> anyhow::Result<()> {
+    // Write next to the pointer and rename, so a crash never leaves it half written.
+    let pointer = model_root.join(ACTIVE_MODEL_FILE_NAME);
+    let temp = pointer.with_extension("txt.tmp");
+    fs::write(&temp, id).context("Kon actieve modelversie niet opslaan")?;
+    fs::rename(&temp, &pointer).context("Kon actieve modelversie niet opslaan")
+}
+
+/// Returns the id the model folder `source` gets once installed.
+///
+/// Packs are stored as `<pack>-<version>`, other models as `<version>`.
+pub(crate) fn installed_model_id(source: &Path) -> String {
+    let version = model_dir_name(&read_model_version_from(&source.join(VERSION_FILE_NAME)));
+    match read_trimmed(&source.join(MODEL_PACK_FILE_NAME)) {
+        Some(pack) => format!("{}-{version}", safe_dir_name(&pack)),
+        None => version,
+    }
+}
+
+/// Moves a complete model folder into `models/versions` and returns its id.
+///
+/// See [`installed_model_id`] for the id. An installed folder with the same
+/// id is replaced; it is only removed once the new folder is in place, and
+/// restored when the move fails.
+pub(crate) fn install_model_version(model_root: &Path, source: &Path) -> anyhow::Result<String> {
+    for name in [MODEL_FILE_NAME, LABEL_FILE_NAME] {
+        if !source.join(name).exists() {
+            return Err(anyhow!("Bestand {name} ontbreekt in {}.", source.display()));
+        }
+    }
+    let id = installed_model_id(source);
+    let versions = model_root.join(MODEL_VERSIONS_DIR);
+    fs::create_dir_all(&versions)
+        .with_context(|| format!("Kon map {} niet aanmaken", versions.display()))?;
+    let dest = versions.join(&id);
+    let backup = versions.join(format!("{id}.old"));
+    let _ = fs::remove_dir_all(&backup);
+    if dest.exists() {
+        fs::rename(&dest, &backup)
+            .with_context(|| format!("Kon map {} niet vervangen", dest.display()))?;
+    }
+    if let Err(err) = move_dir(source, &dest, &versions.join(format!("{id}.tmp"))) {
+        if backup.exists() {
+            let _ = fs::remove_dir_all(&dest);
+            let _ = fs::rename(&backup, &dest);
         }
-        copy_dir_recursive(&bundled_models_dir(), target)
+        return Err(err);
     }
+    let _ = fs::remove_dir_all(&backup);
+    Ok(id)
+}
+
+/// Moves `source` to `dest`. Across file systems the folder is first copied
+/// to `temp` and then renamed, so `dest` never holds a partial copy.
+fn move_dir(source: &Path, dest: &Path, temp: &Path) -> anyhow::Result<()> {
+    if fs::rename(source, dest).is_ok() {
+        return Ok(());
+    }
+    let _ = fs::remove_dir_all(temp);
+    copy_dir_recursive(source, temp)?;
+    fs::rename(temp, dest)
+        .with_context(|| format!("Kon map {} niet installeren", dest.display()))?;
+    let _ = fs::remove_dir_all(source);
+    Ok(())
+}
+
+/// Optional `feedie-model.json` inside a model package.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct ModelPackageMetadata {
+    #[serde(default)]
+    pub(crate) pack: Option<String>,
+    #[serde(default)]
+    pub(crate) version: Option<String>,
+    #[serde(default)]
+    pub(crate) notes: Option<String>,
+    #[serde(default)]
+    pub(crate) min_app_version: Option<String>,
+    #[serde(default)]
+    pub(crate) max_app_version: Option<String>,
+}
+
+/// Unpacks a model ZIP into `target_root`, keeping only the model files.
+///
+/// The version comes from `model_version.txt`, the metadata or `version`, in
+/// that order. A pack id and notes from the metadata are written next to the
+/// model.
+pub(crate) fn unpack_model_archive(
+    archive_path: &Path,
+    target_root: &Path,
+    version: Option<&str>,
+) -> anyhow::Result<()> {
+    let reader = fs::File::open(archive_path)
+        .with_context(|| format!("Kon {} niet openen", archive_path.display()))?;
+    let mut archive = ZipArchive::new(reader).context("Modelpakket is geen geldig ZIP-bestand")?;
+    fs::create_dir_all(target_root).context("Kon doelmap voor model niet aanmaken")?;
+    for name in [
+        MODEL_FILE_NAME,
+        LABEL_FILE_NAME,
+        VERSION_FILE_NAME,
+        MODEL_METADATA_FILE_NAME,
+    ] {
+        let Ok(mut file) = archive.by_name(name) else {
+            continue;
+        };
+        let dest = target_root.join(name);
+        let mut outfile = fs::File::create(&dest)
+            .with_context(|| format!("Kon {} niet aanmaken", dest.display()))?;
+        io::copy(&mut file, &mut outfile)
+            .with_context(|| format!("Uitpakken van {name} mislukt"))?;
+    }
+    for name in [MODEL_FILE_NAME, LABEL_FILE_NAME] {
+        if !target_root.join(name).exists() {
+            return Err(anyhow!("Bestand {name} ontbreekt in modelpakket."));
+        }
+    }
+    let metadata = read_package_metadata(target_root)?;
+    if !target_root.join(VERSION_FILE_NAME).exists() {
+        let version = metadata
+            .version
+            .as_deref()
+            .or(version)
+            .context("Modelpakket bevat geen modelversie.")?;
+        fs::write(target_root.join(VERSION_FILE_NAME), version)
+            .context("Kon modelversie niet opslaan")?;
+    }
+    if let Some(pack) = &metadata.pack {
+        fs::write(target_root.join(MODEL_PACK_FILE_NAME), pack)
+            .context("Kon pakketnaam niet opslaan")?;
+    }
+    if let Some(notes) = &metadata.notes {
+        fs::write(target_root.join(MODEL_NOTES_FILE_NAME), notes)
+            .context("Kon modelnotities niet opslaan")?;
+    }
+    Ok(())
+}
+
+/// Reads `feedie-model.json` from an unpacked package, if present.
+fn read_package_metadata(dir: &Path) -> anyhow::Result<ModelPackageMetadata> {
+    let path = dir.join(MODEL_METADATA_FILE_NAME);
+    if !path.exists() {
+        return Ok(ModelPackageMetadata::default());
+    }
+    let content = fs::read_to_string(&path).context("Kon modelmetadata niet lezen")?;
+    serde_json::from_str(&content).context("Modelmetadata is ongeldig")
+}
+
+/// Checks an unpacked model folder before it is installed.
+///
+/// Rejects unreadable weights, empty label lists, a classifier head whose
+/// size does not match the labels and packages that require another app
+/// version.
+pub(crate) fn validate_model_dir(dir: &Path, app_version: &str) -> anyhow::Result<()> {
+    let header = read_safetensors_header(&dir.join(MODEL_FILE_NAME))?;
+    let labels = fs::read_to_string(dir.join(LABEL_FILE_NAME)).context("Kon labels niet lezen")?;
+    let label_count = parse_label_options(&labels).len();
+    if label_count == 0 {
+        return Err(anyhow!("Labelbestand in modelpakket is leeg."));
+    }
+    let Some(classes) = header[HEAD_WEIGHT_TENSOR]["shape"][0].as_u64() else {
+        return Err(anyhow!(
+            "Modelgewichten bevatten geen {HEAD_WEIGHT_TENSOR}."
+        ));
+    };
+    if classes != label_count as u64 {
+        return Err(anyhow!(
+            "Model kent {classes} klassen, maar het labelbestand bevat {label_count} labels."
+        ));
+    }
+    if read_trimmed(&dir.join(VERSION_FILE_NAME)).is_none() {
+        return Err(anyhow!("Modelpakket bevat geen modelversie."));
+    }
+    let metadata = read_package_metadata(dir)?;
+    let min = metadata.min_app_version.as_deref();
+    let max = metadata.max_app_version.as_deref();
+    if !app_version_in_range(app_version, min, max) {
+        return Err(anyhow!(
+            "Modelpakket vereist Feedie {} tot {}, dit is {app_version}.",
+            min.unwrap_or("-"),
+            max.unwrap_or("-")
+        ));
+    }
+    Ok(())
+}
+
+/// Reads the JSON header at the start of a safetensors file.
+fn read_safetensors_header(path: &Path) -> anyhow::Result<serde_json::Value> {
+    let mut file = fs::File::open(path).context("Kon modelgewichten niet openen")?;
+    let file_len = file.metadata()?.len();
+    let mut len_bytes = [0u8; 8];
+    file.read_exact(&mut len_bytes)
+        .context("Modelgewichten zijn geen safetensors-bestand")?;
+    let header_len = u64::from_le_bytes(len_bytes);
+    if header_len == 0 || header_len > file_len.saturating_sub(8) {
+        return Err(anyhow!("Modelgewichten zijn geen safetensors-bestand."));
+    }
+    let mut header = vec![0u8; header_len as usize];
+    file.read_exact(&mut header)
+        .context("Modelgewichten zijn afgekapt")?;
+    serde_json::from_slice(&header).context("Modelgewichten hebben een ongeldige header")
+}
+
+/// Validates a model ZIP from disk and installs it as a new version.
+///
+/// The package is not activated; returns the id of the installed version.
+pub(crate) fn install_model_package(
+    archive_path: &Path,
+    model_root: &Path,
+    app_version: &str,
+) -> anyhow::Result<String> {
+    let staging = model_root.join(format!("{STAGING_MODEL_DIR}-import"));
+    let _ = fs::remove_dir_all(&staging);
+    let result = unpack_model_archive(archive_path, &staging, None)
+        .and_then(|_| validate_model_dir(&staging, app_version))
+        .and_then(|_| install_model_version(model_root, &staging));
+    if result.is_err() {
+        let _ = fs::remove_dir_all(&staging);
+    }
+    result
+}
+
+/// Moves a model stored directly in `model_root` (before versioned folders
+/// existed) into its own version folder.
+fn migrate_single_model_layout(model_root: &Path) -> anyhow::Result<()> {
+    if !model_root.join(MODEL_FILE_NAME).exists() || !model_root.join(LABEL_FILE_NAME).exists() {
+        return Ok(());
+    }
+    let id = model_dir_name(&read_model_version_from(
+        &model_root.join(VERSION_FILE_NAME),
+    ));
+    let dest = model_root.join(MODEL_VERSIONS_DIR).join(&id);
+    fs::create_dir_all(&dest)
+        .with_context(|| format!("Kon map {} niet aanmaken", dest.display()))?;
+    for name in [MODEL_FILE_NAME, LABEL_FILE_NAME, VERSION_FILE_NAME] {
+        let src = model_root.join(name);
+        if !src.exists() {
+            continue;
+        }
+        if dest.join(name).exists() {
+            fs::remove_file(&src)?;
+        } else {
+            fs::rename(&src, dest.join(name))
+                .with_context(|| format!("Kon {} niet verplaatsen", src.display()))?;
+        }
+    }
+    if !model_root.join(ACTIVE_MODEL_FILE_NAME).exists() {
+        set_active_model(model_root, &id)?;
+    }
+    tracing::info!("Model {id} verplaatst naar {}", dest.display());
+    Ok(())
+}
+
+/// Turns a model version into a safe folder name.
+fn model_dir_name(version: &str) -> String {
+    safe_dir_name(&normalize_model_version(version))
+}
+
+/// Weight of the classifier head; its rows are the model's classes.
+const HEAD_WEIGHT_TENSOR: &str = "head.linear.weight";
+
+/// Replaces characters that are unsafe in folder names, so the result is a
+/// single path component and never `.` or `..`.
+pub(crate) fn safe_dir_name(raw: &str) -> String {
+    let name: String = raw
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.chars().all(|c| c == '.') {
+        "onbekend".to_string()
+    } else {
+        name
+    }
+}
+
+/// Reads a small text file, returning `None` when it is missing or empty.
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|content| content.trim().to_string())
+        .filter(|content| !content.is_empty())
+}
+
+/// Sums the size of all files directly inside `dir`.
+fn dir_size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.metadata().ok())
+                .filter(|meta| meta.is_file())
+                .map(|meta| meta.len())
+                .sum()
+        })
+        .unwrap_or(0)
 }
 
 /// Resolves the path that contains the bundled models that ship with the app.
@@ -231,7 +628,7 @@ const TRANSLATED_LANGUAGES: [Language; 5] = [
     Language::Swedish,
 ];
 
-fn parse_label_options(content: &str) -> Vec<LabelOption> {
+pub(crate) fn parse_label_options(content: &str) -> Vec<LabelOption> {
     let mut seen = HashSet::new();
     let mut options = Vec::new();
     let mut reader = csv::ReaderBuilder::new()
@@ -361,3 +758,146 @@ pub(crate) fn normalize_model_version(value: &str) -> String {
         .unwrap_or(without_prefix);
     without_v.to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_model(dir: &Path, version: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(MODEL_FILE_NAME), b"weights").unwrap();
+        fs::write(dir.join(LABEL_FILE_NAME), "Koolmees\n").unwrap();
+        fs::write(dir.join(VERSION_FILE_NAME), version).unwrap();
+    }
+
+    fn write_package(path: &Path, files: &[(&str, &[u8])]) {
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, content) in files {
+            zip.start_file(*name, zip::write::FileOptions::default())
+                .unwrap();
+            std::io::Write::write_all(&mut zip, content).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn safe_dir_names_stay_inside_their_parent() {
+        assert_eq!(safe_dir_name("../.."), ".._..");
+        assert_eq!(safe_dir_name(".."), "onbekend");
+        assert_eq!(safe_dir_name(" "), "onbekend");
+        assert_eq!(safe_dir_name("be-nl/v2"), "be-nl_v2");
+    }
+
+    /// Safetensors file with only a classifier head of `classes` rows.
+    fn head_weights(classes: usize) -> Vec<u8> {
+        let header = format!(
+            r#"{{"{HEAD_WEIGHT_TENSOR}":{{"dtype":"F32","shape":[{classes},1],"data_offsets":[0,{}]}}}}"#,
+            classes * 4
+        );
+        let mut bytes = (header.len() as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.resize(bytes.len() + classes * 4, 0);
+        bytes
+    }
+
+    #[test]
+    fn model_packages_are_validated_before_install() {
+        let root = tempfile::tempdir().unwrap();
+        let weights = &head_weights(2)[..];
+        let labels: &[u8] = b"Koolmees\nPimpelmees\n";
+
+        let ok = root.path().join("pack.zip");
+        write_package(
+            &ok,
+            &[
+                (MODEL_FILE_NAME, weights),
+                (LABEL_FILE_NAME, labels),
+                (
+                    MODEL_METADATA_FILE_NAME,
+                    br#"{"pack": "uk", "version": "2.0.0", "min_app_version": "1.3.0"}"#,
+                ),
+            ],
+        );
+        let id = install_model_package(&ok, root.path(), "1.3.1").unwrap();
+        assert_eq!(id, "uk-2.0.0");
+        assert!(
+            install_model_package(&ok, root.path(), "1.2.0")
+                .unwrap_err()
+                .to_string()
+                .contains("vereist")
+        );
+
+        let no_labels = root.path().join("no-labels.zip");
+        write_package(
+            &no_labels,
+            &[(MODEL_FILE_NAME, weights), (VERSION_FILE_NAME, b"1")],
+        );
+        assert!(install_model_package(&no_labels, root.path(), "1.3.1").is_err());
+
+        let bad_weights = root.path().join("bad.zip");
+        write_package(
+            &bad_weights,
+            &[
+                (MODEL_FILE_NAME, b"not a model"),
+                (LABEL_FILE_NAME, labels),
+                (VERSION_FILE_NAME, b"1.0.0"),
+            ],
+        );
+        assert!(install_model_package(&bad_weights, root.path(), "1.3.1").is_err());
+
+        let mismatch = root.path().join("mismatch.zip");
+        write_package(
+            &mismatch,
+            &[
+                (MODEL_FILE_NAME, &head_weights(3)),
+                (LABEL_FILE_NAME, labels),
+                (VERSION_FILE_NAME, b"1.0.0"),
+            ],
+        );
+        assert!(
+            install_model_package(&mismatch, root.path(), "1.3.1")
+                .unwrap_err()
+                .to_string()
+                .contains("3 klassen")
+        );
+        assert_eq!(installed_models(root.path()).len(), 1);
+    }
+
+    #[test]
+    fn single_model_layout_is_migrated_and_versions_can_be_switched() {
+        let root = tempfile::tempdir().unwrap();
+        write_model(root.path(), "v1.2.0");
+        migrate_single_model_layout(root.path()).unwrap();
+        assert!(!root.path().join(MODEL_FILE_NAME).exists());
+        let active = resolve_active_model_dir(root.path());
+        assert_eq!(active, root.path().join(MODEL_VERSIONS_DIR).join("1.2.0"));
+
+        let staging = root.path().join(STAGING_MODEL_DIR);
+        write_model(&staging, "model-v1.10.0");
+        fs::write(staging.join(MODEL_NOTES_FILE_NAME), "Meer soorten").unwrap();
+        let id = install_model_version(root.path(), &staging).unwrap();
+        assert_eq!(id, "1.10.0");
+        assert!(!staging.exists());
+
+        let models = installed_models(root.path());
+        let ids: Vec<_> = models.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["1.10.0", "1.2.0"]);
+        assert!(models.iter().all(|m| m.pack.is_none()));
+        assert_eq!(models[0].notes.as_deref(), Some("Meer soorten"));
+        assert!(models[0].size_bytes > 0);
+
+        // Installing does not switch; the pointer still names 1.2.0.
+        assert_eq!(resolve_active_model_dir(root.path()), active);
+        set_active_model(root.path(), &id).unwrap();
+        assert_eq!(resolve_active_model_dir(root.path()), models[0].dir);
+
+        // A regional pack with the same version gets its own folder.
+        write_model(&staging, "v1.10.0");
+        fs::write(staging.join(MODEL_PACK_FILE_NAME), "scandinavia").unwrap();
+        let pack_id = install_model_version(root.path(), &staging).unwrap();
+        assert_eq!(pack_id, "scandinavia-1.10.0");
+        let models = installed_models(root.path());
+        assert_eq!(models.len(), 3);
+        assert_eq!(models[2].display_name(), "scandinavia 1.10.0");
+    }
+}
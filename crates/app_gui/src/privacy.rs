@@ -0,0 +1,251 @@
+//! Safeguards for photos that leave the machine: metadata is stripped from the
+//! uploaded copy and every share is appended to an audit log.
+
+use crate::upload_queue::PermanentError;
+use anyhow::{Context, anyhow, bail};
+use directories_next::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+pub(crate) const JPEG_SOI: [u8; 2] = [0xFF, 0xD8];
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+/// PNG chunks that carry text, timestamps or EXIF.
+const PNG_METADATA_CHUNKS: [&[u8; 4]; 5] = [b"eXIf", b"tEXt", b"zTXt", b"iTXt", b"tIME"];
+
+/// One shared photo in the audit log.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ShareRecord {
+    /// Local time in RFC 3339.
+    pub(crate) shared_at: String,
+    pub(crate) file: PathBuf,
+    pub(crate) label: String,
+    /// Backend and address the photo was sent to.
+    pub(crate) destination: String,
+}
+
+/// Location of the audit log in the user data directory.
+pub(crate) fn share_log_path() -> Option<PathBuf> {
+    ProjectDirs::from("nl", "Feedie", "Feedie")
+        .map(|dirs| dirs.data_dir().join("shared_uploads.jsonl"))
+}
+
+/// Appends `record` as one JSON line.
+pub(crate) fn append_share_record(path: &Path, record: &ShareRecord) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Kon {} niet openen", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(record)?)
+        .with_context(|| format!("Kon {} niet schrijven", path.display()))
+}
+
+/// Reads the audit log; unreadable lines are skipped.
+pub(crate) fn read_share_log(path: &Path) -> Vec<ShareRecord> {
+    fs::read_to_string(path)
+        .map(|contents| {
+            contents
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Writes a copy of `source` without EXIF, XMP, IPTC or text metadata into
+/// `dir`, keeping the file name. The pixel data is copied unchanged.
+pub(crate) fn stripped_copy(source: &Path, dir: &Path) -> anyhow::Result<PathBuf> {
+    let bytes = fs::read(source).with_context(|| format!("Kon {} niet lezen", source.display()))?;
+    let stripped = strip_metadata(&bytes).context(PermanentError(format!(
+        "Metadata verwijderen uit {}",
+        source.display()
+    )))?;
+    let name = source
+        .file_name()
+        .ok_or_else(|| anyhow!("Geen bestandsnaam: {}", source.display()))?;
+    let target = dir.join(name);
+    fs::write(&target, stripped)
+        .with_context(|| format!("Kon {} niet schrijven", target.display()))?;
+    Ok(target)
+}
+
+/// Removes metadata segments from a JPEG or PNG file.
+pub(crate) fn strip_metadata(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+    if bytes.starts_with(&JPEG_SOI) {
+        strip_jpeg(bytes)
+    } else if bytes.starts_with(&PNG_SIGNATURE) {
+        strip_png(bytes)
+    } else {
+        bail!("Alleen JPEG- en PNG-bestanden worden gedeeld")
+    }
+}
+
+/// Keeps JFIF (APP0), ICC profiles (APP2) and Adobe colour info (APP14);
+/// drops every other APPn segment and comments.
+fn strip_jpeg(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+    filter_jpeg_segments(bytes, |marker, _| {
+        !matches!(marker, 0xE1 | 0xE3..=0xED | 0xEF | 0xFE)
+    })
+}
+
+/// Copies a JPEG, keeping only the header segments for which `keep` returns
+/// true. `keep` receives the marker byte and the segment payload; the image
+/// data after start-of-scan is always copied unchanged.
+pub(crate) fn filter_jpeg_segments(
+    bytes: &[u8],
+    keep: impl Fn(u8, &[u8]) -> bool,
+) -> anyhow::Result<Vec<u8>> {
+    if !bytes.starts_with(&JPEG_SOI) {
+        bail!("Geen JPEG-bestand");
+    }
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(&JPEG_SOI);
+    let mut pos = 2;
+    loop {
+        if pos + 1 >= bytes.len() || bytes[pos] != 0xFF {
+            bail!("Ongeldige JPEG-structuur");
+        }
+        let marker = bytes[pos + 1];
+        if marker == 0xFF {
+            // Fill byte before a marker.
+            pos += 1;
+            continue;
+        }
+        if marker == 0xDA {
+            // Start of scan: the entropy-coded image data follows.
+            out.extend_from_slice(&bytes[pos..]);
+            return Ok(out);
+        }
+        if marker == 0x01 || (0xD0..=0xD9).contains(&marker) {
+            out.extend_from_slice(&bytes[pos..pos + 2]);
+            pos += 2;
+            if marker == 0xD9 {
+                return Ok(out);
+            }
+            continue;
+        }
+        let Some(len) = bytes
+            .get(pos + 2..pos + 4)
+            .map(|len| u16::from_be_bytes([len[0], len[1]]) as usize)
+        else {
+            bail!("Onvolledig JPEG-segment");
+        };
+        let end = pos + 2 + len;
+        if len < 2 || end > bytes.len() {
+            bail!("Onvolledig JPEG-segment");
+        }
+        if keep(marker, &bytes[pos + 4..end]) {
+            out.extend_from_slice(&bytes[pos..end]);
+        }
+        pos = end;
+    }
+}
+
+fn strip_png(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(bytes.len());
+    out.extend_from_slice(&PNG_SIGNATURE);
+    let mut pos = PNG_SIGNATURE.len();
+    while pos < bytes.len() {
+        let Some(header) = bytes.get(pos..pos + 8) else {
+            bail!("Onvolledige PNG-chunk");
+        };
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let kind = &header[4..8];
+        // Length, type, data and CRC.
+        let end = pos + 12 + len;
+        if end > bytes.len() {
+            bail!("Onvolledige PNG-chunk");
+        }
+        if !PNG_METADATA_CHUNKS
+            .iter()
+            .any(|chunk| chunk.as_slice() == kind)
+        {
+            out.extend_from_slice(&bytes[pos..end]);
+        }
+        pos = end;
+        if kind == b"IEND" {
+            break;
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+        let mut out = vec![0xFF, marker];
+        out.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        out.extend_from_slice(payload);
+        out
+    }
+
+    #[test]
+    fn jpeg_loses_exif_and_keeps_the_image() {
+        let mut jpeg = image::RgbImage::new(4, 4);
+        jpeg.put_pixel(1, 1, image::Rgb([200, 10, 10]));
+        let mut encoded = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new(&mut encoded)
+            .encode_image(&jpeg)
+            .unwrap();
+        // Insert EXIF with a GPS tag, XMP and a comment right after SOI.
+        let mut tagged = encoded[..2].to_vec();
+        tagged.extend(segment(0xE1, b"Exif\0\0GPSLatitude 51.05"));
+        tagged.extend(segment(0xE1, b"http://ns.adobe.com/xap/1.0/\0<x/>"));
+        tagged.extend(segment(0xFE, b"buurman"));
+        tagged.extend_from_slice(&encoded[2..]);
+
+        let stripped = strip_metadata(&tagged).unwrap();
+        let text = String::from_utf8_lossy(&stripped);
+        assert!(!text.contains("GPSLatitude"));
+        assert!(!text.contains("adobe"));
+        assert!(!text.contains("buurman"));
+        assert_eq!(
+            image::load_from_memory(&stripped).unwrap().to_rgb8(),
+            image::load_from_memory(&encoded).unwrap().to_rgb8()
+        );
+    }
+
+    #[test]
+    fn png_loses_text_chunks() {
+        let mut encoded = Vec::new();
+        image::RgbImage::new(2, 2)
+            .write_to(
+                &mut std::io::Cursor::new(&mut encoded),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+        let mut text_chunk = 9u32.to_be_bytes().to_vec();
+        text_chunk.extend_from_slice(b"tEXtAuthor\0me");
+        text_chunk.extend_from_slice(&[0; 4]);
+        // After the signature and IHDR (8 + 25 bytes).
+        let mut tagged = encoded[..33].to_vec();
+        tagged.extend(&text_chunk);
+        tagged.extend_from_slice(&encoded[33..]);
+
+        let stripped = strip_metadata(&tagged).unwrap();
+        assert_eq!(stripped, encoded);
+        assert!(strip_metadata(b"GIF89a").is_err());
+    }
+
+    #[test]
+    fn share_records_are_appended() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("log").join("shared_uploads.jsonl");
+        let record = ShareRecord {
+            shared_at: "2026-05-01T08:00:00+02:00".to_string(),
+            file: PathBuf::from("/fotos/IMG_0001.JPG"),
+            label: "koolmees".to_string(),
+            destination: "Roboflow voederhuiscamera".to_string(),
+        };
+        append_share_record(&log, &record).unwrap();
+        append_share_record(&log, &record).unwrap();
+        assert_eq!(read_share_log(&log), vec![record.clone(), record]);
+    }
+}
@@ -1,22 +1,28 @@
 //! Roboflow upload helper used when sharing manual corrections.
 
+use crate::upload_queue::StatusError;
 use anyhow::{Context, anyhow};
 use reqwest::blocking::{Client, multipart};
 use std::path::Path;
 use std::time::Duration;
 
-/// Uploads a single image and label pair to Roboflow for improving recognition.
-pub fn upload_to_roboflow(
+/// Base URL of the Roboflow upload API.
+pub(crate) const ROBOFLOW_API_URL: &str = "https://api.roboflow.com";
+
+/// Uploads a single image and label pair to the Roboflow API at `base_url`.
+///
+/// The id Roboflow gives the image is stored in `upload_id` before the label
+/// is attached. When it is already set, the image was uploaded by an earlier
+/// attempt and only the label is sent.
+pub(crate) fn upload_to_roboflow_at(
+    base_url: &str,
     path: &Path,
     label: &str,
     dataset: &str,
     api_key: &str,
+    upload_id: &mut Option<String>,
 ) -> anyhow::Result<()> {
-    let filename = path
-        .file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| "image".to_string());
-
+    let base_url = base_url.trim_end_matches('/');
     let dataset_slug = dataset.trim_matches('/');
     if dataset_slug.is_empty() {
         return Err(anyhow!("Roboflow datasetnaam ontbreekt"));
@@ -28,8 +34,54 @@ pub fn upload_to_roboflow(
         .build()
         .context("HTTP client bouwen")?;
 
+    let upload_id = match upload_id {
+        Some(id) => id,
+        None => upload_id.insert(upload_image(
+            &client,
+            base_url,
+            &dataset_slug_encoded,
+            api_key,
+            path,
+        )?),
+    };
+
+    // Attach a CSV classification annotation so Roboflow applies the selected label.
+    let annotate_url = format!(
+        "{base_url}/dataset/{}/annotate/{}?api_key={}&name={}",
+        dataset_slug_encoded,
+        urlencoding::encode(upload_id),
+        api_key,
+        urlencoding::encode("classification.csv")
+    );
+    let annotation_text = format!("{label}\n");
+
+    let response = client
+        .post(&annotate_url)
+        .header("Content-Type", "text/plain")
+        .body(annotation_text)
+        .send()
+        .context("Roboflow-annotatie mislukt")?;
+    if !response.status().is_success() {
+        return Err(StatusError::from_response(response, "Roboflow-annotatie").into());
+    }
+
+    Ok(())
+}
+
+/// Uploads the image itself and returns the id Roboflow gave it.
+fn upload_image(
+    client: &Client,
+    base_url: &str,
+    dataset_slug_encoded: &str,
+    api_key: &str,
+    path: &Path,
+) -> anyhow::Result<String> {
+    let filename = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "image".to_string());
     let upload_url = format!(
-        "https://api.roboflow.com/dataset/{}/upload?api_key={}&name={}&split=train",
+        "{base_url}/dataset/{}/upload?api_key={}&name={}&split=train",
         dataset_slug_encoded,
         api_key,
         urlencoding::encode(&filename)
@@ -44,17 +96,9 @@ pub fn upload_to_roboflow(
         .multipart(form)
         .send()
         .context("Roboflow-upload mislukt")?;
-    let status = response.status();
-    let response = if status.is_success() {
-        response
-    } else {
-        let body = response
-            .text()
-            .unwrap_or_else(|_| "<geen body>".to_string());
-        return Err(anyhow!(
-            "Roboflow-upload gaf een foutstatus: {status} - {body}"
-        ));
-    };
+    if !response.status().is_success() {
+        return Err(StatusError::from_response(response, "Roboflow-upload").into());
+    }
 
     let json: serde_json::Value = response
         .json()
@@ -69,32 +113,5 @@ pub fn upload_to_roboflow(
         })
         .ok_or_else(|| anyhow!("Upload-ID ontbreekt in Roboflow-antwoord: {json}"))?;
     tracing::info!("Roboflow-upload voltooid ({upload_id})");
-
-    // Attach a CSV classification annotation so Roboflow applies the selected label.
-    let annotate_url = format!(
-        "https://api.roboflow.com/dataset/{}/annotate/{}?api_key={}&name={}",
-        dataset_slug_encoded,
-        urlencoding::encode(upload_id),
-        api_key,
-        urlencoding::encode("classification.csv")
-    );
-    let annotation_text = format!("{label}\n");
-
-    let response = client
-        .post(&annotate_url)
-        .header("Content-Type", "text/plain")
-        .body(annotation_text)
-        .send()
-        .context("Roboflow-annotatie mislukt")?;
-    let status = response.status();
-    if !status.is_success() {
-        let body = response
-            .text()
-            .unwrap_or_else(|_| "<geen body>".to_string());
-        return Err(anyhow!(
-            "Roboflow-annotatie gaf een foutstatus: {status} - {body}"
-        ));
-    }
-
-    Ok(())
+    Ok(upload_id.to_string())
 }
@@ -1,8 +1,11 @@
 //! Persistence for user settings such as language preference.
 
+use crate::annotation::AnnotationSettings;
 use crate::i18n::LanguagePreference;
+use crate::manifest::UpdateChannel;
 use directories_next::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -12,6 +15,12 @@ pub(crate) struct AppSettings {
     pub(crate) language: LanguagePreference,
     pub(crate) background_labels: Vec<String>,
     pub(crate) scan_recursive: bool,
+    pub(crate) use_personal_model: bool,
+    /// Installed model id per scanned folder (camera/site), by folder path.
+    pub(crate) site_models: BTreeMap<String, String>,
+    pub(crate) update_channel: UpdateChannel,
+    /// Backend that manual corrections are uploaded to.
+    pub(crate) annotation: AnnotationSettings,
 }
 
 impl Default for AppSettings {
@@ -20,6 +29,10 @@ impl Default for AppSettings {
             language: LanguagePreference::System,
             background_labels: vec!["achtergrond".to_string()],
             scan_recursive: false,
+            use_personal_model: false,
+            site_models: BTreeMap::new(),
+            update_channel: UpdateChannel::Stable,
+            annotation: AnnotationSettings::default(),
         }
     }
 }
@@ -0,0 +1,145 @@
+//! Verification of the ed25519 signatures on the manifest and update packages.
+//!
+//! The release key's public half is baked in at build time via
+//! `FEEDIE_UPDATE_PUBLIC_KEY`. Signatures are hex-encoded and cover the raw
+//! bytes of the signed file.
+
+use crate::app::UPDATE_PUBLIC_KEY;
+use ed25519_dalek::{Signature, VerifyingKey};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Reasons an update is refused; each has a localized message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SignatureError {
+    /// This build has no (valid) update key, so nothing can be trusted.
+    NoKey,
+    /// The manifest or package carries no signature.
+    Missing,
+    /// The signature does not match the content.
+    Invalid,
+}
+
+impl SignatureError {
+    pub(crate) fn message_key(self) -> &'static str {
+        match self {
+            SignatureError::NoKey => "updates-signature-no-key",
+            SignatureError::Missing => "updates-signature-missing",
+            SignatureError::Invalid => "updates-signature-invalid",
+        }
+    }
+}
+
+impl fmt::Display for SignatureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SignatureError::NoKey => "Deze build bevat geen sleutel om updates te controleren.",
+            SignatureError::Missing => "Update is niet ondertekend.",
+            SignatureError::Invalid => "Handtekening van de update is ongeldig.",
+        })
+    }
+}
+
+impl std::error::Error for SignatureError {}
+
+/// Returns the embedded update key, or `None` when the build has none.
+pub(crate) fn update_public_key() -> Option<VerifyingKey> {
+    parse_public_key(UPDATE_PUBLIC_KEY)
+}
+
+/// Parses a hex-encoded 32-byte ed25519 public key.
+pub(crate) fn parse_public_key(hex_key: &str) -> Option<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(hex_key.trim()).ok()?.try_into().ok()?;
+    VerifyingKey::from_bytes(&bytes).ok()
+}
+
+/// Checks a hex-encoded signature over `data` against `key`.
+pub(crate) fn verify_signature(
+    key: Option<&VerifyingKey>,
+    data: &[u8],
+    signature_hex: Option<&str>,
+) -> Result<(), SignatureError> {
+    let key = key.ok_or(SignatureError::NoKey)?;
+    let signature_hex = signature_hex
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or(SignatureError::Missing)?;
+    let bytes: [u8; 64] = hex::decode(signature_hex)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(SignatureError::Invalid)?;
+    key.verify_strict(data, &Signature::from_bytes(&bytes))
+        .map_err(|_| SignatureError::Invalid)
+}
+
+/// Checks a hex-encoded signature over the contents of the file at `path`.
+pub(crate) fn verify_file_signature(
+    key: Option<&VerifyingKey>,
+    path: &Path,
+    signature_hex: Option<&str>,
+) -> Result<(), SignatureError> {
+    let data = fs::read(path).map_err(|err| {
+        tracing::warn!("Kon {} niet lezen voor handtekening: {err}", path.display());
+        SignatureError::Invalid
+    })?;
+    verify_signature(key, &data, signature_hex)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    /// Fixed key pair used by the update tests.
+    pub(crate) fn test_key() -> SigningKey {
+        SigningKey::from_bytes(&[7; 32])
+    }
+
+    pub(crate) fn sign_hex(data: &[u8]) -> String {
+        hex::encode(test_key().sign(data).to_bytes())
+    }
+
+    #[test]
+    fn signatures_are_required_and_checked() {
+        let key = test_key().verifying_key();
+        let hex_key = hex::encode(key.to_bytes());
+        assert_eq!(parse_public_key(&hex_key), Some(key));
+        assert_eq!(parse_public_key(""), None);
+
+        let data = b"{\"app\": {}}";
+        let signature = sign_hex(data);
+        assert_eq!(verify_signature(Some(&key), data, Some(&signature)), Ok(()));
+        assert_eq!(
+            verify_signature(Some(&key), b"{\"app\": []}", Some(&signature)),
+            Err(SignatureError::Invalid)
+        );
+        assert_eq!(
+            verify_signature(Some(&key), data, Some("zz")),
+            Err(SignatureError::Invalid)
+        );
+        assert_eq!(
+            verify_signature(Some(&key), data, None),
+            Err(SignatureError::Missing)
+        );
+        assert_eq!(
+            verify_signature(None, data, Some(&signature)),
+            Err(SignatureError::NoKey)
+        );
+    }
+
+    #[test]
+    fn signatures_from_the_release_script_verify() {
+        // Made with `openssl pkeyutl -sign -rawin` and hex-encoded the way
+        // scripts/release_manifest.sh writes them.
+        let key =
+            parse_public_key("7c8efd3a157788d3e333bcf60507d40590631d31af66dca4db6e9a85097f6ed0");
+        let signature = "009e92f0b77d8ed4864392105f522e134d3fd8a301cf338542e9a68bb492ec9b\
+                         91b634976dfcacfea5e6d4647904d4d9d5205ac4d5a4d98576b0ca9b1f0b1509\n";
+        assert!(key.is_some());
+        assert_eq!(
+            verify_signature(key.as_ref(), b"{\"app\": {}}", Some(signature)),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,412 @@
+//! Export of corrected scans as a training dataset on disk.
+//!
+//! Manually labelled frames, optionally together with confident model
+//! predictions, are split per label into train/valid/test and written either
+//! as ImageFolder (`<split>/<label>/<image>`) or in the Roboflow multiclass
+//! layout (`<split>/_classes.csv` with one-hot columns) that
+//! `models/feeder_vision_efficientvit_training.py` reads. Labels are written
+//! with their name from the label file, which is what the script matches.
+
+use crate::app::{LabelOption, UiApp};
+use crate::util::{canonical_label, next_available_export_path, sanitize_for_path};
+use anyhow::{Context, anyhow};
+use eframe::egui;
+use feeder_core::Decision;
+use rfd::FileDialog;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Split folder names, in the order used by the training script.
+pub(crate) const SPLITS: [&str; 3] = ["train", "valid", "test"];
+/// Share of every label that goes to valid and test; the rest is train.
+const VALID_FRACTION: f64 = 0.2;
+const TEST_FRACTION: f64 = 0.1;
+/// Model predictions below this confidence are never exported.
+pub(crate) const MIN_PREDICTION_CONFIDENCE: f32 = 0.9;
+/// Name of the label file in each split of the Roboflow layout.
+const CLASSES_FILE: &str = "_classes.csv";
+
+/// On-disk layout of an exported dataset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum DatasetFormat {
+    #[default]
+    ImageFolder,
+    RoboflowMulticlass,
+}
+
+impl DatasetFormat {
+    pub(crate) const ALL: [DatasetFormat; 2] = [
+        DatasetFormat::ImageFolder,
+        DatasetFormat::RoboflowMulticlass,
+    ];
+
+    pub(crate) fn label_key(self) -> &'static str {
+        match self {
+            DatasetFormat::ImageFolder => "dataset-format-imagefolder",
+            DatasetFormat::RoboflowMulticlass => "dataset-format-roboflow",
+        }
+    }
+}
+
+/// One image with its label.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DatasetSample {
+    pub(crate) file: PathBuf,
+    /// Name as written in `feeder-labels.csv`, e.g. `Grote Bonte Specht`.
+    pub(crate) label: String,
+}
+
+/// Name of `label` in the label file; labels the file does not know keep
+/// the name they were stored with.
+pub(crate) fn dataset_label(options: &[LabelOption], label: &str) -> Option<String> {
+    let canonical = canonical_label(label);
+    if canonical.is_empty() {
+        return None;
+    }
+    let name = options
+        .iter()
+        .find(|option| option.canonical == canonical)
+        .map_or_else(|| label.trim().to_string(), |option| option.display.clone());
+    Some(name)
+}
+
+/// Number of images written per split.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct DatasetSummary {
+    pub(crate) counts: [usize; 3],
+    pub(crate) labels: usize,
+}
+
+/// Assigns each sample to train (0), valid (1) or test (2).
+///
+/// Per label, samples are ordered by a hash of their file name and the first
+/// ones go to valid and test, so the split is stratified, looks random and is
+/// the same on every export of the same files.
+pub(crate) fn split_samples(samples: &[DatasetSample]) -> Vec<usize> {
+    let mut by_label: BTreeMap<&str, Vec<(String, usize)>> = BTreeMap::new();
+    for (idx, sample) in samples.iter().enumerate() {
+        let name = sample
+            .file
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let key = hex::encode(Sha256::digest(format!("{}/{name}", sample.label)));
+        by_label.entry(&sample.label).or_default().push((key, idx));
+    }
+    let mut splits = vec![0; samples.len()];
+    for mut members in by_label.into_values() {
+        members.sort();
+        let total = members.len() as f64;
+        let valid = (total * VALID_FRACTION).round() as usize;
+        let test = (total * TEST_FRACTION).round() as usize;
+        for (pos, (_, idx)) in members.into_iter().enumerate() {
+            splits[idx] = if pos < valid {
+                1
+            } else if pos < valid + test {
+                2
+            } else {
+                0
+            };
+        }
+    }
+    splits
+}
+
+/// Copies the samples into `target` in the chosen layout.
+pub(crate) fn write_dataset(
+    samples: &[DatasetSample],
+    target: &Path,
+    format: DatasetFormat,
+) -> anyhow::Result<DatasetSummary> {
+    let splits = split_samples(samples);
+    let labels: BTreeSet<&str> = samples.iter().map(|sample| sample.label.as_str()).collect();
+    let mut summary = DatasetSummary {
+        labels: labels.len(),
+        ..DatasetSummary::default()
+    };
+    let mut rows: [Vec<(String, &str)>; 3] = Default::default();
+    for (sample, &split) in samples.iter().zip(&splits) {
+        let label_slug = sanitize_for_path(&sample.label);
+        let dir = match format {
+            DatasetFormat::ImageFolder => target.join(SPLITS[split]).join(&label_slug),
+            DatasetFormat::RoboflowMulticlass => target.join(SPLITS[split]),
+        };
+        fs::create_dir_all(&dir).with_context(|| format!("Kon {} niet aanmaken", dir.display()))?;
+        let stem = sample
+            .file
+            .file_stem()
+            .map(|stem| sanitize_for_path(&stem.to_string_lossy()))
+            .unwrap_or_default();
+        let ext = sample
+            .file
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_else(|| "jpg".to_string());
+        let dest = next_available_export_path(&dir, &format!("{label_slug}_{stem}"), &ext);
+        fs::copy(&sample.file, &dest).with_context(|| {
+            format!(
+                "Kon {} niet kopiëren naar {}",
+                sample.file.display(),
+                dest.display()
+            )
+        })?;
+        summary.counts[split] += 1;
+        if let Some(name) = dest.file_name() {
+            rows[split].push((name.to_string_lossy().to_string(), &sample.label));
+        }
+    }
+    if format == DatasetFormat::RoboflowMulticlass {
+        // Every split gets the same columns so class indices line up.
+        let columns: Vec<&str> = labels.into_iter().collect();
+        for (split, rows) in rows.iter().enumerate() {
+            if rows.is_empty() {
+                continue;
+            }
+            write_classes_csv(
+                &target.join(SPLITS[split]).join(CLASSES_FILE),
+                &columns,
+                rows,
+            )?;
+        }
+    }
+    Ok(summary)
+}
+
+fn write_classes_csv(path: &Path, columns: &[&str], rows: &[(String, &str)]) -> anyhow::Result<()> {
+    let mut writer = csv::Writer::from_path(path)
+        .with_context(|| format!("Kon {} niet openen", path.display()))?;
+    let mut header = vec!["filename"];
+    header.extend_from_slice(columns);
+    writer.write_record(&header)?;
+    for (file, label) in rows {
+        let mut record = vec![file.as_str()];
+        record.extend(
+            columns
+                .iter()
+                .map(|column| if column == label { "1" } else { "0" }),
+        );
+        writer.write_record(&record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+impl UiApp {
+    /// Renders the training dataset section of the export panel.
+    pub(crate) fn render_training_export_section(&mut self, ui: &mut egui::Ui) {
+        ui.heading(self.t("dataset-title"));
+        ui.label(self.t("dataset-help"));
+        ui.add_space(4.0);
+        ui.horizontal(|ui| {
+            ui.label(self.t("dataset-format"));
+            egui::ComboBox::from_id_salt("dataset_format")
+                .selected_text(self.t(self.dataset_format.label_key()))
+                .show_ui(ui, |ui| {
+                    for format in DatasetFormat::ALL {
+                        let label = self.t(format.label_key());
+                        ui.selectable_value(&mut self.dataset_format, format, label);
+                    }
+                });
+        });
+        let mut args = crate::i18n::Args::new();
+        args.insert(
+            "percent".into(),
+            ((MIN_PREDICTION_CONFIDENCE * 100.0).round() as u64).into(),
+        );
+        let predictions_label = self.t_args("dataset-include-predictions", &args);
+        ui.checkbox(&mut self.dataset_include_predictions, predictions_label);
+        if ui.button(self.t("dataset-export")).clicked() {
+            self.export_training_dataset();
+        }
+    }
+
+    /// Manual labels of the current scan, plus confident predictions when
+    /// enabled. Frames the model is unsure about are left out.
+    pub(crate) fn training_samples(&self, include_predictions: bool) -> Vec<DatasetSample> {
+        self.rijen
+            .iter()
+            .filter_map(|info| {
+                let classification = info.classification.as_ref()?;
+                let Decision::Label(name) = &classification.decision else {
+                    return None;
+                };
+                let manual = classification.is_manual();
+                let confident =
+                    include_predictions && classification.confidence >= MIN_PREDICTION_CONFIDENCE;
+                if !manual && !confident {
+                    return None;
+                }
+                Some(DatasetSample {
+                    file: info.file.clone(),
+                    label: dataset_label(&self.label_options, name)?,
+                })
+            })
+            .collect()
+    }
+
+    fn export_training_dataset(&mut self) {
+        let samples = self.training_samples(self.dataset_include_predictions);
+        if samples.is_empty() {
+            self.status = self.t("dataset-no-samples");
+            return;
+        }
+        let mut dialog = FileDialog::new();
+        if let Some(dir) = &self.gekozen_map {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(target) = dialog.pick_folder() else {
+            self.status = self.t("export-cancelled");
+            return;
+        };
+        // Mixing two exports would leak images between the splits.
+        let result = if SPLITS.iter().any(|split| target.join(split).exists()) {
+            Err(anyhow!(self.t("dataset-target-not-empty")))
+        } else {
+            write_dataset(&samples, &target, self.dataset_format)
+        };
+        match result {
+            Ok(summary) => {
+                let mut args = crate::i18n::Args::new();
+                args.insert("train".into(), (summary.counts[0] as u64).into());
+                args.insert("valid".into(), (summary.counts[1] as u64).into());
+                args.insert("test".into(), (summary.counts[2] as u64).into());
+                args.insert("labels".into(), (summary.labels as u64).into());
+                args.insert("folder".into(), target.display().to_string().into());
+                self.status = self.t_args("dataset-exported", &args);
+            }
+            Err(err) => {
+                self.status = format!("{}: {err:#}", self.t("export-failed"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(dir: &Path, label: &str, count: usize) -> Vec<DatasetSample> {
+        (0..count)
+            .map(|i| {
+                let file = dir.join(format!("{label}_{i:03}.JPG"));
+                fs::write(&file, format!("{label} {i}")).unwrap();
+                DatasetSample {
+                    file,
+                    label: label.to_string(),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn split_is_stratified_and_deterministic() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut all = samples(dir.path(), "koolmees", 20);
+        all.extend(samples(dir.path(), "merel", 10));
+        all.extend(samples(dir.path(), "gaai", 1));
+
+        let splits = split_samples(&all);
+        let count = |label: &str, split: usize| {
+            all.iter()
+                .zip(&splits)
+                .filter(|(sample, s)| sample.label == label && **s == split)
+                .count()
+        };
+        assert_eq!(
+            [
+                count("koolmees", 0),
+                count("koolmees", 1),
+                count("koolmees", 2)
+            ],
+            [14, 4, 2]
+        );
+        assert_eq!(
+            [count("merel", 0), count("merel", 1), count("merel", 2)],
+            [7, 2, 1]
+        );
+        assert_eq!(count("gaai", 0), 1);
+
+        // Order of the input does not matter.
+        let mut reversed = all.clone();
+        reversed.reverse();
+        let mut again = split_samples(&reversed);
+        again.reverse();
+        assert_eq!(again, splits);
+    }
+
+    #[test]
+    fn image_folder_layout() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let mut all = samples(source.path(), "koolmees", 10);
+        all.extend(samples(source.path(), "merel", 3));
+
+        let summary = write_dataset(&all, target.path(), DatasetFormat::ImageFolder).unwrap();
+        assert_eq!(summary.counts, [9, 3, 1]);
+        assert_eq!(summary.labels, 2);
+        let files = |split: &str, label: &str| {
+            fs::read_dir(target.path().join(split).join(label))
+                .map(|entries| entries.count())
+                .unwrap_or(0)
+        };
+        assert_eq!(files("train", "koolmees"), 7);
+        assert_eq!(files("valid", "koolmees"), 2);
+        assert_eq!(files("test", "koolmees"), 1);
+        assert_eq!(files("train", "merel"), 2);
+        assert_eq!(files("valid", "merel"), 1);
+        let names: Vec<String> = fs::read_dir(target.path().join("valid/merel"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert!(names[0].starts_with("merel_merel_") && names[0].ends_with(".jpg"));
+    }
+
+    #[test]
+    fn roboflow_layout_has_one_hot_classes_csv() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let mut all = samples(source.path(), "merel", 5);
+        all.extend(samples(source.path(), "koolmees", 5));
+
+        write_dataset(&all, target.path(), DatasetFormat::RoboflowMulticlass).unwrap();
+        let mut reader = csv::Reader::from_path(target.path().join("train/_classes.csv")).unwrap();
+        assert_eq!(
+            reader.headers().unwrap().iter().collect::<Vec<_>>(),
+            ["filename", "koolmees", "merel"]
+        );
+        let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 6);
+        for row in &rows {
+            assert!(target.path().join("train").join(&row[0]).exists());
+            let expected = if row[0].starts_with("merel") {
+                ["0", "1"]
+            } else {
+                ["1", "0"]
+            };
+            assert_eq!([&row[1], &row[2]], expected);
+        }
+        let valid = fs::read_to_string(target.path().join("valid/_classes.csv")).unwrap();
+        assert!(valid.starts_with("filename,koolmees,merel\n"));
+    }
+
+    #[test]
+    fn labels_use_the_label_file_names() {
+        let options =
+            crate::model::parse_label_options(include_str!("../../../models/feeder-labels.csv"));
+        assert_eq!(
+            dataset_label(&options, "grote bonte specht").as_deref(),
+            Some("Grote Bonte Specht")
+        );
+        assert_eq!(
+            dataset_label(&options, " Koolmees ").as_deref(),
+            Some("Koolmees")
+        );
+        assert_eq!(
+            dataset_label(&options, "Mijn Kat").as_deref(),
+            Some("Mijn Kat")
+        );
+        assert_eq!(dataset_label(&options, "  "), None);
+    }
+}
@@ -0,0 +1,759 @@
+//! Persistent queue of pending uploads of manually labelled photos.
+//!
+//! Items are stored in `upload_queue.json` in the data directory and survive
+//! restarts. New items wait for review; once confirmed, a background
+//! dispatcher uploads them with a small concurrency limit and retries failures
+//! with exponential backoff.
+
+use crate::annotation::AnnotationSettings;
+use anyhow::Context;
+use directories_next::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Number of uploads that run at the same time.
+const MAX_CONCURRENT_UPLOADS: usize = 2;
+/// Delay after the first failed attempt; doubled for every further failure.
+const RETRY_BASE_SECS: u64 = 30;
+/// Upper bound for the delay between two attempts.
+const RETRY_MAX_SECS: u64 = 60 * 60;
+
+/// Performs one upload; shared by all upload threads.
+///
+/// The uploader may set [`PendingUpload::remote_id`]; the queue keeps it when
+/// the upload fails, so the next attempt can continue from there.
+pub(crate) type Uploader = Arc<dyn Fn(&mut PendingUpload) -> anyhow::Result<()> + Send + Sync>;
+
+/// A non-success HTTP response from an upload target.
+///
+/// Uploaders return it so the queue can tell rejections that will never
+/// succeed from temporary failures.
+#[derive(Debug)]
+pub(crate) struct StatusError {
+    pub(crate) what: String,
+    pub(crate) status: reqwest::StatusCode,
+    pub(crate) body: String,
+}
+
+impl StatusError {
+    /// Reads the body of a failed `response` into an error.
+    pub(crate) fn from_response(response: reqwest::blocking::Response, what: &str) -> Self {
+        let status = response.status();
+        let body = response
+            .text()
+            .unwrap_or_else(|_| "<geen body>".to_string());
+        Self {
+            what: what.to_string(),
+            status,
+            body,
+        }
+    }
+
+    /// Server errors, timeouts and rate limits are worth another attempt;
+    /// other client errors (bad key, unknown project, …) are not.
+    fn is_retryable(&self) -> bool {
+        !self.status.is_client_error() || matches!(self.status.as_u16(), 408 | 429)
+    }
+}
+
+impl std::fmt::Display for StatusError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} gaf een foutstatus: {} - {}",
+            self.what, self.status, self.body
+        )
+    }
+}
+
+impl std::error::Error for StatusError {}
+
+/// A failure that no retry can fix, such as a photo in a format that cannot
+/// be shared or a backend that is not configured.
+#[derive(Debug)]
+pub(crate) struct PermanentError(pub(crate) String);
+
+impl std::fmt::Display for PermanentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for PermanentError {}
+
+/// One photo waiting to be uploaded.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct PendingUpload {
+    pub(crate) id: u64,
+    pub(crate) path: PathBuf,
+    pub(crate) label: String,
+    /// Backend settings when the photo was queued; the upload goes there even
+    /// when the settings change later. Missing in queues of older versions.
+    #[serde(default)]
+    pub(crate) target: Option<AnnotationSettings>,
+    /// Set once the user approved sharing this photo.
+    #[serde(default)]
+    pub(crate) confirmed: bool,
+    /// Destination named in the review window when the user approved it.
+    #[serde(default)]
+    pub(crate) confirmed_destination: Option<String>,
+    #[serde(default)]
+    pub(crate) attempts: u32,
+    /// Unix time (seconds) before which the item is not retried.
+    #[serde(default)]
+    pub(crate) next_attempt: u64,
+    #[serde(default)]
+    pub(crate) last_error: Option<String>,
+    /// Set after a failure that retrying cannot fix; only "retry now" sends
+    /// the item again.
+    #[serde(default)]
+    pub(crate) gave_up: bool,
+    /// Id the backend gave the photo once it was uploaded; a retry then only
+    /// adds the label.
+    #[serde(default)]
+    pub(crate) remote_id: Option<String>,
+    #[serde(skip)]
+    in_flight: bool,
+}
+
+impl PendingUpload {
+    /// Where the photo goes, as shown in the review window.
+    pub(crate) fn destination(&self) -> Option<String> {
+        self.target.as_ref().map(AnnotationSettings::destination)
+    }
+
+    /// Settings of the backend the user agreed to share this photo with.
+    ///
+    /// Fails when the photo would go anywhere else than the destination that
+    /// was named when it was confirmed.
+    pub(crate) fn confirmed_target(&self) -> Result<&AnnotationSettings, PermanentError> {
+        let target = self.target.as_ref().ok_or_else(|| {
+            PermanentError("Bestemming onbekend; zet de foto opnieuw in de wachtrij".to_string())
+        })?;
+        let destination = target.destination();
+        if self.confirmed_destination.as_deref() != Some(destination.as_str()) {
+            return Err(PermanentError(format!(
+                "Bestemming {destination} is niet bevestigd; bevestig de foto opnieuw"
+            )));
+        }
+        Ok(target)
+    }
+}
+
+/// Outcome of a single upload, reported to the UI.
+#[derive(Debug)]
+pub(crate) enum UploadEvent {
+    Uploaded,
+    Failed(String),
+}
+
+/// Snapshot of the queue for the settings panel.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct QueueStatus {
+    /// Items waiting for the user to confirm or remove them.
+    pub(crate) awaiting_review: usize,
+    /// Confirmed items that still have to be uploaded.
+    pub(crate) pending: usize,
+    pub(crate) uploading: usize,
+    /// Items that failed at least once.
+    pub(crate) failing: usize,
+    pub(crate) last_error: Option<String>,
+    /// Seconds until the next automatic retry of a failed item.
+    pub(crate) next_retry_in: Option<u64>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct QueueFile {
+    next_id: u64,
+    items: Vec<PendingUpload>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    wake: Condvar,
+    path: Option<PathBuf>,
+}
+
+struct State {
+    file: QueueFile,
+    shutdown: bool,
+}
+
+/// Handle to the queue; dropping it stops the dispatcher.
+pub(crate) struct UploadQueue {
+    shared: Arc<Shared>,
+}
+
+/// Location of the queue file in the user data directory.
+pub(crate) fn upload_queue_path() -> Option<PathBuf> {
+    ProjectDirs::from("nl", "Feedie", "Feedie")
+        .map(|dirs| dirs.data_dir().join("upload_queue.json"))
+}
+
+impl UploadQueue {
+    /// Loads the queue from `path` and starts uploading what is due.
+    pub(crate) fn open(
+        path: Option<PathBuf>,
+        uploader: Uploader,
+        events: Sender<UploadEvent>,
+    ) -> Self {
+        let file = path
+            .as_deref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(
+                |contents| match serde_json::from_str::<QueueFile>(&contents) {
+                    Ok(file) => Some(file),
+                    Err(err) => {
+                        tracing::warn!("Uploadwachtrij onleesbaar: {err}");
+                        None
+                    }
+                },
+            )
+            .unwrap_or_default();
+        if !file.items.is_empty() {
+            tracing::info!("{} upload(s) hervat uit de wachtrij", file.items.len());
+        }
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                file,
+                shutdown: false,
+            }),
+            wake: Condvar::new(),
+            path,
+        });
+        let dispatcher = Arc::clone(&shared);
+        thread::spawn(move || dispatch(dispatcher, uploader, events));
+        Self { shared }
+    }
+
+    /// Adds photos to the review queue for the backend in `target`; returns
+    /// how many were added.
+    pub(crate) fn enqueue(
+        &self,
+        items: impl IntoIterator<Item = (PathBuf, String)>,
+        target: &AnnotationSettings,
+    ) -> usize {
+        let mut state = self.shared.lock();
+        let mut added = 0;
+        for (path, label) in items {
+            // A photo relabelled again replaces its earlier pending upload.
+            state
+                .file
+                .items
+                .retain(|item| item.in_flight || item.path != path);
+            let id = state.file.next_id;
+            state.file.next_id += 1;
+            state.file.items.push(PendingUpload {
+                id,
+                path,
+                label,
+                target: Some(target.clone()),
+                confirmed: false,
+                confirmed_destination: None,
+                attempts: 0,
+                next_attempt: 0,
+                last_error: None,
+                gave_up: false,
+                remote_id: None,
+                in_flight: false,
+            });
+            added += 1;
+        }
+        self.shared.persist(&state);
+        self.shared.wake.notify_all();
+        added
+    }
+
+    /// Makes every waiting item due immediately.
+    pub(crate) fn retry_now(&self) {
+        let mut state = self.shared.lock();
+        for item in &mut state.file.items {
+            item.next_attempt = 0;
+            item.gave_up = false;
+        }
+        self.shared.persist(&state);
+        self.shared.wake.notify_all();
+    }
+
+    /// Items that have not been confirmed yet, oldest first.
+    pub(crate) fn awaiting_review(&self) -> Vec<PendingUpload> {
+        let state = self.shared.lock();
+        state
+            .file
+            .items
+            .iter()
+            .filter(|item| !item.confirmed)
+            .cloned()
+            .collect()
+    }
+
+    /// Approves the given items for upload to the destination shown for each.
+    ///
+    /// An item is only ever uploaded to the destination it was approved for.
+    pub(crate) fn confirm(&self, approved: &[(u64, String)]) {
+        let mut state = self.shared.lock();
+        for item in state.file.items.iter_mut() {
+            if let Some((_, destination)) = approved.iter().find(|(id, _)| *id == item.id) {
+                item.confirmed = true;
+                item.confirmed_destination = Some(destination.clone());
+            }
+        }
+        self.shared.persist(&state);
+        self.shared.wake.notify_all();
+    }
+
+    /// Removes the given items without uploading them.
+    pub(crate) fn remove(&self, ids: &[u64]) {
+        let mut state = self.shared.lock();
+        state
+            .file
+            .items
+            .retain(|item| item.in_flight || !ids.contains(&item.id));
+        self.shared.persist(&state);
+    }
+
+    /// Drops all waiting items; uploads that are already running finish.
+    pub(crate) fn clear(&self) {
+        let mut state = self.shared.lock();
+        state.file.items.retain(|item| item.in_flight);
+        self.shared.persist(&state);
+    }
+
+    pub(crate) fn status(&self) -> QueueStatus {
+        let state = self.shared.lock();
+        let now = now_secs();
+        let (items, review): (Vec<_>, Vec<_>) =
+            state.file.items.iter().partition(|item| item.confirmed);
+        QueueStatus {
+            awaiting_review: review.len(),
+            pending: items.len(),
+            uploading: items.iter().filter(|item| item.in_flight).count(),
+            failing: items.iter().filter(|item| item.attempts > 0).count(),
+            last_error: items
+                .iter()
+                .filter(|item| item.attempts > 0)
+                .max_by_key(|item| item.next_attempt)
+                .and_then(|item| item.last_error.clone()),
+            next_retry_in: items
+                .iter()
+                .filter(|item| !item.in_flight && !item.gave_up && item.attempts > 0)
+                .map(|item| item.next_attempt.saturating_sub(now))
+                .min(),
+        }
+    }
+}
+
+impl Drop for UploadQueue {
+    fn drop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.wake.notify_all();
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn persist(&self, state: &State) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Err(err) = write_queue_file(path, &state.file) {
+            tracing::warn!("Uploadwachtrij kon niet worden opgeslagen: {err:#}");
+        }
+    }
+}
+
+fn write_queue_file(path: &Path, file: &QueueFile) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, serde_json::to_string_pretty(file)?)
+        .with_context(|| format!("Kon {} niet schrijven", temp.display()))?;
+    fs::rename(&temp, path).with_context(|| format!("Kon {} niet vervangen", path.display()))
+}
+
+/// Starts due uploads and sleeps until the next one is due or the queue changes.
+fn dispatch(shared: Arc<Shared>, uploader: Uploader, events: Sender<UploadEvent>) {
+    let mut state = shared.lock();
+    loop {
+        if state.shutdown {
+            return;
+        }
+        let now = now_secs();
+        let mut running = state
+            .file
+            .items
+            .iter()
+            .filter(|item| item.in_flight)
+            .count();
+        for item in state.file.items.iter_mut() {
+            if running >= MAX_CONCURRENT_UPLOADS {
+                break;
+            }
+            if !item.confirmed || item.in_flight || item.gave_up || item.next_attempt > now {
+                continue;
+            }
+            item.in_flight = true;
+            running += 1;
+            let mut item = item.clone();
+            let shared = Arc::clone(&shared);
+            let uploader = Arc::clone(&uploader);
+            let events = events.clone();
+            thread::spawn(move || {
+                let result = if item.remote_id.is_some() || item.path.exists() {
+                    uploader(&mut item)
+                } else {
+                    Err(anyhow::Error::new(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("Bestand ontbreekt: {}", item.path.display()),
+                    )))
+                };
+                finish(&shared, &item, result, &events);
+            });
+        }
+        let wait = state
+            .file
+            .items
+            .iter()
+            .filter(|item| item.confirmed && !item.in_flight && !item.gave_up)
+            .map(|item| item.next_attempt.saturating_sub(now))
+            .min()
+            .unwrap_or(60)
+            .clamp(1, 60);
+        state = shared
+            .wake
+            .wait_timeout(state, Duration::from_secs(wait))
+            .map(|(guard, _)| guard)
+            .unwrap_or_else(|poisoned| poisoned.into_inner().0);
+    }
+}
+
+/// Records the outcome of an upload and wakes the dispatcher.
+fn finish(
+    shared: &Shared,
+    done: &PendingUpload,
+    result: anyhow::Result<()>,
+    events: &Sender<UploadEvent>,
+) {
+    let mut state = shared.lock();
+    let Some(pos) = state.file.items.iter().position(|item| item.id == done.id) else {
+        return;
+    };
+    match result {
+        Ok(()) => {
+            state.file.items.remove(pos);
+            let _ = events.send(UploadEvent::Uploaded);
+        }
+        Err(err) => {
+            let message = format!("{err:#}");
+            let item = &mut state.file.items[pos];
+            item.in_flight = false;
+            item.remote_id = done.remote_id.clone();
+            item.attempts += 1;
+            item.next_attempt = now_secs() + retry_delay(item.attempts);
+            item.last_error = Some(message.clone());
+            item.gave_up = !is_retryable(&err);
+            if item.gave_up {
+                tracing::warn!(
+                    "Upload van {} definitief mislukt: {message}",
+                    item.path.display()
+                );
+            } else {
+                tracing::warn!(
+                    "Upload van {} mislukt (poging {}): {message}",
+                    item.path.display(),
+                    item.attempts
+                );
+            }
+            let _ = events.send(UploadEvent::Failed(message));
+        }
+    }
+    shared.persist(&state);
+    shared.wake.notify_all();
+}
+
+/// A missing photo, a rejected request or a [`PermanentError`] fails the
+/// same way every time.
+fn is_retryable(err: &anyhow::Error) -> bool {
+    if err.downcast_ref::<PermanentError>().is_some() {
+        return false;
+    }
+    err.chain().all(|cause| {
+        if let Some(status) = cause.downcast_ref::<StatusError>() {
+            return status.is_retryable();
+        }
+        cause
+            .downcast_ref::<std::io::Error>()
+            .is_none_or(|io| io.kind() != std::io::ErrorKind::NotFound)
+    })
+}
+
+/// Backoff after `attempts` failures: 30 s, 1 min, 2 min, … up to an hour.
+fn retry_delay(attempts: u32) -> u64 {
+    RETRY_BASE_SECS
+        .saturating_mul(1u64 << attempts.saturating_sub(1).min(16))
+        .min(RETRY_MAX_SECS)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::roboflow::upload_to_roboflow_at;
+    use crate::test_support::MockServer;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+
+    #[test]
+    fn failed_uploads_are_kept_retried_and_resumed() {
+        let dir = tempfile::tempdir().unwrap();
+        let photo = dir.path().join("IMG_0001.JPG");
+        fs::write(&photo, b"jpeg").unwrap();
+        let queue_path = dir.path().join("upload_queue.json");
+
+        // The first upload fails, everything after that succeeds.
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let server = MockServer::start(move |request| {
+            if request.path.contains("/upload") {
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return (503, "{}".to_string());
+                }
+                return (200, r#"{"id": "img1"}"#.to_string());
+            }
+            (200, "{}".to_string())
+        });
+        let url = server.url.clone();
+        let uploader: Uploader = Arc::new(move |item: &mut PendingUpload| {
+            upload_to_roboflow_at(
+                &url,
+                &item.path,
+                &item.label,
+                "voederhuis",
+                "key",
+                &mut item.remote_id,
+            )
+        });
+
+        let (tx, rx) = mpsc::channel();
+        let queue = UploadQueue::open(Some(queue_path.clone()), Arc::clone(&uploader), tx);
+        queue.enqueue(
+            [(photo.clone(), "koolmees".to_string())],
+            &AnnotationSettings::default(),
+        );
+        // Nothing is sent before the item is confirmed in the review queue.
+        thread::sleep(Duration::from_millis(300));
+        assert!(server.requests().is_empty());
+        assert_eq!(queue.status().awaiting_review, 1);
+        let review = queue.awaiting_review();
+        assert_eq!(review[0].target, Some(AnnotationSettings::default()));
+        queue.confirm(&[(review[0].id, review[0].destination().unwrap())]);
+        let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(matches!(event, UploadEvent::Failed(_)));
+        let status = queue.status();
+        assert_eq!((status.pending, status.failing), (1, 1));
+        assert!(status.next_retry_in.unwrap() > 0);
+        drop(queue);
+
+        // After a restart the item is still there and "retry now" sends it.
+        let saved: QueueFile =
+            serde_json::from_str(&fs::read_to_string(&queue_path).unwrap()).unwrap();
+        assert_eq!(saved.items[0].attempts, 1);
+        let (tx, rx) = mpsc::channel();
+        let queue = UploadQueue::open(Some(queue_path.clone()), uploader, tx);
+        assert_eq!(queue.status().pending, 1);
+        queue.retry_now();
+        let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(matches!(event, UploadEvent::Uploaded));
+        assert_eq!(queue.status(), QueueStatus::default());
+        let saved: QueueFile =
+            serde_json::from_str(&fs::read_to_string(&queue_path).unwrap()).unwrap();
+        assert!(saved.items.is_empty());
+
+        let annotation = server
+            .requests()
+            .into_iter()
+            .find(|request| request.path.contains("/annotate/img1"))
+            .unwrap();
+        assert_eq!(annotation.method, "POST");
+        assert_eq!(annotation.body_text(), "koolmees\n");
+    }
+
+    #[test]
+    fn a_failed_annotation_is_retried_without_uploading_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let photo = dir.path().join("IMG_0001.JPG");
+        fs::write(&photo, b"jpeg").unwrap();
+        let queue_path = dir.path().join("upload_queue.json");
+
+        // The first annotation fails, the upload before it succeeds.
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let server = MockServer::start(move |request| {
+            if request.path.contains("/upload") {
+                return (200, r#"{"id": "img1"}"#.to_string());
+            }
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                return (503, "{}".to_string());
+            }
+            (200, "{}".to_string())
+        });
+        let url = server.url.clone();
+        let uploader: Uploader = Arc::new(move |item: &mut PendingUpload| {
+            upload_to_roboflow_at(
+                &url,
+                &item.path,
+                &item.label,
+                "voederhuis",
+                "key",
+                &mut item.remote_id,
+            )
+        });
+
+        let (tx, rx) = mpsc::channel();
+        let queue = UploadQueue::open(Some(queue_path.clone()), uploader, tx);
+        queue.enqueue(
+            [(photo.clone(), "koolmees".to_string())],
+            &AnnotationSettings::default(),
+        );
+        let review = queue.awaiting_review();
+        queue.confirm(&[(review[0].id, review[0].destination().unwrap())]);
+        let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(matches!(event, UploadEvent::Failed(_)));
+        let saved: QueueFile =
+            serde_json::from_str(&fs::read_to_string(&queue_path).unwrap()).unwrap();
+        assert_eq!(saved.items[0].remote_id.as_deref(), Some("img1"));
+
+        queue.retry_now();
+        let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert!(matches!(event, UploadEvent::Uploaded));
+        let paths: Vec<String> = server.requests().into_iter().map(|r| r.path).collect();
+        assert_eq!(paths.len(), 3);
+        assert_eq!(paths.iter().filter(|p| p.contains("/upload")).count(), 1);
+        assert!(paths[2].contains("/annotate/img1"));
+    }
+
+    #[test]
+    fn rejected_and_missing_uploads_fail_without_retrying() {
+        let dir = tempfile::tempdir().unwrap();
+        let photo = dir.path().join("IMG_0001.JPG");
+        fs::write(&photo, b"jpeg").unwrap();
+        let server = MockServer::start(|_| (401, "{}"));
+        let url = server.url.clone();
+        let uploader: Uploader = Arc::new(move |item: &mut PendingUpload| {
+            upload_to_roboflow_at(
+                &url,
+                &item.path,
+                &item.label,
+                "voederhuis",
+                "key",
+                &mut item.remote_id,
+            )
+        });
+
+        let (tx, rx) = mpsc::channel();
+        let queue = UploadQueue::open(None, uploader, tx);
+        queue.enqueue(
+            [
+                (photo.clone(), "koolmees".to_string()),
+                (dir.path().join("weg.jpg"), "pimpelmees".to_string()),
+            ],
+            &AnnotationSettings::default(),
+        );
+        let approved: Vec<(u64, String)> = queue
+            .awaiting_review()
+            .iter()
+            .map(|item| (item.id, item.destination().unwrap()))
+            .collect();
+        queue.confirm(&approved);
+        for _ in 0..2 {
+            let event = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+            assert!(matches!(event, UploadEvent::Failed(_)));
+        }
+        let status = queue.status();
+        assert_eq!((status.pending, status.failing), (2, 2));
+        assert_eq!(status.next_retry_in, None);
+        assert_eq!(server.requests().len(), 1);
+
+        // "Retry now" is the way to send them again, e.g. after fixing the key.
+        queue.retry_now();
+        rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn only_timeouts_rate_limits_and_server_errors_are_retried() {
+        let error = |status: u16| {
+            anyhow::Error::new(StatusError {
+                what: "Upload".to_string(),
+                status: reqwest::StatusCode::from_u16(status).unwrap(),
+                body: String::new(),
+            })
+            .context("Roboflow-upload mislukt")
+        };
+        assert!(is_retryable(&error(503)));
+        assert!(is_retryable(&error(429)));
+        assert!(is_retryable(&error(408)));
+        assert!(!is_retryable(&error(401)));
+        assert!(!is_retryable(&error(404)));
+        assert!(is_retryable(&anyhow::anyhow!("verbinding verbroken")));
+        let dir = tempfile::tempdir().unwrap();
+        let photo = dir.path().join("IMG_0001.HEIC");
+        fs::write(&photo, b"heic").unwrap();
+        let unsupported = crate::privacy::stripped_copy(&photo, dir.path()).unwrap_err();
+        assert!(!is_retryable(&unsupported));
+        let not_configured = anyhow::Error::new(PermanentError("niet ingesteld".to_string()));
+        assert!(!is_retryable(&not_configured.context("Upload mislukt")));
+    }
+
+    #[test]
+    fn uploads_only_go_to_the_confirmed_destination() {
+        let settings = AnnotationSettings::default();
+        let mut item = PendingUpload {
+            id: 1,
+            path: PathBuf::from("IMG_0001.JPG"),
+            label: "merel".to_string(),
+            target: Some(settings.clone()),
+            confirmed: true,
+            confirmed_destination: Some(settings.destination()),
+            attempts: 0,
+            next_attempt: 0,
+            last_error: None,
+            gave_up: false,
+            remote_id: None,
+            in_flight: false,
+        };
+        assert_eq!(item.confirmed_target().unwrap(), &settings);
+
+        item.confirmed_destination = Some("https://elders.example.org".to_string());
+        let err = anyhow::Error::new(item.confirmed_target().unwrap_err());
+        assert!(!is_retryable(&err));
+        item.target = None;
+        assert!(item.confirmed_target().is_err());
+    }
+
+    #[test]
+    fn retry_delay_grows_and_is_capped() {
+        assert_eq!(retry_delay(1), 30);
+        assert_eq!(retry_delay(2), 60);
+        assert_eq!(retry_delay(4), 240);
+        assert_eq!(retry_delay(40), RETRY_MAX_SECS);
+    }
+}
@@ -1,13 +1,16 @@
 //! Utility helpers for label formatting, filenames, and icons.
 
 use anyhow::{Context, anyhow};
-use chrono::{DateTime, Local};
 use eframe::egui::viewport::IconData;
-use std::fs;
+use feeder_core::LEGACY_MANUAL_SUFFIX;
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Normalizes labels by stripping Feedie suffixes and converting to lowercase.
 ///
+/// The manual suffix only still shows up in files written by older versions,
+/// such as CSV exports of a scan.
+///
 /// # Examples
 ///
 /// ```
@@ -15,7 +18,10 @@ use std::path::{Path, PathBuf};
 /// assert_eq!("koolmees", canonical);
 /// ```
 pub fn canonical_label(name: &str) -> String {
-    let stripped = name.strip_suffix(" (manueel)").unwrap_or(name).trim();
+    let stripped = name
+        .strip_suffix(LEGACY_MANUAL_SUFFIX)
+        .unwrap_or(name)
+        .trim();
     let primary = stripped
         .split_once(',')
         .map(|(first, _)| first.trim())
@@ -24,6 +30,14 @@ pub fn canonical_label(name: &str) -> String {
     cleaned.to_ascii_lowercase()
 }
 
+/// Current time as seconds since the Unix epoch.
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 /// Converts machine friendly names into a readable display label.
 ///
 /// # Examples
@@ -93,19 +107,15 @@ pub fn next_available_export_path(base_dir: &Path, base: &str, ext: &str) -> Pat
     }
 }
 
-/// Derives human readable timestamps from a file's metadata.
+/// Derives human readable date and time strings from a photo's capture time
+/// (EXIF `DateTimeOriginal`, or the file time when the photo has none).
 ///
 /// # Errors
 ///
-/// Returns an error if the metadata or timestamps cannot be read from disk.
+/// Returns an error if the photo has no capture time at all.
 pub fn extract_timestamp(path: &Path) -> anyhow::Result<(String, String)> {
-    let metadata = fs::metadata(path)
-        .with_context(|| format!("Kon metadata niet lezen voor {}", path.display()))?;
-    let system_time = metadata
-        .created()
-        .or_else(|_| metadata.modified())
+    let datetime = feeder_core::capture_time(path)
         .with_context(|| format!("Geen tijdstempel beschikbaar voor {}", path.display()))?;
-    let datetime: DateTime<Local> = system_time.into();
     let date = datetime.format("%Y-%m-%d").to_string();
     let time = datetime.format("%H:%M:%S").to_string();
     Ok((date, time))
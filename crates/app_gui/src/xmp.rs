@@ -0,0 +1,458 @@
+//! Species keywords as XMP for photo managers such as digiKam and Lightroom.
+//!
+//! Labels are written as flat `dc:subject` keywords, hierarchical keywords
+//! (`lr:hierarchicalSubject`, `digiKam:TagsList`) and a few `feedie:`
+//! attributes that let a later scan restore the exact label. They go into
+//! `<photo>.<ext>.xmp` sidecars next to the originals, or are embedded in
+//! exported JPEG copies.
+
+use crate::app::UiApp;
+use crate::privacy::{JPEG_SOI, filter_jpeg_segments};
+use crate::util::{canonical_label, now_secs};
+use anyhow::{Context, bail};
+use feeder_core::{Classification, Decision, ImageInfo, Provenance};
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const FEEDIE_NS: &str = "https://github.com/kpauly/feedie/ns/1.0/";
+/// Identifier that starts the payload of an XMP APP1 segment.
+const XMP_JPEG_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+
+/// Label of one photo as stored in XMP.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct XmpLabel {
+    pub(crate) canonical: String,
+    /// Species name in the interface language.
+    pub(crate) display: String,
+    pub(crate) scientific: Option<String>,
+    /// Model probability; `None` for manual labels.
+    pub(crate) confidence: Option<f32>,
+    pub(crate) manual: bool,
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Builds a complete XMP packet for `label`.
+pub(crate) fn xmp_packet(label: &XmpLabel) -> String {
+    let source = if label.manual { "manual" } else { "model" };
+    let mut flat = vec![label.display.clone()];
+    let mut hierarchy = vec![vec!["Species", label.display.as_str()]];
+    if let Some(scientific) = &label.scientific {
+        flat.push(scientific.clone());
+        hierarchy.push(vec!["Scientific name", scientific]);
+    }
+    let source_name = if label.manual { "Manual" } else { "Model" };
+    hierarchy.push(vec!["Source", source_name]);
+    let percent = label
+        .confidence
+        .map(|confidence| format!("{:.0}%", confidence * 100.0));
+    if let Some(percent) = &percent {
+        hierarchy.push(vec!["Confidence", percent]);
+    }
+    let bag = |items: Vec<String>| -> String {
+        items
+            .iter()
+            .map(|item| format!("      <rdf:li>{}</rdf:li>\n", xml_escape(item)))
+            .collect()
+    };
+    let with_separator = |separator: &str| -> Vec<String> {
+        hierarchy
+            .iter()
+            .map(|path| format!("Feedie{separator}{}", path.join(separator)))
+            .collect()
+    };
+    let confidence_attr = label
+        .confidence
+        .map(|confidence| format!("\n    feedie:Confidence=\"{confidence:.3}\""))
+        .unwrap_or_default();
+    format!(
+        r#"<?xpacket begin="{bom}" id="W5M0MpCehiHzreSzNTczkc9d"?>
+<x:xmpmeta xmlns:x="adobe:ns:meta/" x:xmptk="Feedie">
+ <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about=""
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:lr="http://ns.adobe.com/lightroom/1.0/"
+    xmlns:digiKam="http://www.digikam.org/ns/1.0/"
+    xmlns:feedie="{FEEDIE_NS}"
+    feedie:Label="{canonical}"
+    feedie:Source="{source}"{confidence_attr}>
+   <dc:subject>
+    <rdf:Bag>
+{flat}    </rdf:Bag>
+   </dc:subject>
+   <lr:hierarchicalSubject>
+    <rdf:Bag>
+{lightroom}    </rdf:Bag>
+   </lr:hierarchicalSubject>
+   <digiKam:TagsList>
+    <rdf:Seq>
+{digikam}    </rdf:Seq>
+   </digiKam:TagsList>
+  </rdf:Description>
+ </rdf:RDF>
+</x:xmpmeta>
+<?xpacket end="w"?>"#,
+        bom = '\u{feff}',
+        canonical = xml_escape(&label.canonical),
+        flat = bag(flat),
+        lightroom = bag(with_separator("|")),
+        digikam = bag(with_separator("/")),
+    )
+}
+
+/// Reads the `feedie:` attributes back from a packet written by
+/// [`xmp_packet`]; other XMP yields `None`.
+pub(crate) fn parse_xmp_label(packet: &str) -> Option<XmpLabel> {
+    let attribute = |name: &str| {
+        let start = packet.find(&format!("feedie:{name}=\""))? + name.len() + 9;
+        let len = packet[start..].find('"')?;
+        Some(xml_unescape(&packet[start..start + len]))
+    };
+    let canonical = canonical_label(&attribute("Label")?);
+    if canonical.is_empty() {
+        return None;
+    }
+    Some(XmpLabel {
+        display: canonical.clone(),
+        canonical,
+        scientific: None,
+        confidence: attribute("Confidence").and_then(|value| value.parse().ok()),
+        manual: attribute("Source").as_deref() == Some("manual"),
+    })
+}
+
+/// Sidecar next to `file`, named `<file>.<ext>.xmp` like digiKam does.
+pub(crate) fn sidecar_path(file: &Path) -> PathBuf {
+    let mut name = OsString::from(file.as_os_str());
+    name.push(".xmp");
+    PathBuf::from(name)
+}
+
+/// True when `packet` is exactly what [`xmp_packet`] wrote, so replacing it
+/// loses nothing another program added.
+fn is_unedited_packet(packet: &str) -> bool {
+    let Some(mut label) = parse_xmp_label(packet) else {
+        return false;
+    };
+    let Some(subjects) = packet
+        .split_once("<dc:subject>")
+        .and_then(|(_, rest)| rest.split_once("</dc:subject>"))
+        .map(|(subjects, _)| subjects)
+    else {
+        return false;
+    };
+    let mut keywords = subjects
+        .split("<rdf:li>")
+        .skip(1)
+        .filter_map(|item| item.split_once("</rdf:li>"))
+        .map(|(keyword, _)| xml_unescape(keyword));
+    let Some(display) = keywords.next() else {
+        return false;
+    };
+    label.display = display;
+    label.scientific = keywords.next();
+    xmp_packet(&label) == packet
+}
+
+/// Writes the sidecar for `file`. Returns `false` without touching it when a
+/// sidecar exists that Feedie did not write, or that digiKam, Lightroom or
+/// another program has changed since.
+pub(crate) fn write_sidecar(file: &Path, label: &XmpLabel) -> anyhow::Result<bool> {
+    let path = sidecar_path(file);
+    if let Ok(existing) = fs::read_to_string(&path)
+        && !is_unedited_packet(&existing)
+    {
+        return Ok(false);
+    }
+    fs::write(&path, xmp_packet(label))
+        .with_context(|| format!("Kon {} niet schrijven", path.display()))?;
+    Ok(true)
+}
+
+/// Reads a Feedie label from `<file>.<ext>.xmp` or `<file>.xmp`.
+pub(crate) fn read_sidecar(file: &Path) -> Option<XmpLabel> {
+    [sidecar_path(file), file.with_extension("xmp")]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .find_map(|packet| parse_xmp_label(&packet))
+}
+
+/// Replaces any XMP in a JPEG with `packet`, placed after the JFIF and EXIF
+/// segments.
+pub(crate) fn embed_in_jpeg(bytes: &[u8], packet: &str) -> anyhow::Result<Vec<u8>> {
+    let bytes = filter_jpeg_segments(bytes, |marker, payload| {
+        !(marker == 0xE1 && payload.starts_with(XMP_JPEG_HEADER))
+    })?;
+    let segment_len = 2 + XMP_JPEG_HEADER.len() + packet.len();
+    let Ok(segment_len) = u16::try_from(segment_len) else {
+        bail!("XMP-pakket te groot voor een JPEG-segment");
+    };
+    let mut pos = JPEG_SOI.len();
+    while let [0xFF, 0xE0 | 0xE1, high, low, ..] = bytes[pos..] {
+        pos += 2 + u16::from_be_bytes([high, low]) as usize;
+    }
+    let mut out = Vec::with_capacity(bytes.len() + segment_len as usize + 2);
+    out.extend_from_slice(&bytes[..pos]);
+    out.extend_from_slice(&[0xFF, 0xE1]);
+    out.extend_from_slice(&segment_len.to_be_bytes());
+    out.extend_from_slice(XMP_JPEG_HEADER);
+    out.extend_from_slice(packet.as_bytes());
+    out.extend_from_slice(&bytes[pos..]);
+    Ok(out)
+}
+
+/// Tags an exported copy: JPEGs get embedded XMP, other files a sidecar.
+pub(crate) fn tag_export_copy(path: &Path, label: &XmpLabel) -> anyhow::Result<()> {
+    let bytes = fs::read(path).with_context(|| format!("Kon {} niet lezen", path.display()))?;
+    if bytes.starts_with(&JPEG_SOI) {
+        let tagged = embed_in_jpeg(&bytes, &xmp_packet(label))?;
+        fs::write(path, tagged).with_context(|| format!("Kon {} niet schrijven", path.display()))
+    } else {
+        write_sidecar(path, label).map(|_| ())
+    }
+}
+
+/// True when restoring `label` changes `current`: a model sidecar never
+/// replaces a manual label, and a sidecar with the label the frame already
+/// has only counts when it turns a prediction into a manual label.
+fn sidecar_applies(current: Option<&Classification>, label: &XmpLabel) -> bool {
+    let Some(current) = current else {
+        return true;
+    };
+    if current.is_manual() && !label.manual {
+        return false;
+    }
+    let same_label = matches!(
+        &current.decision,
+        Decision::Label(name) if canonical_label(name) == label.canonical
+    );
+    !same_label || (label.manual && !current.is_manual())
+}
+
+impl UiApp {
+    /// Builds the XMP label of a scanned frame; frames without a label get
+    /// no keywords.
+    pub(crate) fn xmp_label(&self, info: &ImageInfo) -> Option<XmpLabel> {
+        let Some(
+            classification @ Classification {
+                decision: Decision::Label(name),
+                confidence,
+                ..
+            },
+        ) = &info.classification
+        else {
+            return None;
+        };
+        let canonical = canonical_label(name);
+        let manual = classification.is_manual();
+        Some(XmpLabel {
+            display: self.display_for(&canonical),
+            scientific: self.scientific_for(&canonical),
+            confidence: (!manual).then_some(*confidence),
+            manual,
+            canonical,
+        })
+    }
+
+    /// Context-menu action: writes sidecars next to the selected originals.
+    pub(crate) fn write_xmp_sidecars(&mut self, indices: &[usize]) {
+        let mut written = 0usize;
+        let mut skipped = 0usize;
+        for &idx in indices {
+            let Some(info) = self.rijen.get(idx) else {
+                continue;
+            };
+            let Some(label) = self.xmp_label(info) else {
+                skipped += 1;
+                continue;
+            };
+            match write_sidecar(&info.file, &label) {
+                Ok(true) => written += 1,
+                Ok(false) => {
+                    tracing::info!(
+                        "XMP-sidecar van ander programma overgeslagen: {}",
+                        sidecar_path(&info.file).display()
+                    );
+                    skipped += 1;
+                }
+                Err(err) => {
+                    tracing::warn!("{err:#}");
+                    skipped += 1;
+                }
+            }
+        }
+        let mut args = crate::i18n::Args::new();
+        args.insert("written".into(), written.into());
+        args.insert("skipped".into(), skipped.into());
+        self.status = self.t_args("xmp-sidecars-written", &args);
+    }
+
+    /// Restores labels from Feedie sidecars next to the scanned photos.
+    pub(crate) fn import_xmp_sidecars(&mut self) {
+        let mut restored = 0usize;
+        for idx in 0..self.rijen.len() {
+            let Some(label) = read_sidecar(&self.rijen[idx].file) else {
+                continue;
+            };
+            if !sidecar_applies(self.rijen[idx].classification.as_ref(), &label) {
+                continue;
+            }
+            let not_a_species = label.canonical == "achtergrond" || label.canonical == "iets sp";
+            let (confidence, present) = if label.manual {
+                (1.0, !not_a_species)
+            } else {
+                let confidence = label.confidence.unwrap_or(0.0);
+                let present = !not_a_species
+                    && !self.is_background_label(&label.canonical)
+                    && confidence >= self.presence_threshold;
+                (confidence, present)
+            };
+            let info = &mut self.rijen[idx];
+            let provenance = if label.manual {
+                Provenance::manual(info.classification.as_ref(), now_secs())
+            } else {
+                Provenance::default()
+            };
+            info.classification = Some(Classification {
+                decision: Decision::Label(label.canonical),
+                confidence,
+                provenance,
+            });
+            info.present = present;
+            restored += 1;
+        }
+        if restored > 0 {
+            self.save_cache_for_current_folder();
+        }
+        let mut args = crate::i18n::Args::new();
+        args.insert("restored".into(), restored.into());
+        self.status = self.t_args("xmp-import-done", &args);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(manual: bool) -> XmpLabel {
+        XmpLabel {
+            canonical: "koolmees".to_string(),
+            display: "Great Tit".to_string(),
+            scientific: Some("Parus major".to_string()),
+            confidence: (!manual).then_some(0.874),
+            manual,
+        }
+    }
+
+    #[test]
+    fn packet_has_keywords_and_roundtrips() {
+        let packet = xmp_packet(&label(false));
+        assert!(packet.contains("<rdf:li>Great Tit</rdf:li>"));
+        assert!(packet.contains("<rdf:li>Parus major</rdf:li>"));
+        assert!(packet.contains("<rdf:li>Feedie|Scientific name|Parus major</rdf:li>"));
+        assert!(packet.contains("<rdf:li>Feedie/Source/Model</rdf:li>"));
+        assert!(packet.contains("<rdf:li>Feedie|Confidence|87%</rdf:li>"));
+
+        let parsed = parse_xmp_label(&packet).unwrap();
+        assert_eq!(parsed.canonical, "koolmees");
+        assert!(!parsed.manual);
+        assert_eq!(parsed.confidence, Some(0.874));
+        let manual = parse_xmp_label(&xmp_packet(&label(true))).unwrap();
+        assert!(manual.manual);
+        assert_eq!(manual.confidence, None);
+    }
+
+    #[test]
+    fn sidecars_never_replace_manual_labels_or_repeat_a_label() {
+        let classification = |name: &str, manual: bool| Classification {
+            decision: Decision::Label(name.to_string()),
+            confidence: 0.9,
+            provenance: if manual {
+                Provenance::manual(None, 0)
+            } else {
+                Provenance::default()
+            },
+        };
+        let manual_pimpelmees = classification("Pimpelmees", true);
+        let model_koolmees = classification("Koolmees", false);
+
+        assert!(sidecar_applies(None, &label(false)));
+        assert!(!sidecar_applies(Some(&manual_pimpelmees), &label(false)));
+        assert!(sidecar_applies(Some(&manual_pimpelmees), &label(true)));
+        assert!(!sidecar_applies(Some(&model_koolmees), &label(false)));
+        assert!(sidecar_applies(Some(&model_koolmees), &label(true)));
+        assert!(!sidecar_applies(
+            Some(&classification("Koolmees", true)),
+            &label(true)
+        ));
+    }
+
+    #[test]
+    fn sidecars_skip_foreign_files_and_are_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let photo = dir.path().join("IMG_0001.JPG");
+        assert!(write_sidecar(&photo, &label(true)).unwrap());
+        assert_eq!(sidecar_path(&photo), dir.path().join("IMG_0001.JPG.xmp"));
+        assert!(read_sidecar(&photo).unwrap().manual);
+
+        let other = dir.path().join("IMG_0002.JPG");
+        fs::write(sidecar_path(&other), "<x:xmpmeta>digiKam</x:xmpmeta>").unwrap();
+        assert!(!write_sidecar(&other, &label(true)).unwrap());
+        assert_eq!(read_sidecar(&other), None);
+    }
+
+    #[test]
+    fn sidecars_edited_by_other_programs_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let photo = dir.path().join("IMG_0003.JPG");
+        let mut unscientific = label(false);
+        unscientific.scientific = None;
+        assert!(write_sidecar(&photo, &unscientific).unwrap());
+        assert!(write_sidecar(&photo, &label(false)).unwrap());
+        assert!(write_sidecar(&photo, &label(true)).unwrap());
+
+        let edited = fs::read_to_string(sidecar_path(&photo))
+            .unwrap()
+            .replace("feedie:Source=", "xmp:Rating=\"4\"\n    feedie:Source=");
+        fs::write(sidecar_path(&photo), &edited).unwrap();
+        assert!(!write_sidecar(&photo, &label(false)).unwrap());
+        assert_eq!(fs::read_to_string(sidecar_path(&photo)).unwrap(), edited);
+        assert!(read_sidecar(&photo).unwrap().manual);
+    }
+
+    #[test]
+    fn embedding_replaces_xmp_and_keeps_the_image() {
+        let mut image = image::RgbImage::new(4, 4);
+        image.put_pixel(2, 2, image::Rgb([10, 200, 10]));
+        let mut encoded = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new(&mut encoded)
+            .encode_image(&image)
+            .unwrap();
+
+        let once = embed_in_jpeg(&encoded, &xmp_packet(&label(false))).unwrap();
+        let twice = embed_in_jpeg(&once, &xmp_packet(&label(true))).unwrap();
+        let text = String::from_utf8_lossy(&twice);
+        assert_eq!(text.matches("http://ns.adobe.com/xap/1.0/").count(), 1);
+        assert_eq!(parse_xmp_label(&text).map(|label| label.manual), Some(true));
+        assert_eq!(
+            image::load_from_memory(&twice).unwrap().to_rgb8(),
+            image::load_from_memory(&encoded).unwrap().to_rgb8()
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! Capture time of a photo, from its EXIF data or the file system.
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::Path;
+
+/// Returns when a photo was taken.
+///
+/// Reads the EXIF `DateTimeOriginal` tag (or `DateTime` when the camera
+/// wrote no original time) as local time. Files without usable EXIF fall
+/// back to their creation time, or their modification time where the file
+/// system has none. Returns `None` when neither is available.
+///
+/// # Examples
+///
+/// ```no_run
+/// if let Some(time) = feeder_core::capture_time("/path/to/IMG_0001.JPG".as_ref()) {
+///     println!("{}", time.to_rfc3339());
+/// }
+/// ```
+pub fn capture_time(path: &Path) -> Option<DateTime<Local>> {
+    exif_time(path).or_else(|| file_time(path))
+}
+
+fn exif_time(path: &Path) -> Option<DateTime<Local>> {
+    let file = File::open(path).ok()?;
+    let exif = exif::Reader::new()
+        .read_from_container(&mut BufReader::new(file))
+        .ok()?;
+    [exif::Tag::DateTimeOriginal, exif::Tag::DateTime]
+        .into_iter()
+        .find_map(|tag| {
+            let field = exif.get_field(tag, exif::In::PRIMARY)?;
+            let exif::Value::Ascii(values) = &field.value else {
+                return None;
+            };
+            let text = std::str::from_utf8(values.first()?).ok()?;
+            let naive = NaiveDateTime::parse_from_str(text.trim(), "%Y:%m:%d %H:%M:%S").ok()?;
+            Local.from_local_datetime(&naive).earliest()
+        })
+}
+
+fn file_time(path: &Path) -> Option<DateTime<Local>> {
+    let metadata = fs::metadata(path).ok()?;
+    let time = metadata.created().or_else(|_| metadata.modified()).ok()?;
+    Some(time.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// JPEG header with an EXIF block holding only `DateTimeOriginal`.
+    fn jpeg_with_original_time(time: &str) -> Vec<u8> {
+        // Little-endian TIFF: IFD0 points to the EXIF IFD at offset 26,
+        // whose single entry points to the 20-byte string at offset 44.
+        let mut tiff = b"II\x2A\x00\x08\x00\x00\x00".to_vec();
+        tiff.extend_from_slice(&[1, 0, 0x69, 0x87, 4, 0, 1, 0, 0, 0, 26, 0, 0, 0]);
+        tiff.extend_from_slice(&[0; 4]);
+        tiff.extend_from_slice(&[1, 0, 0x03, 0x90, 2, 0, 20, 0, 0, 0, 44, 0, 0, 0]);
+        tiff.extend_from_slice(&[0; 4]);
+        tiff.extend_from_slice(time.as_bytes());
+        tiff.push(0);
+        let mut payload = b"Exif\0\0".to_vec();
+        payload.extend(tiff);
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        jpeg.extend(payload);
+        jpeg.extend_from_slice(&[0xFF, 0xD9]);
+        jpeg
+    }
+
+    #[test]
+    fn exif_time_wins_over_the_file_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let tagged = dir.path().join("IMG_0001.JPG");
+        fs::write(&tagged, jpeg_with_original_time("2026:04:12 07:05:00")).unwrap();
+        let time = capture_time(&tagged).unwrap();
+        assert_eq!(
+            time.naive_local().to_string(),
+            "2026-04-12 07:05:00".to_string()
+        );
+
+        let plain = dir.path().join("IMG_0002.JPG");
+        fs::write(&plain, [0xFF, 0xD8, 0xFF, 0xD9]).unwrap();
+        assert_eq!(capture_time(&plain), file_time(&plain));
+        assert!(capture_time(&plain).is_some());
+        assert_eq!(capture_time(&dir.path().join("weg.jpg")), None);
+    }
+}
@@ -0,0 +1,512 @@
+//! Model evaluation against labelled ground truth.
+//!
+//! Ground truth comes either from a folder laid out as `<label>/<images>` or
+//! from any list of [`EvalSample`]s (for example manually labelled scan rows).
+//! The report covers accuracy, per-class precision/recall, a confusion matrix
+//! and a presence-threshold sweep, and can be written as JSON and HTML.
+
+use crate::{
+    Classification, Decision, EfficientVitClassifier, ImageInfo, canonical_label_for_match,
+    is_supported_image,
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Label used in reports for frames without a usable prediction.
+pub const UNKNOWN_EVAL_LABEL: &str = "unknown";
+
+/// Image with its expected label.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvalSample {
+    pub file: PathBuf,
+    pub truth: String,
+}
+
+/// Precision and recall for a single label.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassMetrics {
+    pub label: String,
+    /// Number of samples with this ground-truth label.
+    pub support: usize,
+    /// Number of samples predicted as this label.
+    pub predicted: usize,
+    pub true_positives: usize,
+    /// `None` when the label was never predicted.
+    pub precision: Option<f32>,
+    /// `None` when the label does not occur in the ground truth.
+    pub recall: Option<f32>,
+}
+
+/// Presence detection quality at a single confidence threshold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThresholdPoint {
+    pub threshold: f32,
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+    pub true_negatives: usize,
+    pub precision: Option<f32>,
+    pub recall: Option<f32>,
+}
+
+/// Full evaluation outcome.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvaluationReport {
+    /// Number of evaluated samples.
+    pub total: usize,
+    /// Number of samples whose predicted label matches the ground truth.
+    pub correct: usize,
+    pub accuracy: f32,
+    /// Canonical labels in the order used by `confusion`.
+    pub labels: Vec<String>,
+    pub per_class: Vec<ClassMetrics>,
+    /// `confusion[truth][predicted]` counts, indexed like `labels`.
+    pub confusion: Vec<Vec<usize>>,
+    /// Presence detection metrics for thresholds from 0.05 to 0.95.
+    pub threshold_sweep: Vec<ThresholdPoint>,
+}
+
+/// Collects samples from a folder laid out as `<label>/<images>`.
+///
+/// Each direct subfolder names the label; images may be nested deeper inside
+/// it. Files directly in `root` are ignored.
+///
+/// # Errors
+///
+/// Returns an error when `root` is not a readable folder.
+pub fn samples_from_folder(root: impl AsRef<Path>) -> Result<Vec<EvalSample>> {
+    let root = root.as_ref();
+    if !root.is_dir() {
+        anyhow::bail!("Pad is geen map: {}", root.display());
+    }
+    let mut label_dirs: Vec<PathBuf> = fs::read_dir(root)
+        .with_context(|| format!("Map niet te lezen: {}", root.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    label_dirs.sort();
+
+    let mut samples = Vec::new();
+    for dir in label_dirs {
+        let Some(label) = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        let mut files: Vec<PathBuf> = WalkDir::new(&dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.is_file() && is_supported_image(path))
+            .collect();
+        files.sort();
+        samples.extend(files.into_iter().map(|file| EvalSample {
+            file,
+            truth: label.clone(),
+        }));
+    }
+    Ok(samples)
+}
+
+/// Classifies the samples and compares the predictions with the ground truth.
+///
+/// The callback receives `(done, total)` like
+/// [`EfficientVitClassifier::classify_with_progress`].
+///
+/// # Errors
+///
+/// Returns an error if classification fails.
+pub fn evaluate_samples<F>(
+    classifier: &EfficientVitClassifier,
+    samples: &[EvalSample],
+    background_labels: &[String],
+    progress: F,
+) -> Result<EvaluationReport>
+where
+    F: FnMut(usize, usize),
+{
+    let mut rows: Vec<ImageInfo> = samples
+        .iter()
+        .map(|sample| ImageInfo {
+            file: sample.file.clone(),
+            present: false,
+            classification: None,
+            embedding: None,
+            embedding_model: None,
+            detections: Vec::new(),
+            top_k: Vec::new(),
+            error: None,
+        })
+        .collect();
+    classifier.classify_with_progress(&mut rows, progress)?;
+    let predictions: Vec<Option<Classification>> =
+        rows.into_iter().map(scored_prediction).collect();
+    Ok(evaluate_predictions(
+        samples,
+        &predictions,
+        background_labels,
+    ))
+}
+
+/// Prediction that is scored for a classified row.
+///
+/// The classifier reports its background class as [`Decision::Unknown`];
+/// the raw top-1 label in [`ImageInfo::top_k`] brings that label back, so a
+/// correctly predicted background frame counts as correct.
+pub(crate) fn scored_prediction(info: ImageInfo) -> Option<Classification> {
+    let mut classification = info.classification?;
+    if classification.decision == Decision::Unknown
+        && let Some(best) = info.top_k.first()
+    {
+        classification.decision = Decision::Label(best.label.clone());
+    }
+    Some(classification)
+}
+
+/// Builds a report from ground truth and matching predictions.
+///
+/// `predictions` must be in the same order as `samples`. Labels are compared
+/// after canonicalization; missing or unknown predictions count as
+/// [`UNKNOWN_EVAL_LABEL`]. For the threshold sweep a sample counts as present
+/// when its label is not one of `background_labels`.
+///
+/// # Examples
+///
+/// ```
+/// use feeder_core::{Classification, Decision, EvalSample, Provenance, evaluate_predictions};
+/// let samples = vec![EvalSample { file: "a.jpg".into(), truth: "Koolmees".into() }];
+/// let predictions = vec![Some(Classification {
+///     decision: Decision::Label("koolmees".into()),
+///     confidence: 0.9,
+///     provenance: Provenance::default(),
+/// })];
+/// let report = evaluate_predictions(&samples, &predictions, &[]);
+/// assert_eq!(report.accuracy, 1.0);
+/// ```
+pub fn evaluate_predictions(
+    samples: &[EvalSample],
+    predictions: &[Option<Classification>],
+    background_labels: &[String],
+) -> EvaluationReport {
+    let backgrounds: Vec<String> = background_labels
+        .iter()
+        .map(|label| canonical_label_for_match(label))
+        .collect();
+    let pairs: Vec<(String, String, f32)> = samples
+        .iter()
+        .zip(predictions)
+        .map(|(sample, prediction)| {
+            let truth = canonical_label_for_match(&sample.truth);
+            let (predicted, confidence) = match prediction {
+                Some(Classification {
+                    decision: Decision::Label(name),
+                    confidence,
+                    ..
+                }) => (canonical_label_for_match(name), *confidence),
+                Some(Classification { confidence, .. }) => {
+                    (UNKNOWN_EVAL_LABEL.to_string(), *confidence)
+                }
+                None => (UNKNOWN_EVAL_LABEL.to_string(), 0.0),
+            };
+            (truth, predicted, confidence)
+        })
+        .collect();
+
+    // Ground-truth labels first, then labels that were only predicted.
+    let mut labels: Vec<String> = pairs.iter().map(|(truth, _, _)| truth.clone()).collect();
+    labels.sort();
+    labels.dedup();
+    let mut extra: Vec<String> = pairs
+        .iter()
+        .map(|(_, predicted, _)| predicted.clone())
+        .filter(|predicted| !labels.contains(predicted))
+        .collect();
+    extra.sort();
+    extra.dedup();
+    labels.extend(extra);
+    let index: HashMap<&str, usize> = labels
+        .iter()
+        .enumerate()
+        .map(|(idx, label)| (label.as_str(), idx))
+        .collect();
+
+    let mut confusion = vec![vec![0usize; labels.len()]; labels.len()];
+    for (truth, predicted, _) in &pairs {
+        confusion[index[truth.as_str()]][index[predicted.as_str()]] += 1;
+    }
+    let correct = (0..labels.len()).map(|idx| confusion[idx][idx]).sum();
+    let per_class = labels
+        .iter()
+        .enumerate()
+        .map(|(idx, label)| {
+            let support: usize = confusion[idx].iter().sum();
+            let predicted: usize = confusion.iter().map(|row| row[idx]).sum();
+            let true_positives = confusion[idx][idx];
+            ClassMetrics {
+                label: label.clone(),
+                support,
+                predicted,
+                true_positives,
+                precision: ratio(true_positives, predicted),
+                recall: ratio(true_positives, support),
+            }
+        })
+        .collect();
+
+    let is_present =
+        |label: &str| label != UNKNOWN_EVAL_LABEL && !backgrounds.iter().any(|bg| bg == label);
+    let threshold_sweep = (1..20)
+        .map(|step| {
+            let threshold = step as f32 * 0.05;
+            let mut point = ThresholdPoint {
+                threshold,
+                true_positives: 0,
+                false_positives: 0,
+                false_negatives: 0,
+                true_negatives: 0,
+                precision: None,
+                recall: None,
+            };
+            for (truth, predicted, confidence) in &pairs {
+                let actual = is_present(truth);
+                let detected = is_present(predicted) && *confidence >= threshold;
+                match (actual, detected) {
+                    (true, true) => point.true_positives += 1,
+                    (false, true) => point.false_positives += 1,
+                    (true, false) => point.false_negatives += 1,
+                    (false, false) => point.true_negatives += 1,
+                }
+            }
+            point.precision = ratio(
+                point.true_positives,
+                point.true_positives + point.false_positives,
+            );
+            point.recall = ratio(
+                point.true_positives,
+                point.true_positives + point.false_negatives,
+            );
+            point
+        })
+        .collect();
+
+    EvaluationReport {
+        total: pairs.len(),
+        correct,
+        accuracy: ratio(correct, pairs.len()).unwrap_or(0.0),
+        labels,
+        per_class,
+        confusion,
+        threshold_sweep,
+    }
+}
+
+impl EvaluationReport {
+    /// Writes `evaluation.json` and `evaluation.html` into `dir`.
+    ///
+    /// Returns the path of the HTML report.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O or serialization error.
+    pub fn save(&self, dir: impl AsRef<Path>) -> Result<PathBuf> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Map niet aan te maken: {}", dir.display()))?;
+        fs::write(
+            dir.join("evaluation.json"),
+            serde_json::to_string_pretty(self)?,
+        )?;
+        let html_path = dir.join("evaluation.html");
+        fs::write(&html_path, self.to_html())?;
+        Ok(html_path)
+    }
+
+    /// Renders the report as a standalone HTML page.
+    pub fn to_html(&self) -> String {
+        let pct = |value: Option<f32>| {
+            value
+                .map(|v| format!("{:.1}%", v * 100.0))
+                .unwrap_or_else(|| "–".to_string())
+        };
+        let mut html = String::new();
+        html.push_str(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Feedie evaluation</title>\n\
+             <style>body{font-family:sans-serif;margin:2em}table{border-collapse:collapse;margin-bottom:2em}\
+             td,th{border:1px solid #ccc;padding:4px 8px;text-align:right}th:first-child,td:first-child{text-align:left}\
+             td.hit{background:#d9f2d9}td.miss{background:#f9dcdc}</style></head><body>\n",
+        );
+        let _ = writeln!(
+            html,
+            "<h1>Feedie evaluation</h1>\n<p>Accuracy: <strong>{}</strong> ({} / {})</p>",
+            pct(Some(self.accuracy)),
+            self.correct,
+            self.total
+        );
+
+        html.push_str("<h2>Per class</h2>\n<table><tr><th>Label</th><th>Support</th><th>Predicted</th><th>Precision</th><th>Recall</th></tr>\n");
+        for class in &self.per_class {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape_html(&class.label),
+                class.support,
+                class.predicted,
+                pct(class.precision),
+                pct(class.recall)
+            );
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Confusion matrix</h2>\n<p>Rows: ground truth, columns: prediction.</p>\n<table><tr><th></th>");
+        for label in &self.labels {
+            let _ = write!(html, "<th>{}</th>", escape_html(label));
+        }
+        html.push_str("</tr>\n");
+        for (row_idx, row) in self.confusion.iter().enumerate() {
+            let _ = write!(html, "<tr><td>{}</td>", escape_html(&self.labels[row_idx]));
+            for (col_idx, count) in row.iter().enumerate() {
+                let class = match (*count, row_idx == col_idx) {
+                    (0, _) => "",
+                    (_, true) => " class=\"hit\"",
+                    (_, false) => " class=\"miss\"",
+                };
+                let _ = write!(html, "<td{class}>{count}</td>");
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Presence threshold sweep</h2>\n<table><tr><th>Threshold</th><th>TP</th><th>FP</th><th>FN</th><th>TN</th><th>Precision</th><th>Recall</th></tr>\n");
+        for point in &self.threshold_sweep {
+            let _ = writeln!(
+                html,
+                "<tr><td>{:.2}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                point.threshold,
+                point.true_positives,
+                point.false_positives,
+                point.false_negatives,
+                point.true_negatives,
+                pct(point.precision),
+                pct(point.recall)
+            );
+        }
+        html.push_str("</table>\n</body></html>\n");
+        html
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> Option<f32> {
+    (denominator > 0).then(|| numerator as f32 / denominator as f32)
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Provenance;
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    #[test]
+    fn evaluate_predictions_builds_confusion_matrix_and_sweep() {
+        let sample = |file: &str, truth: &str| EvalSample {
+            file: PathBuf::from(file),
+            truth: truth.to_string(),
+        };
+        let label = |name: &str, confidence: f32| {
+            Some(Classification {
+                decision: Decision::Label(name.to_string()),
+                confidence,
+                provenance: Provenance::default(),
+            })
+        };
+        let samples = vec![
+            sample("a.jpg", "Koolmees"),
+            sample("b.jpg", "Koolmees"),
+            sample("c.jpg", "Pimpelmees"),
+            sample("d.jpg", "Achtergrond"),
+            sample("e.jpg", "Pimpelmees"),
+        ];
+        let predictions = vec![
+            label("koolmees", 0.9),
+            label("Pimpelmees", 0.6),
+            label("pimpelmees", 0.8),
+            label("Achtergrond", 0.7),
+            None,
+        ];
+        let report = evaluate_predictions(&samples, &predictions, &["Achtergrond".to_string()]);
+
+        assert_eq!((report.correct, report.total), (3, 5));
+        assert!((report.accuracy - 0.6).abs() < 1e-6);
+        assert_eq!(
+            report.labels,
+            vec!["achtergrond", "koolmees", "pimpelmees", UNKNOWN_EVAL_LABEL]
+        );
+        assert_eq!(report.confusion[1], vec![0, 1, 1, 0]);
+        assert_eq!(report.confusion[2], vec![0, 0, 1, 1]);
+        let pimpelmees = &report.per_class[2];
+        assert_eq!(pimpelmees.precision, Some(0.5));
+        assert_eq!(pimpelmees.recall, Some(0.5));
+
+        let at_half = report
+            .threshold_sweep
+            .iter()
+            .find(|point| (point.threshold - 0.5).abs() < 1e-6)
+            .unwrap();
+        assert_eq!(
+            (
+                at_half.true_positives,
+                at_half.false_positives,
+                at_half.false_negatives,
+                at_half.true_negatives
+            ),
+            (3, 0, 1, 1)
+        );
+        let at_high = report.threshold_sweep.last().unwrap();
+        assert_eq!(at_high.true_positives, 0);
+        assert_eq!(at_high.precision, None);
+    }
+
+    #[test]
+    fn samples_from_folder_uses_subfolders_as_labels() -> Result<()> {
+        let dir = tempdir()?;
+        File::create(dir.path().join("loose.jpg"))?;
+        fs::create_dir_all(dir.path().join("Koolmees").join("dag1"))?;
+        File::create(dir.path().join("Koolmees").join("a.jpg"))?;
+        File::create(dir.path().join("Koolmees").join("dag1").join("b.png"))?;
+        File::create(dir.path().join("Koolmees").join("notes.txt"))?;
+        fs::create_dir(dir.path().join("Achtergrond"))?;
+        File::create(dir.path().join("Achtergrond").join("c.jpg"))?;
+
+        let samples = samples_from_folder(dir.path())?;
+        let summary: Vec<(String, String)> = samples
+            .iter()
+            .map(|s| {
+                let name = s.file.file_name().unwrap().to_string_lossy().to_string();
+                (s.truth.clone(), name)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Achtergrond".to_string(), "c.jpg".to_string()),
+                ("Koolmees".to_string(), "a.jpg".to_string()),
+                ("Koolmees".to_string(), "b.png".to_string()),
+            ]
+        );
+        Ok(())
+    }
+}
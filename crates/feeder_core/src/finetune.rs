@@ -0,0 +1,383 @@
+//! On-device fine-tuning of the classifier head from manual corrections.
+//!
+//! Only the final linear layer is trained: the backbone and the head's batch
+//! norm stay frozen, so the stored reference embeddings can be used directly as
+//! training inputs and a run finishes in seconds on the CPU. The result is a
+//! complete personal model (stock weights plus the new head) together with an
+//! extended labels file, written to its own folder.
+
+use crate::classifier::read_label_names;
+use crate::{ClassifierConfig, ReferenceExample, ReferenceSet, canonical_label_for_match};
+use anyhow::{Context, Result};
+use candle_core::{D, DType, Device, Tensor, Var};
+use candle_nn::optim::{AdamW, Optimizer, ParamsAdamW};
+use candle_nn::{self as nn, ModuleT, VarBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// File name of the JSON report written next to a personal model.
+pub const TRAINING_REPORT_FILE_NAME: &str = "training-report.json";
+
+/// Tuning knobs for [`train_personal_head`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeadTrainingConfig {
+    /// Number of full passes over the training examples.
+    pub epochs: usize,
+    /// AdamW learning rate.
+    pub learning_rate: f64,
+    /// Strength of the penalty that keeps the head close to the stock weights,
+    /// so species without corrections are not forgotten.
+    pub anchor_weight: f64,
+    /// Every n-th example of a label is held out for evaluation; labels with
+    /// fewer than n examples are used for training only.
+    pub holdout_every: usize,
+}
+
+impl Default for HeadTrainingConfig {
+    fn default() -> Self {
+        Self {
+            epochs: 150,
+            learning_rate: 1e-2,
+            anchor_weight: 1e-3,
+            holdout_every: 5,
+        }
+    }
+}
+
+/// Outcome of a training run, stored as [`TRAINING_REPORT_FILE_NAME`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HeadTrainingReport {
+    /// Labels that the stock model does not know and were added to the head.
+    pub new_labels: Vec<String>,
+    /// Number of examples used for training.
+    pub train_examples: usize,
+    /// Number of held-out examples used for evaluation.
+    pub holdout_examples: usize,
+    /// Accuracy of the stock head on the held-out examples.
+    pub stock_accuracy: Option<f32>,
+    /// Accuracy of the personal head on the held-out examples.
+    pub personal_accuracy: Option<f32>,
+}
+
+impl HeadTrainingReport {
+    /// Loads the report stored in a personal model folder, if any.
+    pub fn load(dir: impl AsRef<Path>) -> Option<Self> {
+        let data = fs::read_to_string(dir.as_ref().join(TRAINING_REPORT_FILE_NAME)).ok()?;
+        serde_json::from_str(&data).ok()
+    }
+}
+
+/// Fine-tunes the classifier head on `references` and writes a personal model.
+///
+/// The stock model and labels from `cfg` are copied into `output_dir` under
+/// their original file names, with the linear head replaced and labels that
+/// only exist in the reference set appended. Both heads are evaluated on a
+/// deterministic held-out split (see [`HeadTrainingConfig::holdout_every`]).
+/// The callback receives `(epoch, epochs)` after every epoch.
+///
+/// # Errors
+///
+/// Returns an error when the stock model cannot be read, no usable examples
+/// exist, or the personal model cannot be written.
+pub fn train_personal_head<F>(
+    cfg: &ClassifierConfig,
+    references: &ReferenceSet,
+    training: &HeadTrainingConfig,
+    output_dir: &Path,
+    mut progress: F,
+) -> Result<HeadTrainingReport>
+where
+    F: FnMut(usize, usize),
+{
+    let device = Device::Cpu;
+    let feature_dim = cfg.variant.feature_dim();
+    let stock_labels = read_label_names(&cfg.labels_path)?;
+    let mut tensors = candle_core::safetensors::load(&cfg.model_path, &device)
+        .with_context(|| format!("Model niet te lezen: {}", cfg.model_path.display()))?;
+
+    let usable: Vec<&ReferenceExample> = references
+        .examples
+        .iter()
+        .filter(|example| example.embedding.len() == feature_dim)
+        .collect();
+    if usable.is_empty() {
+        anyhow::bail!("Geen bruikbare voorbeelden om te trainen");
+    }
+
+    // Personal labels: stock labels first, then labels only the user knows.
+    let mut labels = stock_labels.clone();
+    let mut new_labels = Vec::new();
+    for example in &usable {
+        let canonical = canonical_label_for_match(&example.label);
+        if !labels
+            .iter()
+            .any(|label| canonical_label_for_match(label) == canonical)
+        {
+            let display = display_label(&example.label);
+            labels.push(display.clone());
+            new_labels.push(display);
+        }
+    }
+    let label_index: HashMap<String, usize> = labels
+        .iter()
+        .enumerate()
+        .map(|(idx, label)| (canonical_label_for_match(label), idx))
+        .collect();
+
+    let (train, holdout) = split_examples(&usable, training.holdout_every.max(2));
+    if train.is_empty() {
+        anyhow::bail!("Geen bruikbare voorbeelden om te trainen");
+    }
+
+    let head = |name: &str| -> Result<Tensor> {
+        let tensor = tensors
+            .get(name)
+            .with_context(|| format!("Model mist tensor {name}"))?;
+        Ok(tensor.to_dtype(DType::F32)?)
+    };
+    let stock_weight = head("head.linear.weight")?;
+    let stock_bias = head("head.linear.bias")?;
+    let head_norm = {
+        let bn_tensors: HashMap<String, Tensor> = ["weight", "bias", "running_mean", "running_var"]
+            .iter()
+            .map(|name| Ok((name.to_string(), head(&format!("head.bn.{name}"))?)))
+            .collect::<Result<_>>()?;
+        let vb = VarBuilder::from_tensors(bn_tensors, DType::F32, &device);
+        nn::batch_norm(feature_dim, 1e-6, vb)?
+    };
+    let normalize = |examples: &[&ReferenceExample]| -> Result<(Tensor, Tensor)> {
+        let data: Vec<f32> = examples
+            .iter()
+            .flat_map(|example| example.embedding.iter().copied())
+            .collect();
+        let targets: Vec<u32> = examples
+            .iter()
+            .map(|example| label_index[&canonical_label_for_match(&example.label)] as u32)
+            .collect();
+        let features = Tensor::from_vec(data, (examples.len(), feature_dim), &device)?;
+        let features = head_norm.forward_t(&features, false)?;
+        Ok((features, Tensor::new(targets, &device)?))
+    };
+
+    // Start from the stock head; rows for new labels start at zero.
+    let extra = labels.len() - stock_labels.len();
+    let (anchor_weight, anchor_bias) = if extra > 0 {
+        (
+            Tensor::cat(
+                &[
+                    &stock_weight,
+                    &Tensor::zeros((extra, feature_dim), DType::F32, &device)?,
+                ],
+                0,
+            )?,
+            Tensor::cat(
+                &[&stock_bias, &Tensor::zeros(extra, DType::F32, &device)?],
+                0,
+            )?,
+        )
+    } else {
+        (stock_weight.clone(), stock_bias.clone())
+    };
+    let weight = Var::from_tensor(&anchor_weight)?;
+    let bias = Var::from_tensor(&anchor_bias)?;
+    let mut optimizer = AdamW::new(
+        vec![weight.clone(), bias.clone()],
+        ParamsAdamW {
+            lr: training.learning_rate,
+            weight_decay: 0.0,
+            ..Default::default()
+        },
+    )?;
+
+    let (train_x, train_y) = normalize(&train)?;
+    let epochs = training.epochs.max(1);
+    for epoch in 0..epochs {
+        let logits = train_x
+            .matmul(&weight.as_tensor().t()?)?
+            .broadcast_add(bias.as_tensor())?;
+        let anchor = (weight.as_tensor() - &anchor_weight)?.sqr()?.sum_all()?;
+        let loss =
+            (nn::loss::cross_entropy(&logits, &train_y)? + (anchor * training.anchor_weight)?)?;
+        optimizer.backward_step(&loss)?;
+        progress(epoch + 1, epochs);
+    }
+
+    let (stock_accuracy, personal_accuracy) = if holdout.is_empty() {
+        (None, None)
+    } else {
+        let (holdout_x, holdout_y) = normalize(&holdout)?;
+        let accuracy = |weight: &Tensor, bias: &Tensor| -> Result<f32> {
+            let predicted = holdout_x
+                .matmul(&weight.t()?)?
+                .broadcast_add(bias)?
+                .argmax(D::Minus1)?;
+            let correct = predicted
+                .eq(&holdout_y)?
+                .to_dtype(DType::F32)?
+                .sum_all()?
+                .to_scalar::<f32>()?;
+            Ok(correct / holdout.len() as f32)
+        };
+        (
+            Some(accuracy(&stock_weight, &stock_bias)?),
+            Some(accuracy(weight.as_tensor(), bias.as_tensor())?),
+        )
+    };
+
+    // Keep the stored dtype of the stock model for the replaced head.
+    for (name, trained) in [
+        ("head.linear.weight", weight.as_tensor()),
+        ("head.linear.bias", bias.as_tensor()),
+    ] {
+        let dtype = tensors.get(name).map(|t| t.dtype()).unwrap_or(DType::F32);
+        tensors.insert(name.to_string(), trained.to_dtype(dtype)?);
+    }
+
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Map niet aan te maken: {}", output_dir.display()))?;
+    let model_name = cfg
+        .model_path
+        .file_name()
+        .context("Modelpad zonder bestandsnaam")?;
+    let labels_name = cfg
+        .labels_path
+        .file_name()
+        .context("Labelpad zonder bestandsnaam")?;
+    candle_core::safetensors::save(&tensors, output_dir.join(model_name))?;
+    let mut labels_csv = fs::read_to_string(&cfg.labels_path).context("labels niet te lezen")?;
+    if !labels_csv.ends_with('\n') {
+        labels_csv.push('\n');
+    }
+    for label in &new_labels {
+        // Same column count as the stock file: six names plus scientific name.
+        labels_csv.push_str(&format!("{label},,,,,,\n"));
+    }
+    fs::write(output_dir.join(labels_name), labels_csv)?;
+
+    let report = HeadTrainingReport {
+        new_labels,
+        train_examples: train.len(),
+        holdout_examples: holdout.len(),
+        stock_accuracy,
+        personal_accuracy,
+    };
+    fs::write(
+        output_dir.join(TRAINING_REPORT_FILE_NAME),
+        serde_json::to_string_pretty(&report)?,
+    )?;
+    Ok(report)
+}
+
+/// Splits examples per label, holding out every `every`-th one.
+fn split_examples<'a>(
+    examples: &[&'a ReferenceExample],
+    every: usize,
+) -> (Vec<&'a ReferenceExample>, Vec<&'a ReferenceExample>) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut train = Vec::new();
+    let mut holdout = Vec::new();
+    for example in examples {
+        let position = seen
+            .entry(canonical_label_for_match(&example.label))
+            .or_default();
+        *position += 1;
+        if position.is_multiple_of(every) {
+            holdout.push(*example);
+        } else {
+            train.push(*example);
+        }
+    }
+    (train, holdout)
+}
+
+/// Capitalizes the first letter of a user label for the labels file.
+fn display_label(label: &str) -> String {
+    let trimmed = label.trim();
+    let mut chars = trimmed.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EfficientVitVariant;
+    use tempfile::tempdir;
+
+    #[test]
+    fn train_personal_head_learns_new_label_and_writes_model() -> Result<()> {
+        let dir = tempdir()?;
+        let dim = EfficientVitVariant::M0.feature_dim();
+        let device = Device::Cpu;
+        let tensors: HashMap<String, Tensor> = [
+            ("head.bn.weight", Tensor::ones(dim, DType::F32, &device)?),
+            ("head.bn.bias", Tensor::zeros(dim, DType::F32, &device)?),
+            (
+                "head.bn.running_mean",
+                Tensor::zeros(dim, DType::F32, &device)?,
+            ),
+            (
+                "head.bn.running_var",
+                Tensor::ones(dim, DType::F32, &device)?,
+            ),
+            (
+                "head.linear.weight",
+                Tensor::zeros((2, dim), DType::F32, &device)?,
+            ),
+            ("head.linear.bias", Tensor::zeros(2, DType::F32, &device)?),
+        ]
+        .into_iter()
+        .map(|(name, tensor)| (name.to_string(), tensor))
+        .collect();
+        let model_path = dir.path().join("model.safetensors");
+        candle_core::safetensors::save(&tensors, &model_path)?;
+        let labels_path = dir.path().join("labels.csv");
+        fs::write(
+            &labels_path,
+            "Achtergrond,Background,,,,,\nKoolmees,Great Tit,,,,,Parus major\n",
+        )?;
+
+        let mut refs = ReferenceSet::default();
+        for i in 0..5 {
+            let mut tit = vec![0.0; dim];
+            tit[0] = 1.0;
+            tit[2] = 0.1 * i as f32;
+            refs.add("koolmees", tit, None);
+            let mut squirrel = vec![0.0; dim];
+            squirrel[1] = 1.0;
+            squirrel[3] = 0.1 * i as f32;
+            refs.add("eekhoorn", squirrel, None);
+        }
+        let cfg = ClassifierConfig {
+            model_path,
+            labels_path,
+            ..Default::default()
+        };
+        let out = dir.path().join("personal");
+        let mut epochs_seen = 0;
+        let report = train_personal_head(
+            &cfg,
+            &refs,
+            &HeadTrainingConfig::default(),
+            &out,
+            |epoch, _| epochs_seen = epoch,
+        )?;
+
+        assert_eq!(epochs_seen, HeadTrainingConfig::default().epochs);
+        assert_eq!(report.new_labels, vec!["Eekhoorn".to_string()]);
+        assert_eq!((report.train_examples, report.holdout_examples), (8, 2));
+        assert_eq!(report.personal_accuracy, Some(1.0));
+        assert!(report.stock_accuracy.unwrap() < 1.0);
+        assert_eq!(HeadTrainingReport::load(&out), Some(report));
+
+        let saved = candle_core::safetensors::load(out.join("model.safetensors"), &device)?;
+        assert_eq!(saved["head.linear.weight"].dims(), &[3, dim]);
+        let labels = fs::read_to_string(out.join("labels.csv"))?;
+        assert!(labels.ends_with("Eekhoorn,,,,,,\n"));
+        Ok(())
+    }
+}
@@ -1,9 +1,13 @@
 //! # feeder_core
 //!
 //! `feeder_core` exposes the building blocks for scanning folders, running the
-//! EfficientViT classifier, and exporting CSV data. This crate is kept UI-free
-//! so both the GUI and any future CLI or service can reuse the same inference
-//! pipeline.
+//! EfficientViT classifier, ranking frames by visual similarity, predicting
+//! user-defined labels from a few-shot reference set, fine-tuning a personal
+//! classifier head, evaluating a model against ground truth, exchanging
+//! MegaDetector batch-output files, and exporting and re-importing CSV data
+//! (including the versioned CSV/JSON Lines frame records). This crate is
+//! kept UI-free so both the GUI and any future CLI or service can reuse the
+//! same inference pipeline.
 //!
 //! ## Examples
 //!
@@ -34,7 +38,33 @@ use zune_jpeg::zune_core::bytestream::ZCursor;
 use zune_jpeg::zune_core::colorspace::ColorSpace;
 use zune_jpeg::zune_core::options::DecoderOptions;
 
+pub use capture::capture_time;
 pub use classifier::{ClassifierConfig, EfficientVitClassifier, EfficientVitVariant};
+pub use evaluation::{
+    ClassMetrics, EvalSample, EvaluationReport, ThresholdPoint, UNKNOWN_EVAL_LABEL,
+    evaluate_predictions, evaluate_samples, samples_from_folder,
+};
+pub use finetune::{
+    HeadTrainingConfig, HeadTrainingReport, TRAINING_REPORT_FILE_NAME, train_personal_head,
+};
+pub use megadetector::{
+    ANIMAL_CATEGORY, Detection, MEGADETECTOR_DEFAULT_THRESHOLD, MEGADETECTOR_FORMAT_VERSION,
+    MegaDetectorImport, export_megadetector_json, import_megadetector_json,
+};
+pub use records::{
+    DEFAULT_VISIT_GAP, FrameRecord, RECORD_COLUMNS, RECORD_SCHEMA_VERSION, RecordFormat,
+    RecordOptions, frame_records, write_records,
+};
+pub use reference::{KnnConfig, ReferenceExample, ReferenceSet};
+pub use similarity::{cosine_similarity, rank_by_similarity};
+
+mod capture;
+mod evaluation;
+mod finetune;
+mod megadetector;
+mod records;
+mod reference;
+mod similarity;
 
 /// Classification decision for an image/crop.
 ///
@@ -52,6 +82,135 @@ pub enum Decision {
 pub struct Classification {
     pub decision: Decision,
     pub confidence: f32,
+    /// Who made the decision; omitted for plain model predictions.
+    #[serde(default, skip_serializing_if = "Provenance::is_model")]
+    pub provenance: Provenance,
+}
+
+/// Suffix older versions appended to manual labels instead of setting
+/// [`Provenance::source`].
+pub const LEGACY_MANUAL_SUFFIX: &str = " (manueel)";
+
+impl Classification {
+    /// Whether the user assigned this decision.
+    pub fn is_manual(&self) -> bool {
+        self.provenance.source == DecisionSource::Manual
+    }
+
+    /// Turns a label saved as `"<label> (manueel)"` by older versions into a
+    /// manual decision on `<label>`. Returns true when something changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use feeder_core::{Classification, Decision, DecisionSource, Provenance};
+    ///
+    /// let mut classification = Classification {
+    ///     decision: Decision::Label("koolmees (manueel)".into()),
+    ///     confidence: 1.0,
+    ///     provenance: Provenance::default(),
+    /// };
+    /// assert!(classification.migrate_legacy_manual());
+    /// assert_eq!(classification.decision, Decision::Label("koolmees".into()));
+    /// assert_eq!(classification.provenance.source, DecisionSource::Manual);
+    /// ```
+    pub fn migrate_legacy_manual(&mut self) -> bool {
+        let Decision::Label(name) = &self.decision else {
+            return false;
+        };
+        let Some(label) = name.strip_suffix(LEGACY_MANUAL_SUFFIX) else {
+            return false;
+        };
+        self.decision = Decision::Label(label.trim().to_string());
+        self.provenance.source = DecisionSource::Manual;
+        true
+    }
+}
+
+/// Where the decision for a frame came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DecisionSource {
+    /// Predicted by the classifier.
+    #[default]
+    Model,
+    /// Assigned by the user.
+    Manual,
+    /// Decided before classification, e.g. an empty frame from a
+    /// MegaDetector import.
+    Prefilter,
+}
+
+impl DecisionSource {
+    /// Value written to exports, e.g. the `source` record column.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DecisionSource::Model => "model",
+            DecisionSource::Manual => "manual",
+            DecisionSource::Prefilter => "prefilter",
+        }
+    }
+}
+
+/// Origin of a [`Classification`] and, for manual edits, what they replaced.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Provenance {
+    pub source: DecisionSource,
+    /// Model (or prefilter) decision that was in place before the first
+    /// manual edit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original: Option<OriginalDecision>,
+    /// Seconds since the Unix epoch of the last manual edit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_at: Option<u64>,
+}
+
+impl Provenance {
+    /// True for a model prediction without further details, which is what
+    /// a missing `provenance` field deserializes to.
+    pub fn is_model(&self) -> bool {
+        *self == Provenance::default()
+    }
+
+    /// Provenance for a manual edit of `previous`, keeping the decision
+    /// the model made before any earlier edits.
+    pub fn manual(previous: Option<&Classification>, edited_at: u64) -> Self {
+        let original = previous.and_then(|previous| {
+            if previous.is_manual() {
+                previous.provenance.original.clone()
+            } else {
+                Some(OriginalDecision {
+                    decision: previous.decision.clone(),
+                    confidence: previous.confidence,
+                    source: previous.provenance.source,
+                })
+            }
+        });
+        Provenance {
+            source: DecisionSource::Manual,
+            original,
+            edited_at: Some(edited_at),
+        }
+    }
+}
+
+/// Decision that a manual edit replaced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OriginalDecision {
+    pub decision: Decision,
+    pub confidence: f32,
+    #[serde(default)]
+    pub source: DecisionSource,
+}
+
+/// Probability the model gave one of its labels.
+///
+/// [`ImageInfo::top_k`] keeps the best few per frame so exports can show
+/// the runner-up species.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelScore {
+    pub label: String,
+    pub probability: f32,
 }
 
 /// Core image information gathered by the pipeline.
@@ -65,6 +224,25 @@ pub struct ImageInfo {
     pub present: bool,
     /// Optional classifier output with decision and confidence.
     pub classification: Option<Classification>,
+    /// Pooled backbone features captured during classification.
+    ///
+    /// Used by [`rank_by_similarity`] to find visually similar frames.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+    /// Model that produced `embedding`, as set in [`ClassifierConfig::model_id`].
+    ///
+    /// Embeddings of different models cannot be compared with each other.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_model: Option<String>,
+    /// Detector boxes, for example imported with [`import_megadetector_json`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub detections: Vec<Detection>,
+    /// Best model labels with their probabilities, highest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub top_k: Vec<LabelScore>,
+    /// Why the last classification of this frame failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 /// Options controlling how folder scanning behaves.
@@ -142,6 +320,11 @@ pub fn scan_folder_with(path: impl AsRef<Path>, opts: ScanOptions) -> Result<Vec
                 file: path.to_path_buf(),
                 present: false,
                 classification: None,
+                embedding: None,
+                embedding_model: None,
+                detections: Vec::new(),
+                top_k: Vec::new(),
+                error: None,
             });
         }
     }
@@ -151,6 +334,9 @@ pub fn scan_folder_with(path: impl AsRef<Path>, opts: ScanOptions) -> Result<Vec
 
 /// Export the provided rows to CSV with headers `file,present,species,confidence`.
 ///
+/// This is record schema 1; [`frame_records`] and [`write_records`] produce
+/// the richer, versioned schema.
+///
 /// # Errors
 ///
 /// Returns any I/O or serialization errors encountered while writing the CSV.
@@ -158,7 +344,7 @@ pub fn scan_folder_with(path: impl AsRef<Path>, opts: ScanOptions) -> Result<Vec
 /// # Examples
 ///
 /// ```no_run
-/// # use feeder_core::{ImageInfo, export_csv, Decision, Classification};
+/// # use feeder_core::{ImageInfo, export_csv, Decision, Classification, Provenance};
 /// # use std::path::PathBuf;
 /// let rows = vec![ImageInfo {
 ///     file: PathBuf::from("/tmp/frame.jpg"),
@@ -166,7 +352,13 @@ pub fn scan_folder_with(path: impl AsRef<Path>, opts: ScanOptions) -> Result<Vec
 ///     classification: Some(Classification {
 ///         decision: Decision::Label("koolmees".into()),
 ///         confidence: 0.92,
+///         provenance: Provenance::default(),
 ///     }),
+///     embedding: None,
+///     embedding_model: None,
+///     detections: Vec::new(),
+///     top_k: Vec::new(),
+///     error: None,
 /// }];
 /// export_csv(&rows, "/tmp/results.csv")?;
 /// # Ok::<_, anyhow::Error>(())
@@ -181,6 +373,7 @@ pub fn export_csv(rows: &[ImageInfo], path: impl AsRef<Path>) -> Result<()> {
                 Some(Classification {
                     decision,
                     confidence,
+                    ..
                 }) => {
                     let s = match decision {
                         Decision::Unknown => Some("Unknown".to_string()),
@@ -211,6 +404,123 @@ pub fn export_csv(rows: &[ImageInfo], path: impl AsRef<Path>) -> Result<()> {
     Ok(())
 }
 
+/// Label row read back by [`import_csv`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvLabel {
+    /// Path as written in the CSV; absolute or relative to the scan folder.
+    pub file: PathBuf,
+    /// `present` column, when the CSV has one and the value is a boolean.
+    pub present: Option<bool>,
+    /// Species, or `None` when the cell is empty.
+    pub species: Option<String>,
+    pub confidence: Option<f32>,
+}
+
+/// Result of [`match_csv_labels`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsvMatch {
+    /// Index into the scanned rows with the label that belongs to it.
+    pub matched: Vec<(usize, CsvLabel)>,
+    /// CSV rows that did not correspond to any scanned file.
+    pub unmatched: Vec<CsvLabel>,
+}
+
+/// Read labels from a CSV written by [`export_csv`] or edited by hand.
+///
+/// Only a `file` column is required; `present`, `species` (or `label`, or
+/// `label_display` in a [`write_records`] CSV) and `confidence` are
+/// optional. Header names are case-insensitive and the column order does not
+/// matter, so spreadsheets can be re-saved freely.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read or has no `file` column.
+///
+/// # Examples
+///
+/// ```no_run
+/// let labels = feeder_core::import_csv("/tmp/results.csv")?;
+/// println!("{} rows", labels.len());
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+pub fn import_csv(path: impl AsRef<Path>) -> Result<Vec<CsvLabel>> {
+    let path = path.as_ref();
+    let mut rdr = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(path)
+        .with_context(|| format!("CSV niet te openen: {}", path.display()))?;
+    let headers = rdr.headers()?.clone();
+    let column = |names: &[&str]| {
+        headers.iter().position(|header| {
+            let header = strip_label_bom(header).trim();
+            names.iter().any(|name| header.eq_ignore_ascii_case(name))
+        })
+    };
+    let Some(file_col) = column(&["file", "path"]) else {
+        anyhow::bail!("Kolom 'file' ontbreekt in {}", path.display());
+    };
+    let present_col = column(&["present"]);
+    let species_col = column(&["species", "label", "label_display"]);
+    let confidence_col = column(&["confidence"]);
+
+    let mut labels = Vec::new();
+    for record in rdr.records() {
+        let record = record?;
+        let cell = |col: Option<usize>| {
+            col.and_then(|idx| record.get(idx))
+                .filter(|value| !value.is_empty())
+        };
+        let Some(file) = cell(Some(file_col)) else {
+            continue;
+        };
+        labels.push(CsvLabel {
+            file: PathBuf::from(file),
+            present: cell(present_col).and_then(|value| {
+                match value.to_ascii_lowercase().as_str() {
+                    "true" | "1" | "yes" | "ja" => Some(true),
+                    "false" | "0" | "no" | "nee" => Some(false),
+                    _ => None,
+                }
+            }),
+            species: cell(species_col).map(str::to_string),
+            confidence: cell(confidence_col).and_then(|value| value.parse().ok()),
+        });
+    }
+    Ok(labels)
+}
+
+/// Pair imported labels with scanned rows by absolute path, or by path
+/// relative to `root`.
+///
+/// Windows separators in relative paths are accepted on every platform, so
+/// a spreadsheet edited on another machine still matches.
+pub fn match_csv_labels(rows: &[ImageInfo], root: &Path, labels: Vec<CsvLabel>) -> CsvMatch {
+    let by_path: std::collections::HashMap<&Path, usize> = rows
+        .iter()
+        .enumerate()
+        .map(|(idx, info)| (info.file.as_path(), idx))
+        .collect();
+    let mut result = CsvMatch::default();
+    for label in labels {
+        let raw = label.file.to_string_lossy().replace('\\', "/");
+        let candidate = Path::new(&raw);
+        let resolved = if candidate.is_absolute() {
+            candidate.to_path_buf()
+        } else {
+            root.join(candidate)
+        };
+        match by_path
+            .get(resolved.as_path())
+            .or_else(|| by_path.get(label.file.as_path()))
+        {
+            Some(&idx) => result.matched.push((idx, label)),
+            None => result.unmatched.push(label),
+        }
+    }
+    result
+}
+
 /// Returns true when the file extension is supported by the classifier.
 fn is_supported_image(path: &Path) -> bool {
     match path.extension().and_then(|s| s.to_str()) {
@@ -343,19 +653,19 @@ fn normalize_channel(value: u8, mean: f32, std: f32) -> f32 {
 /// EfficientViT classifier implementation and configuration helpers.
 mod classifier {
     use super::{
-        Classification, Decision, ImageInfo, canonical_label_for_match, load_image_tensor_data,
-        strip_label_bom,
+        Classification, Decision, ImageInfo, KnnConfig, LabelScore, Provenance, ReferenceSet,
+        canonical_label_for_match, load_image_tensor_data, strip_label_bom,
     };
     use anyhow::{Context, Result};
     use candle_core::{D, DType, Device, Tensor};
-    use candle_nn::{self as nn, Func, Module, VarBuilder};
+    use candle_nn::{self as nn, BatchNorm, Func, Linear, Module, VarBuilder};
     use candle_transformers::models::efficientvit::{
         self as efficientvit_model, Config as EfficientVitConfig,
     };
     use rayon::prelude::*;
     use std::fs::{self, OpenOptions};
     use std::io::Write;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::sync::{Mutex, OnceLock, mpsc};
     use std::thread;
     use std::time::Instant;
@@ -395,6 +705,9 @@ mod classifier {
 
     const PIPELINE_QUEUE_DEPTH: usize = 2;
 
+    /// Number of labels kept in [`ImageInfo::top_k`].
+    const TOP_K_LABELS: usize = 3;
+
     struct BatchSpec {
         start: usize,
         files: Vec<PathBuf>,
@@ -431,6 +744,16 @@ mod classifier {
                 Self::M5 => EfficientVitConfig::m5(),
             }
         }
+
+        /// Returns the width of the pooled feature vector that feeds the head.
+        pub fn feature_dim(&self) -> usize {
+            match self {
+                Self::M0 | Self::M1 => 192,
+                Self::M2 => 224,
+                Self::M3 => 320,
+                Self::M4 | Self::M5 => 384,
+            }
+        }
     }
 
     /// Configuration for the Candle-based EfficientViT classifier.
@@ -458,6 +781,18 @@ mod classifier {
         pub background_labels: Vec<String>,
         /// Number of images to classify per batch.
         pub batch_size: usize,
+        /// Optional few-shot references classified alongside the model head.
+        ///
+        /// A nearest-neighbour match wins when it names a label the head does
+        /// not know, or when it is more confident than the head.
+        pub references: Option<ReferenceSet>,
+        /// Nearest-neighbour settings used together with `references`.
+        pub knn: KnnConfig,
+        /// Identifies the model, for example its installed version.
+        ///
+        /// Recorded with every embedding; `references` recorded for another
+        /// model are ignored.
+        pub model_id: Option<String>,
     }
 
     impl Default for ClassifierConfig {
@@ -472,17 +807,66 @@ mod classifier {
                 std: [0.229, 0.224, 0.225],
                 background_labels: vec!["Achtergrond".to_string()],
                 batch_size: 8,
+                references: None,
+                knn: KnnConfig::default(),
+                model_id: None,
             }
         }
     }
 
+    /// Reads the primary (first column) label names from a labels file.
+    pub(crate) fn read_label_names(path: &Path) -> Result<Vec<String>> {
+        let labels_raw = fs::read_to_string(path).context("labels niet te lezen")?;
+        let mut labels: Vec<String> = labels_raw
+            .lines()
+            .filter_map(|line| {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    return None;
+                }
+                let primary = trimmed
+                    .split_once(',')
+                    .map(|(first, _)| first.trim())
+                    .unwrap_or(trimmed)
+                    .trim_end_matches(',')
+                    .trim();
+                let display = strip_label_bom(primary).trim();
+                if display.is_empty() {
+                    None
+                } else {
+                    Some(display.to_string())
+                }
+            })
+            .collect();
+        if labels.is_empty() {
+            anyhow::bail!("labels-bestand bevat geen labels");
+        }
+        labels.dedup();
+        Ok(labels)
+    }
+
+    /// Whether `refs` were embedded by the model that `cfg` loads.
+    fn refs_match_model(refs: &ReferenceSet, cfg: &ClassifierConfig) -> bool {
+        if refs.model == cfg.model_id {
+            return true;
+        }
+        tracing::warn!(
+            "Referenties van model {} genegeerd; actief model is {}",
+            refs.model.as_deref().unwrap_or("onbekend"),
+            cfg.model_id.as_deref().unwrap_or("onbekend")
+        );
+        false
+    }
+
     /// High-level wrapper around the EfficientViT model used to classify images.
     ///
     /// This struct owns the loaded model, label list, and normalization values.
     /// Call [`EfficientVitClassifier::classify_with_progress`] to mutate
     /// [`ImageInfo`] entries with predictions.
     pub struct EfficientVitClassifier {
-        model: Func<'static>,
+        backbone: Func<'static>,
+        head_norm: BatchNorm,
+        head_linear: Linear,
         device: Device,
         labels: Vec<String>,
         input_size: u32,
@@ -491,6 +875,9 @@ mod classifier {
         std: [f32; 3],
         background_labels: Vec<String>,
         batch_size: usize,
+        references: Option<ReferenceSet>,
+        knn: KnnConfig,
+        model_id: Option<String>,
     }
 
     impl EfficientVitClassifier {
@@ -514,33 +901,7 @@ mod classifier {
                 );
             }
 
-            let labels_raw =
-                fs::read_to_string(&cfg.labels_path).context("labels niet te lezen")?;
-            let mut labels: Vec<String> = labels_raw
-                .lines()
-                .filter_map(|line| {
-                    let trimmed = line.trim();
-                    if trimmed.is_empty() {
-                        return None;
-                    }
-                    let primary = trimmed
-                        .split_once(',')
-                        .map(|(first, _)| first.trim())
-                        .unwrap_or(trimmed)
-                        .trim_end_matches(',')
-                        .trim();
-                    let display = strip_label_bom(primary).trim();
-                    if display.is_empty() {
-                        None
-                    } else {
-                        Some(display.to_string())
-                    }
-                })
-                .collect();
-            if labels.is_empty() {
-                anyhow::bail!("labels-bestand bevat geen labels");
-            }
-            labels.dedup();
+            let labels = read_label_names(&cfg.labels_path)?;
 
             let device = Device::Cpu;
             let vb = unsafe {
@@ -551,10 +912,18 @@ mod classifier {
                 )?
             };
             let vit_config = cfg.variant.config();
-            let model = efficientvit_model::efficientvit(&vit_config, labels.len(), vb)?;
+            let backbone =
+                efficientvit_model::efficientvit_no_final_layer(&vit_config, vb.clone())?;
+            // The head is built separately so the pooled features stay available
+            // as embeddings for similarity search.
+            let feature_dim = cfg.variant.feature_dim();
+            let head_norm = nn::batch_norm(feature_dim, 1e-6, vb.pp("head").pp("bn"))?;
+            let head_linear = nn::linear(feature_dim, labels.len(), vb.pp("head").pp("linear"))?;
 
             Ok(Self {
-                model,
+                backbone,
+                head_norm,
+                head_linear,
                 device,
                 labels,
                 input_size: cfg.input_size,
@@ -567,6 +936,12 @@ mod classifier {
                     .map(|s| canonical_label_for_match(s))
                     .collect(),
                 batch_size: cfg.batch_size.max(1),
+                references: cfg
+                    .references
+                    .clone()
+                    .filter(|refs| !refs.is_empty() && refs_match_model(refs, cfg)),
+                knn: cfg.knn,
+                model_id: cfg.model_id.clone(),
             })
         }
 
@@ -590,6 +965,65 @@ mod classifier {
             &self,
             rows: &mut [ImageInfo],
             batch_size: usize,
+            progress: F,
+        ) -> Result<()>
+        where
+            F: FnMut(usize, usize),
+        {
+            self.run_batches(rows, batch_size, PassMode::Classify, progress)
+        }
+
+        /// Classifies only the rows without a classification yet, such as the
+        /// animal frames left by [`import_megadetector_json`].
+        ///
+        /// The callback receives `(done, total)` counted over those rows.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if tensor creation or model evaluation fails.
+        pub fn classify_pending_with_progress<F>(
+            &self,
+            rows: &mut [ImageInfo],
+            progress: F,
+        ) -> Result<()>
+        where
+            F: FnMut(usize, usize),
+        {
+            let pending: Vec<usize> = rows
+                .iter()
+                .enumerate()
+                .filter(|(_, info)| info.classification.is_none())
+                .map(|(idx, _)| idx)
+                .collect();
+            let mut subset: Vec<ImageInfo> = pending.iter().map(|&idx| rows[idx].clone()).collect();
+            self.classify_with_progress(&mut subset, progress)?;
+            for (idx, info) in pending.into_iter().zip(subset) {
+                rows[idx] = info;
+            }
+            Ok(())
+        }
+
+        /// Computes embeddings for the provided rows without touching their
+        /// classification.
+        ///
+        /// Rows that fail to load keep their previous state and simply end up
+        /// without an embedding.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error if tensor creation or model evaluation fails.
+        pub fn embed_with_progress<F>(&self, rows: &mut [ImageInfo], progress: F) -> Result<()>
+        where
+            F: FnMut(usize, usize),
+        {
+            self.run_batches(rows, self.batch_size, PassMode::EmbedOnly, progress)
+        }
+
+        fn run_batches<F>(
+            &self,
+            rows: &mut [ImageInfo],
+            batch_size: usize,
+            mode: PassMode,
             mut progress: F,
         ) -> Result<()>
         where
@@ -647,8 +1081,7 @@ mod classifier {
                                     path.display()
                                 );
                                 if let Some(info) = chunk.get_mut(idx) {
-                                    info.present = false;
-                                    info.classification = None;
+                                    mode.mark_failed(info, format!("{err}"));
                                 }
                             }
                         },
@@ -658,8 +1091,7 @@ mod classifier {
                                 path.display()
                             );
                             if let Some(info) = chunk.get_mut(idx) {
-                                info.present = false;
-                                info.classification = None;
+                                mode.mark_failed(info, format!("{err}"));
                             }
                         }
                     }
@@ -681,9 +1113,15 @@ mod classifier {
                 let forward_start = logger.map(|_| Instant::now());
                 let views = tensors.iter().collect::<Vec<_>>();
                 let batch = Tensor::stack(&views, 0)?;
-                let logits = self.model.forward(&batch)?;
-                let probs = nn::ops::softmax(&logits, D::Minus1)?;
-                let probs_rows = probs.to_vec2::<f32>()?;
+                let features = self.backbone.forward(&batch)?;
+                let feature_rows = features.to_vec2::<f32>()?;
+                let probs_rows = match mode {
+                    PassMode::Classify => {
+                        let logits = self.head_logits(&features)?;
+                        nn::ops::softmax(&logits, D::Minus1)?.to_vec2::<f32>()?
+                    }
+                    PassMode::EmbedOnly => Vec::new(),
+                };
                 let forward_ms = forward_start.map(|start| start.elapsed().as_millis());
 
                 if let Some(logger) = logger {
@@ -701,24 +1139,32 @@ mod classifier {
                     ));
                 }
 
-                for (row_probs, idx_in_chunk) in
-                    probs_rows.into_iter().zip(tensor_order.into_iter())
-                {
+                let mut probs_rows = probs_rows.into_iter();
+                for (embedding, idx_in_chunk) in feature_rows.into_iter().zip(tensor_order) {
+                    let row_probs = probs_rows.next();
                     if let Some(info) = chunk.get_mut(idx_in_chunk) {
-                        match self.build_result_from_probs(&row_probs) {
-                            Ok(result) => {
-                                info.present = result.present;
-                                info.classification = result.classification;
-                            }
-                            Err(err) => {
-                                tracing::warn!(
-                                    "Resultaat opbouwen mislukt voor {}: {err}",
-                                    info.file.display()
-                                );
-                                info.present = false;
-                                info.classification = None;
+                        if let Some(row_probs) = row_probs {
+                            match self.build_result_from_probs(&row_probs, &embedding) {
+                                Ok(result) => {
+                                    info.present = result.present;
+                                    info.classification = result.classification;
+                                    info.top_k = result.top_k;
+                                    info.error = None;
+                                }
+                                Err(err) => {
+                                    tracing::warn!(
+                                        "Resultaat opbouwen mislukt voor {}: {err}",
+                                        info.file.display()
+                                    );
+                                    info.present = false;
+                                    info.classification = None;
+                                    info.top_k.clear();
+                                    info.error = Some(format!("{err}"));
+                                }
                             }
                         }
+                        info.embedding = Some(embedding);
+                        info.embedding_model = self.model_id.clone();
                     }
                 }
 
@@ -759,6 +1205,32 @@ mod classifier {
             }
         }
 
+        /// Applies the classification head to pooled backbone features.
+        fn head_logits(&self, features: &Tensor) -> Result<Tensor> {
+            Ok(features
+                .apply_t(&self.head_norm, false)?
+                .apply(&self.head_linear)?)
+        }
+
+        /// Returns the nearest-neighbour label when it should replace the head.
+        fn knn_override(&self, embedding: &[f32], head_prob: f32) -> Option<(String, f32)> {
+            let references = self.references.as_ref()?;
+            let result = references.classify(embedding, &self.knn);
+            let Decision::Label(label) = result.decision else {
+                return None;
+            };
+            let canonical = canonical_label_for_match(&label);
+            let known_to_head = self
+                .labels
+                .iter()
+                .any(|known| canonical_label_for_match(known) == canonical);
+            if !known_to_head || result.confidence > head_prob {
+                Some((label, result.confidence))
+            } else {
+                None
+            }
+        }
+
         fn tensor_from_data(&self, data: Vec<f32>) -> Result<Tensor> {
             Ok(Tensor::from_vec(
                 data,
@@ -767,7 +1239,11 @@ mod classifier {
             )?)
         }
 
-        fn build_result_from_probs(&self, probs: &[f32]) -> Result<ClassificationResult> {
+        fn build_result_from_probs(
+            &self,
+            probs: &[f32],
+            embedding: &[f32],
+        ) -> Result<ClassificationResult> {
             if probs.is_empty() {
                 anyhow::bail!("lege logits");
             }
@@ -776,11 +1252,16 @@ mod classifier {
                 .enumerate()
                 .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
                 .unwrap();
-            let label = self
+            let mut label = self
                 .labels
                 .get(best_idx)
                 .cloned()
                 .unwrap_or_else(|| format!("class_{best_idx}"));
+            let mut best_prob = best_prob;
+            if let Some((knn_label, knn_confidence)) = self.knn_override(embedding, best_prob) {
+                label = knn_label;
+                best_prob = knn_confidence;
+            }
             let label_canonical = canonical_label_for_match(&label);
             let is_background = self
                 .background_labels
@@ -792,12 +1273,28 @@ mod classifier {
             } else {
                 Decision::Label(label)
             };
+            let mut ranked: Vec<(usize, f32)> = probs.iter().copied().enumerate().collect();
+            ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+            let top_k = ranked
+                .into_iter()
+                .take(TOP_K_LABELS)
+                .map(|(idx, probability)| LabelScore {
+                    label: self
+                        .labels
+                        .get(idx)
+                        .cloned()
+                        .unwrap_or_else(|| format!("class_{idx}")),
+                    probability,
+                })
+                .collect();
             Ok(ClassificationResult {
                 present,
                 classification: Some(Classification {
                     decision,
                     confidence: best_prob,
+                    provenance: Provenance::default(),
                 }),
+                top_k,
             })
         }
     }
@@ -805,6 +1302,124 @@ mod classifier {
     struct ClassificationResult {
         present: bool,
         classification: Option<Classification>,
+        top_k: Vec<LabelScore>,
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::evaluation::scored_prediction;
+        use crate::{EvalSample, evaluate_predictions};
+
+        /// Classifier with an identity backbone and head, so tests can feed
+        /// probabilities straight into `build_result_from_probs`.
+        fn classifier(labels: &[&str], background: &[&str]) -> EfficientVitClassifier {
+            let device = Device::Cpu;
+            let n = labels.len();
+            let ones = Tensor::ones(n, DType::F32, &device).unwrap();
+            let zeros = Tensor::zeros(n, DType::F32, &device).unwrap();
+            EfficientVitClassifier {
+                backbone: Func::new(|xs| Ok(xs.clone())),
+                head_norm: BatchNorm::new(
+                    n,
+                    zeros.clone(),
+                    ones.clone(),
+                    ones,
+                    zeros.clone(),
+                    1e-6,
+                )
+                .unwrap(),
+                head_linear: Linear::new(Tensor::eye(n, DType::F32, &device).unwrap(), Some(zeros)),
+                device,
+                labels: labels.iter().map(|label| label.to_string()).collect(),
+                input_size: 224,
+                presence_threshold: 0.5,
+                mean: [0.0; 3],
+                std: [1.0; 3],
+                background_labels: background
+                    .iter()
+                    .map(|label| canonical_label_for_match(label))
+                    .collect(),
+                batch_size: 1,
+                references: None,
+                knn: KnnConfig::default(),
+                model_id: None,
+            }
+        }
+
+        #[test]
+        fn background_predictions_are_scored_as_background() -> Result<()> {
+            let classifier = classifier(&["Achtergrond", "Koolmees"], &["Achtergrond"]);
+            let samples = [
+                EvalSample {
+                    file: "leeg.jpg".into(),
+                    truth: "Achtergrond".into(),
+                },
+                EvalSample {
+                    file: "mees.jpg".into(),
+                    truth: "Koolmees".into(),
+                },
+            ];
+            let predictions: Vec<Option<Classification>> = [[0.9, 0.1], [0.2, 0.8]]
+                .iter()
+                .map(|probs| {
+                    let result = classifier.build_result_from_probs(probs, &[])?;
+                    Ok(scored_prediction(ImageInfo {
+                        file: PathBuf::new(),
+                        present: result.present,
+                        classification: result.classification,
+                        embedding: None,
+                        embedding_model: None,
+                        detections: Vec::new(),
+                        top_k: result.top_k,
+                        error: None,
+                    }))
+                })
+                .collect::<Result<_>>()?;
+            assert_eq!(
+                predictions[0].as_ref().map(|c| &c.decision),
+                Some(&Decision::Label("Achtergrond".into()))
+            );
+
+            let report = evaluate_predictions(&samples, &predictions, &["Achtergrond".into()]);
+            assert_eq!(report.correct, 2);
+            assert_eq!(report.accuracy, 1.0);
+            Ok(())
+        }
+
+        #[test]
+        fn references_of_another_model_are_ignored() {
+            let mut refs = ReferenceSet::for_model("1.2.0");
+            refs.add("Eekhoorn", vec![1.0, 0.0], None);
+            let mut cfg = ClassifierConfig {
+                model_id: Some("1.3.0".into()),
+                ..Default::default()
+            };
+            assert!(!refs_match_model(&refs, &cfg));
+            cfg.model_id = Some("1.2.0".into());
+            assert!(refs_match_model(&refs, &cfg));
+        }
+    }
+
+    /// Selects what a pass over the rows should produce.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum PassMode {
+        Classify,
+        EmbedOnly,
+    }
+
+    impl PassMode {
+        /// Resets a row whose image could not be turned into a tensor.
+        fn mark_failed(self, info: &mut ImageInfo, error: String) {
+            info.embedding = None;
+            info.embedding_model = None;
+            if self == PassMode::Classify {
+                info.present = false;
+                info.classification = None;
+                info.top_k.clear();
+                info.error = Some(error);
+            }
+        }
     }
 }
 
@@ -823,6 +1438,11 @@ mod tests {
                 file: PathBuf::from("a.jpg"),
                 present: false,
                 classification: None,
+                embedding: None,
+                embedding_model: None,
+                detections: Vec::new(),
+                top_k: Vec::new(),
+                error: None,
             },
             ImageInfo {
                 file: PathBuf::from("b.jpg"),
@@ -830,7 +1450,13 @@ mod tests {
                 classification: Some(Classification {
                     decision: Decision::Unknown,
                     confidence: 0.42,
+                    provenance: Provenance::default(),
                 }),
+                embedding: None,
+                embedding_model: None,
+                detections: Vec::new(),
+                top_k: Vec::new(),
+                error: None,
             },
             ImageInfo {
                 file: PathBuf::from("c.jpg"),
@@ -838,7 +1464,13 @@ mod tests {
                 classification: Some(Classification {
                     decision: Decision::Label("Sparrow".into()),
                     confidence: 0.91,
+                    provenance: Provenance::default(),
                 }),
+                embedding: None,
+                embedding_model: None,
+                detections: Vec::new(),
+                top_k: Vec::new(),
+                error: None,
             },
         ];
 
@@ -874,6 +1506,84 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn import_csv_reads_export_and_matches_relative_paths() -> Result<()> {
+        let dir = tempdir()?;
+        let root = dir.path().join("scan");
+        let row = |name: &str| ImageInfo {
+            file: root.join(name),
+            present: false,
+            classification: None,
+            embedding: None,
+            embedding_model: None,
+            detections: Vec::new(),
+            top_k: Vec::new(),
+            error: None,
+        };
+        let mut rows = vec![row("a.jpg"), row("sub/b.jpg"), row("c.jpg")];
+        rows[0].present = true;
+        rows[0].classification = Some(Classification {
+            decision: Decision::Label("Koolmees".into()),
+            confidence: 0.8,
+            provenance: Provenance::default(),
+        });
+        let exported = dir.path().join("export.csv");
+        export_csv(&rows, &exported)?;
+        let labels = import_csv(&exported)?;
+        assert_eq!(labels.len(), 3);
+        assert_eq!(labels[0].species.as_deref(), Some("Koolmees"));
+        assert_eq!(labels[0].present, Some(true));
+        assert_eq!(labels[0].confidence, Some(0.8));
+        assert_eq!(labels[1].species, None);
+        assert_eq!(match_csv_labels(&rows, &root, labels).matched.len(), 3);
+
+        let edited = dir.path().join("edited.csv");
+        fs::write(
+            &edited,
+            "\u{feff}Label,File\nmerel,sub\\b.jpg\n,missing.jpg\n",
+        )?;
+        let result = match_csv_labels(&rows, &root, import_csv(&edited)?);
+        assert_eq!(result.matched.len(), 1);
+        assert_eq!(result.matched[0].0, 1);
+        assert_eq!(result.matched[0].1.species.as_deref(), Some("merel"));
+        assert_eq!(result.unmatched[0].file, PathBuf::from("missing.jpg"));
+        Ok(())
+    }
+
+    #[test]
+    fn manual_provenance_keeps_first_model_prediction() -> Result<()> {
+        let model: Classification =
+            serde_json::from_str(r#"{"decision":{"Label":"Pimpelmees"},"confidence":0.64}"#)?;
+        assert!(model.provenance.is_model());
+        assert!(!serde_json::to_string(&model)?.contains("provenance"));
+
+        let first = Classification {
+            decision: Decision::Label("koolmees".into()),
+            confidence: 1.0,
+            provenance: Provenance::manual(Some(&model), 100),
+        };
+        let second = Provenance::manual(Some(&first), 200);
+        assert!(first.is_manual());
+        assert_eq!(second.edited_at, Some(200));
+        assert_eq!(
+            second.original,
+            Some(OriginalDecision {
+                decision: Decision::Label("Pimpelmees".into()),
+                confidence: 0.64,
+                source: DecisionSource::Model,
+            })
+        );
+
+        let mut legacy: Classification =
+            serde_json::from_str(r#"{"decision":{"Label":"merel (manueel)"},"confidence":1.0}"#)?;
+        assert!(legacy.migrate_legacy_manual());
+        assert!(!legacy.migrate_legacy_manual());
+        assert_eq!(legacy.decision, Decision::Label("merel".into()));
+        let saved = serde_json::to_string(&legacy)?;
+        assert!(saved.contains(r#""provenance":{"source":"manual"}"#));
+        Ok(())
+    }
+
     #[test]
     fn scan_folder_empty_returns_empty() -> Result<()> {
         let dir = tempdir()?;
@@ -0,0 +1,578 @@
+//! MegaDetector batch-output JSON import and export.
+//!
+//! MegaDetector and AddaxAI exchange results as one JSON file per folder with
+//! relative image paths, normalized `[x, y, width, height]` boxes and optional
+//! per-box classifications. Importing such a file prefills [`ImageInfo`] rows
+//! so only frames with an animal still need the Feedie classifier.
+
+use crate::{
+    Classification, Decision, DecisionSource, ImageInfo, Provenance, canonical_label_for_match,
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// MegaDetector format version written by [`export_megadetector_json`].
+pub const MEGADETECTOR_FORMAT_VERSION: &str = "1.4";
+
+/// Detection confidence MegaDetector users typically treat as "something is
+/// there".
+pub const MEGADETECTOR_DEFAULT_THRESHOLD: f32 = 0.2;
+
+/// Detection category for animals; the other standard categories are
+/// `person` and `vehicle`.
+pub const ANIMAL_CATEGORY: &str = "animal";
+
+const FAILURE_NOT_CLASSIFIED: &str = "Not classified by Feedie";
+
+/// Bounding box reported by a detector.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Detection {
+    /// Category name such as `animal`, `person` or `vehicle`.
+    pub category: String,
+    /// Detector confidence between 0 and 1.
+    pub confidence: f32,
+    /// Normalized `[x_min, y_min, width, height]`, origin top-left.
+    pub bbox: [f32; 4],
+}
+
+impl Detection {
+    /// Returns true when the box is an animal.
+    pub fn is_animal(&self) -> bool {
+        self.category == ANIMAL_CATEGORY
+    }
+}
+
+/// Summary of [`import_megadetector_json`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MegaDetectorImport {
+    /// Rows that had an entry in the file.
+    pub matched: usize,
+    /// Matched rows with an animal box; these still need classification.
+    pub animal: usize,
+    /// Matched rows without an animal box, now decided as empty.
+    pub empty: usize,
+    /// Matched rows left untouched because they carry a manual label.
+    pub manual: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BatchOutput {
+    #[serde(default)]
+    info: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    detection_categories: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    classification_categories: BTreeMap<String, String>,
+    images: Vec<BatchImage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BatchImage {
+    file: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    failure: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_detection_conf: Option<f32>,
+    #[serde(default)]
+    detections: Option<Vec<BatchDetection>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BatchDetection {
+    category: String,
+    conf: f32,
+    bbox: [f32; 4],
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    classifications: Vec<(String, f32)>,
+}
+
+fn standard_detection_categories() -> BTreeMap<String, String> {
+    [("1", ANIMAL_CATEGORY), ("2", "person"), ("3", "vehicle")]
+        .into_iter()
+        .map(|(id, name)| (id.to_string(), name.to_string()))
+        .collect()
+}
+
+/// Image paths in batch files are relative to the scanned folder and use
+/// forward slashes, also when written on Windows.
+fn relative_file(root: &Path, file: &Path) -> String {
+    let rel = file.strip_prefix(root).unwrap_or(file);
+    rel.components()
+        .map(|part| part.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn resolve_file(root: &Path, file: &str) -> PathBuf {
+    let file = file.replace('\\', "/");
+    let path = Path::new(&file);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        root.join(path)
+    }
+}
+
+/// Writes `rows` as a MegaDetector batch-output file.
+///
+/// Imported boxes are written back as-is, with the Feedie label attached to
+/// every animal box. Frames without boxes get a single whole-frame animal
+/// detection when Feedie found a species. Frames decided as empty
+/// ([`Decision::Unknown`] or one of `background_labels`) get a whole-frame
+/// box with confidence `1 - confidence` and no classification, so a frame
+/// Feedie was unsure about stays above a detection threshold while a sure
+/// one falls below it; a certain empty frame has no detections. This mirrors
+/// [`import_megadetector_json`]. Rows that were never classified are written
+/// as failures so downstream tools do not mistake them for empty frames.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be written.
+///
+/// # Examples
+///
+/// ```no_run
+/// # let rows: Vec<feeder_core::ImageInfo> = Vec::new();
+/// feeder_core::export_megadetector_json(
+///     &rows,
+///     "/data/camera",
+///     "/data/camera/md.json",
+///     &["achtergrond".to_string()],
+/// )?;
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+pub fn export_megadetector_json(
+    rows: &[ImageInfo],
+    root: impl AsRef<Path>,
+    path: impl AsRef<Path>,
+    background_labels: &[String],
+) -> Result<()> {
+    let root = root.as_ref();
+    let path = path.as_ref();
+    let backgrounds: Vec<String> = background_labels
+        .iter()
+        .map(|label| canonical_label_for_match(label))
+        .collect();
+    let is_species = |label: &str| !backgrounds.contains(&canonical_label_for_match(label));
+    let detection_categories = standard_detection_categories();
+    let mut category_ids: HashMap<String, String> = detection_categories
+        .iter()
+        .map(|(id, name)| (name.clone(), id.clone()))
+        .collect();
+    let mut labels: Vec<&str> = rows
+        .iter()
+        .filter_map(|info| match &info.classification {
+            Some(Classification {
+                decision: Decision::Label(label),
+                ..
+            }) if is_species(label) => Some(label.as_str()),
+            _ => None,
+        })
+        .collect();
+    labels.sort_unstable();
+    labels.dedup();
+    let classification_categories: BTreeMap<String, String> = labels
+        .iter()
+        .enumerate()
+        .map(|(idx, label)| (idx.to_string(), label.to_string()))
+        .collect();
+    let label_ids: HashMap<&str, String> = labels
+        .iter()
+        .enumerate()
+        .map(|(idx, label)| (*label, idx.to_string()))
+        .collect();
+
+    let mut images = Vec::with_capacity(rows.len());
+    for info in rows {
+        let file = relative_file(root, &info.file);
+        let mut empty_confidence = None;
+        let classification = match &info.classification {
+            None if info.detections.is_empty() => {
+                images.push(BatchImage {
+                    file,
+                    failure: Some(FAILURE_NOT_CLASSIFIED.to_string()),
+                    max_detection_conf: None,
+                    detections: None,
+                });
+                continue;
+            }
+            None => None,
+            Some(Classification {
+                decision: Decision::Label(label),
+                confidence,
+                ..
+            }) if is_species(label) => Some((label_ids[label.as_str()].clone(), *confidence)),
+            Some(Classification { confidence, .. }) => {
+                empty_confidence = Some(*confidence);
+                None
+            }
+        };
+        let detections: Vec<BatchDetection> = if info.detections.is_empty() {
+            let whole_frame = |conf: f32, classifications| BatchDetection {
+                category: category_ids[ANIMAL_CATEGORY].clone(),
+                conf,
+                bbox: [0.0, 0.0, 1.0, 1.0],
+                classifications,
+            };
+            match (&classification, empty_confidence) {
+                (Some((id, confidence)), _) => {
+                    vec![whole_frame(*confidence, vec![(id.clone(), *confidence)])]
+                }
+                (None, Some(confidence)) if confidence < 1.0 => {
+                    vec![whole_frame((1.0 - confidence).clamp(0.0, 1.0), Vec::new())]
+                }
+                _ => Vec::new(),
+            }
+        } else {
+            info.detections
+                .iter()
+                .map(|detection| {
+                    let next_id = (category_ids.len() + 1).to_string();
+                    let category = category_ids
+                        .entry(detection.category.clone())
+                        .or_insert(next_id)
+                        .clone();
+                    let classifications = match &classification {
+                        Some(found) if detection.is_animal() => vec![found.clone()],
+                        _ => Vec::new(),
+                    };
+                    BatchDetection {
+                        category,
+                        conf: detection.confidence,
+                        bbox: detection.bbox,
+                        classifications,
+                    }
+                })
+                .collect()
+        };
+        let max_detection_conf = detections
+            .iter()
+            .map(|detection| detection.conf)
+            .fold(0.0_f32, f32::max);
+        images.push(BatchImage {
+            file,
+            failure: None,
+            max_detection_conf: Some(max_detection_conf),
+            detections: Some(detections),
+        });
+    }
+
+    // Categories of imported boxes outside the standard three are kept.
+    let detection_categories: BTreeMap<String, String> = category_ids
+        .into_iter()
+        .map(|(name, id)| (id, name))
+        .collect();
+    let mut info = serde_json::Map::new();
+    info.insert(
+        "format_version".to_string(),
+        MEGADETECTOR_FORMAT_VERSION.into(),
+    );
+    info.insert("classifier".to_string(), "Feedie EfficientViT".into());
+    let output = BatchOutput {
+        info,
+        detection_categories,
+        classification_categories,
+        images,
+    };
+    let json = serde_json::to_string_pretty(&output)?;
+    fs::write(path, json).with_context(|| format!("Kon {} niet schrijven", path.display()))
+}
+
+/// Prefills `rows` from a MegaDetector batch-output file for folder `root`.
+///
+/// Boxes at or above `min_confidence` are stored in
+/// [`ImageInfo::detections`]. A frame without such an animal box is decided
+/// as empty ([`Decision::Unknown`] with confidence `1 - max_detection_conf`);
+/// a frame with one keeps `classification: None` so
+/// [`classify_pending_with_progress`](crate::EfficientVitClassifier::classify_pending_with_progress)
+/// only classifies those. Failed and unknown images are ignored, and rows
+/// with a manual label keep it.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be read or is not a batch-output
+/// file.
+///
+/// # Examples
+///
+/// ```no_run
+/// use feeder_core::{MEGADETECTOR_DEFAULT_THRESHOLD, import_megadetector_json, scan_folder};
+///
+/// let mut rows = scan_folder("/data/camera")?;
+/// let summary = import_megadetector_json(
+///     &mut rows,
+///     "/data/camera",
+///     "/data/camera/md.json",
+///     MEGADETECTOR_DEFAULT_THRESHOLD,
+/// )?;
+/// println!("{} frames left to classify", summary.animal);
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+pub fn import_megadetector_json(
+    rows: &mut [ImageInfo],
+    root: impl AsRef<Path>,
+    path: impl AsRef<Path>,
+    min_confidence: f32,
+) -> Result<MegaDetectorImport> {
+    let root = root.as_ref();
+    let path = path.as_ref();
+    let data = fs::read_to_string(path)
+        .with_context(|| format!("MegaDetector-bestand niet te lezen: {}", path.display()))?;
+    let output: BatchOutput = serde_json::from_str(&data)
+        .with_context(|| format!("Ongeldig MegaDetector-bestand: {}", path.display()))?;
+    let categories = if output.detection_categories.is_empty() {
+        standard_detection_categories()
+    } else {
+        output.detection_categories
+    };
+    let by_file: HashMap<PathBuf, &BatchImage> = output
+        .images
+        .iter()
+        .filter(|image| image.failure.is_none())
+        .map(|image| (resolve_file(root, &image.file), image))
+        .collect();
+
+    let mut summary = MegaDetectorImport::default();
+    for info in rows.iter_mut() {
+        let Some(image) = by_file.get(&info.file) else {
+            continue;
+        };
+        let detections: Vec<Detection> = image
+            .detections
+            .iter()
+            .flatten()
+            .filter(|detection| detection.conf >= min_confidence)
+            .map(|detection| Detection {
+                category: categories
+                    .get(&detection.category)
+                    .cloned()
+                    .unwrap_or_else(|| detection.category.clone()),
+                confidence: detection.conf,
+                bbox: detection.bbox,
+            })
+            .collect();
+        summary.matched += 1;
+        if info
+            .classification
+            .as_ref()
+            .is_some_and(Classification::is_manual)
+        {
+            summary.manual += 1;
+            continue;
+        }
+        info.present = false;
+        if detections.iter().any(Detection::is_animal) {
+            summary.animal += 1;
+            info.classification = None;
+        } else {
+            summary.empty += 1;
+            let max_conf = image.max_detection_conf.unwrap_or_else(|| {
+                image
+                    .detections
+                    .iter()
+                    .flatten()
+                    .map(|detection| detection.conf)
+                    .fold(0.0, f32::max)
+            });
+            info.classification = Some(Classification {
+                decision: Decision::Unknown,
+                confidence: (1.0 - max_conf).clamp(0.0, 1.0),
+                provenance: Provenance {
+                    source: DecisionSource::Prefilter,
+                    ..Provenance::default()
+                },
+            });
+        }
+        info.detections = detections;
+    }
+    tracing::info!(
+        "MegaDetector: {} beelden gekoppeld, {} met dier, {} leeg, {} handmatig behouden",
+        summary.matched,
+        summary.animal,
+        summary.empty,
+        summary.manual
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(root: &Path, name: &str) -> ImageInfo {
+        ImageInfo {
+            file: root.join(name),
+            present: false,
+            classification: None,
+            embedding: None,
+            embedding_model: None,
+            detections: Vec::new(),
+            top_k: Vec::new(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn import_prefills_empty_frames_and_keeps_animal_boxes() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let json = r#"{
+            "info": {"format_version": "1.4", "detector": "md_v5a.0.0.pt"},
+            "detection_categories": {"1": "animal", "2": "person", "3": "vehicle"},
+            "images": [
+                {"file": "sub\\a.jpg", "max_detection_conf": 0.93, "detections": [
+                    {"category": "1", "conf": 0.93, "bbox": [0.1, 0.2, 0.3, 0.4]},
+                    {"category": "1", "conf": 0.05, "bbox": [0.5, 0.5, 0.1, 0.1]}
+                ]},
+                {"file": "b.jpg", "max_detection_conf": 0.12, "detections": [
+                    {"category": "1", "conf": 0.12, "bbox": [0.0, 0.0, 0.2, 0.2]}
+                ]},
+                {"file": "c.jpg", "failure": "Failure image access", "detections": null}
+            ]
+        }"#;
+        let md_path = root.join("md.json");
+        fs::write(&md_path, json).unwrap();
+        let mut rows = vec![
+            row(root, "sub/a.jpg"),
+            row(root, "b.jpg"),
+            row(root, "c.jpg"),
+        ];
+
+        let summary = import_megadetector_json(&mut rows, root, &md_path, 0.2).unwrap();
+        assert_eq!(
+            summary,
+            MegaDetectorImport {
+                matched: 2,
+                animal: 1,
+                empty: 1,
+                manual: 0
+            }
+        );
+        assert_eq!(rows[0].classification, None);
+        assert_eq!(rows[0].detections.len(), 1);
+        assert_eq!(rows[0].detections[0].category, "animal");
+        assert_eq!(rows[0].detections[0].bbox, [0.1, 0.2, 0.3, 0.4]);
+        let empty = rows[1].classification.as_ref().unwrap();
+        assert_eq!(empty.decision, Decision::Unknown);
+        assert!((empty.confidence - 0.88).abs() < 1e-6);
+        assert!(rows[1].detections.is_empty());
+        assert_eq!(rows[2].classification, None);
+    }
+
+    #[test]
+    fn import_keeps_manual_labels() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let json = r#"{"images": [
+            {"file": "a.jpg", "max_detection_conf": 0.0, "detections": []},
+            {"file": "b.jpg", "max_detection_conf": 0.0, "detections": []}
+        ]}"#;
+        let md_path = root.join("md.json");
+        fs::write(&md_path, json).unwrap();
+        let manual = Classification {
+            decision: Decision::Label("koolmees".to_string()),
+            confidence: 1.0,
+            provenance: Provenance::manual(None, 0),
+        };
+        let mut labelled = row(root, "a.jpg");
+        labelled.present = true;
+        labelled.classification = Some(manual.clone());
+        let mut rows = vec![labelled, row(root, "b.jpg")];
+
+        let summary = import_megadetector_json(&mut rows, root, &md_path, 0.2).unwrap();
+        assert_eq!((summary.matched, summary.empty, summary.manual), (2, 1, 1));
+        assert_eq!(rows[0].classification, Some(manual));
+        assert!(rows[0].present);
+        assert_eq!(
+            rows[1].classification.as_ref().map(|c| &c.decision),
+            Some(&Decision::Unknown)
+        );
+    }
+
+    #[test]
+    fn export_writes_categories_boxes_and_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let label = |name: &str, confidence: f32| {
+            Some(Classification {
+                decision: Decision::Label(name.to_string()),
+                confidence,
+                provenance: Provenance::default(),
+            })
+        };
+        let mut boxed = row(root, "sub/a.jpg");
+        boxed.classification = label("merel", 0.81);
+        boxed.detections = vec![Detection {
+            category: ANIMAL_CATEGORY.to_string(),
+            confidence: 0.93,
+            bbox: [0.1, 0.2, 0.3, 0.4],
+        }];
+        let mut whole = row(root, "b.jpg");
+        whole.present = true;
+        whole.classification = label("koolmees", 0.9);
+        let mut empty = row(root, "c.jpg");
+        empty.classification = Some(Classification {
+            decision: Decision::Unknown,
+            confidence: 0.9,
+            provenance: Provenance::default(),
+        });
+        let pending = row(root, "d.jpg");
+        let mut unsure = row(root, "e.jpg");
+        unsure.classification = Some(Classification {
+            decision: Decision::Unknown,
+            confidence: 0.6,
+            provenance: Provenance::default(),
+        });
+        let mut background = row(root, "f.jpg");
+        background.classification = label("Achtergrond", 1.0);
+        let md_path = root.join("md.json");
+        export_megadetector_json(
+            &[boxed, whole, empty, pending, unsure, background],
+            root,
+            &md_path,
+            &["achtergrond".to_string()],
+        )
+        .unwrap();
+
+        let output: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&md_path).unwrap()).unwrap();
+        assert_eq!(output["info"]["format_version"], "1.4");
+        assert_eq!(output["detection_categories"]["1"], "animal");
+        assert_eq!(output["classification_categories"]["0"], "koolmees");
+        assert_eq!(output["classification_categories"]["1"], "merel");
+        let images = output["images"].as_array().unwrap();
+        assert_eq!(images[0]["file"], "sub/a.jpg");
+        let detection = &images[0]["detections"][0];
+        assert_eq!(detection["category"], "1");
+        assert!((detection["conf"].as_f64().unwrap() - 0.93).abs() < 1e-6);
+        assert_eq!(detection["classifications"][0][0], "1");
+        assert_eq!(images[1]["detections"][0]["bbox"][2], 1.0);
+        assert_eq!(images[1]["detections"][0]["classifications"][0][0], "0");
+        assert!((images[2]["detections"][0]["conf"].as_f64().unwrap() - 0.1).abs() < 1e-6);
+        assert!(images[2]["detections"][0].get("classifications").is_none());
+        assert_eq!(images[3]["failure"], FAILURE_NOT_CLASSIFIED);
+        assert!((images[4]["detections"][0]["conf"].as_f64().unwrap() - 0.4).abs() < 1e-6);
+        assert_eq!(images[5]["detections"].as_array().unwrap().len(), 0);
+        assert_eq!(
+            output["classification_categories"]
+                .as_object()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let mut reimported = vec![
+            row(root, "sub/a.jpg"),
+            row(root, "c.jpg"),
+            row(root, "e.jpg"),
+            row(root, "f.jpg"),
+        ];
+        let summary = import_megadetector_json(&mut reimported, root, &md_path, 0.2).unwrap();
+        assert_eq!((summary.animal, summary.empty), (2, 2));
+        let empty = reimported[1].classification.as_ref().unwrap();
+        assert!((empty.confidence - 0.9).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,448 @@
+//! Versioned per-frame export records, written as CSV or JSON Lines.
+//!
+//! [`export_csv`](crate::export_csv) is schema 1: four columns, and no
+//! label for frames without an animal. Schema 2 keeps the classification of
+//! every frame and adds where the decision came from, the model, the site
+//! and a visit id. Both formats name the schema version (a `schema_version`
+//! column in CSV, a header line in JSON Lines), so scripts can check it and
+//! ignore columns they do not know.
+
+use crate::{
+    Decision, DecisionSource, ImageInfo, LabelScore, canonical_label_for_match, capture_time,
+};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Version of the record layout produced by this module.
+pub const RECORD_SCHEMA_VERSION: u32 = 2;
+
+/// Column order of schema 2, also listed in the JSON Lines header.
+pub const RECORD_COLUMNS: [&str; 14] = [
+    "file",
+    "relative_path",
+    "capture_time",
+    "present",
+    "label_canonical",
+    "label_display",
+    "label_scientific",
+    "confidence",
+    "top_k",
+    "source",
+    "model_version",
+    "site",
+    "visit_id",
+    "error",
+];
+
+/// Frames of the same species at most this far apart share a visit id.
+pub const DEFAULT_VISIT_GAP: Duration = Duration::from_secs(120);
+
+/// File format for [`write_records`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RecordFormat {
+    /// Comma-separated values with a leading `schema_version` column.
+    #[default]
+    Csv,
+    /// One JSON object per line after a header object.
+    JsonLines,
+}
+
+impl RecordFormat {
+    /// File extension without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            RecordFormat::Csv => "csv",
+            RecordFormat::JsonLines => "jsonl",
+        }
+    }
+}
+
+/// Context that is the same for every record of an export.
+#[derive(Debug, Clone)]
+pub struct RecordOptions {
+    /// Scan folder; `relative_path` is relative to it.
+    pub root: PathBuf,
+    pub model_version: Option<String>,
+    pub site: Option<String>,
+    /// Largest gap between frames of one visit.
+    pub visit_gap: Duration,
+}
+
+impl Default for RecordOptions {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::new(),
+            model_version: None,
+            site: None,
+            visit_gap: DEFAULT_VISIT_GAP,
+        }
+    }
+}
+
+/// One frame in schema 2.
+///
+/// `label_display` starts out as the stored label and `label_scientific`
+/// empty; callers that know nicer names can fill them in before writing.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FrameRecord {
+    pub file: PathBuf,
+    /// Path below [`RecordOptions::root`] with `/` separators.
+    pub relative_path: String,
+    /// EXIF capture time, or the file time without EXIF, in RFC 3339; see
+    /// [`capture_time`](crate::capture_time).
+    pub capture_time: Option<String>,
+    pub present: bool,
+    pub label_canonical: Option<String>,
+    pub label_display: Option<String>,
+    pub label_scientific: Option<String>,
+    /// Model probability; `None` for manual labels, which have none.
+    pub confidence: Option<f32>,
+    pub top_k: Vec<LabelScore>,
+    /// `None` for frames that were never classified.
+    pub source: Option<DecisionSource>,
+    pub model_version: Option<String>,
+    pub site: Option<String>,
+    /// Numbered from 1 in order of the first frame; only frames with an
+    /// animal belong to a visit.
+    pub visit_id: Option<u32>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JsonLinesHeader {
+    schema_version: u32,
+    columns: [&'static str; 14],
+}
+
+/// Numbers visits: frames with the same label that follow each other within
+/// `gap` get the same id. Frames without a time or an animal label (`None`)
+/// get no id.
+fn visit_ids(
+    frames: &[(Option<DateTime<Local>>, Option<&str>)],
+    gap: Duration,
+) -> Vec<Option<u32>> {
+    let mut order: Vec<usize> = (0..frames.len())
+        .filter(|&idx| frames[idx].0.is_some() && frames[idx].1.is_some())
+        .collect();
+    order.sort_by_key(|&idx| frames[idx].0);
+
+    let gap = chrono::Duration::from_std(gap).unwrap_or(chrono::Duration::MAX);
+    let mut ids = vec![None; frames.len()];
+    let mut open: HashMap<&str, (u32, DateTime<Local>)> = HashMap::new();
+    let mut next_id = 0u32;
+    for idx in order {
+        let (Some(time), Some(label)) = frames[idx] else {
+            continue;
+        };
+        let id = match open.get(label) {
+            Some(&(id, last)) if time - last <= gap => id,
+            _ => {
+                next_id += 1;
+                next_id
+            }
+        };
+        open.insert(label, (id, time));
+        ids[idx] = Some(id);
+    }
+    ids
+}
+
+/// Builds a schema 2 record for every row, in the same order.
+///
+/// # Examples
+///
+/// ```no_run
+/// use feeder_core::{RecordFormat, RecordOptions, frame_records, scan_folder, write_records};
+///
+/// let rows = scan_folder("/data/camera")?;
+/// let options = RecordOptions {
+///     root: "/data/camera".into(),
+///     site: Some("tuin".into()),
+///     ..RecordOptions::default()
+/// };
+/// let records = frame_records(&rows, &options);
+/// write_records(&records, "/tmp/records.jsonl", RecordFormat::JsonLines)?;
+/// # Ok::<_, anyhow::Error>(())
+/// ```
+pub fn frame_records(rows: &[ImageInfo], options: &RecordOptions) -> Vec<FrameRecord> {
+    let mut records: Vec<FrameRecord> = rows
+        .iter()
+        .map(|info| {
+            let label = match info.classification.as_ref().map(|c| &c.decision) {
+                Some(Decision::Label(name)) => Some(name.trim()),
+                _ => None,
+            };
+            FrameRecord {
+                file: info.file.clone(),
+                relative_path: info
+                    .file
+                    .strip_prefix(&options.root)
+                    .unwrap_or(&info.file)
+                    .components()
+                    .map(|part| part.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                capture_time: None,
+                present: info.present,
+                label_canonical: label.map(canonical_label_for_match),
+                label_display: label.map(str::to_string),
+                label_scientific: None,
+                confidence: info
+                    .classification
+                    .as_ref()
+                    .filter(|c| !c.is_manual())
+                    .map(|c| c.confidence),
+                top_k: info.top_k.clone(),
+                source: info.classification.as_ref().map(|c| c.provenance.source),
+                model_version: options.model_version.clone(),
+                site: options.site.clone(),
+                visit_id: None,
+                error: info.error.clone(),
+            }
+        })
+        .collect();
+
+    let times: Vec<_> = rows.iter().map(|info| capture_time(&info.file)).collect();
+    let frames: Vec<_> = records
+        .iter()
+        .zip(&times)
+        .map(|(record, time)| {
+            let label = record.label_canonical.as_deref().filter(|_| record.present);
+            (*time, label)
+        })
+        .collect();
+    let visits = visit_ids(&frames, options.visit_gap);
+    for ((record, time), visit) in records.iter_mut().zip(times).zip(visits) {
+        record.capture_time = time.map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, false));
+        record.visit_id = visit;
+    }
+    records
+}
+
+/// Writes `records` at `path` in the chosen format.
+///
+/// CSV puts a `schema_version` column before [`RECORD_COLUMNS`], so the
+/// file stays a plain table for pandas, R and spreadsheets; JSON Lines
+/// starts with `{"schema_version":2,"columns":[...]}`. In CSV, `top_k` is
+/// written as `label:probability` pairs separated by `;`.
+///
+/// # Errors
+///
+/// Returns an error when the file cannot be written.
+pub fn write_records(
+    records: &[FrameRecord],
+    path: impl AsRef<Path>,
+    format: RecordFormat,
+) -> Result<()> {
+    let path = path.as_ref();
+    let file = File::create(path).with_context(|| format!("Kon {} niet openen", path.display()))?;
+    let mut out = BufWriter::new(file);
+    match format {
+        RecordFormat::Csv => {
+            write_csv(records, out)?;
+        }
+        RecordFormat::JsonLines => {
+            let header = JsonLinesHeader {
+                schema_version: RECORD_SCHEMA_VERSION,
+                columns: RECORD_COLUMNS,
+            };
+            serde_json::to_writer(&mut out, &header)?;
+            writeln!(out)?;
+            for record in records {
+                serde_json::to_writer(&mut out, record)?;
+                writeln!(out)?;
+            }
+            out.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn write_csv(records: &[FrameRecord], out: BufWriter<File>) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(std::iter::once("schema_version").chain(RECORD_COLUMNS))?;
+    let version = RECORD_SCHEMA_VERSION.to_string();
+    let text = |value: &Option<String>| value.clone().unwrap_or_default();
+    for record in records {
+        let top_k = record
+            .top_k
+            .iter()
+            .map(|score| format!("{}:{:.4}", score.label, score.probability))
+            .collect::<Vec<_>>()
+            .join(";");
+        writer.write_record([
+            version.clone(),
+            record.file.to_string_lossy().into_owned(),
+            record.relative_path.clone(),
+            text(&record.capture_time),
+            record.present.to_string(),
+            text(&record.label_canonical),
+            text(&record.label_display),
+            text(&record.label_scientific),
+            record
+                .confidence
+                .map(|confidence| confidence.to_string())
+                .unwrap_or_default(),
+            top_k,
+            record
+                .source
+                .map(|source| source.as_str().to_string())
+                .unwrap_or_default(),
+            text(&record.model_version),
+            text(&record.site),
+            record.visit_id.map(|id| id.to_string()).unwrap_or_default(),
+            text(&record.error),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Classification, Provenance, import_csv};
+    use chrono::TimeZone;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn visits_split_on_gap_and_label() {
+        let at = |minute: u32, second: u32| {
+            Some(
+                Local
+                    .with_ymd_and_hms(2026, 4, 12, 7, minute, second)
+                    .unwrap(),
+            )
+        };
+        let frames = vec![
+            (at(0, 0), Some("koolmees")),
+            (at(1, 30), Some("koolmees")),
+            (at(1, 40), Some("merel")),
+            (at(2, 0), None),
+            (at(9, 0), Some("koolmees")),
+            (None, Some("koolmees")),
+        ];
+        assert_eq!(
+            visit_ids(&frames, DEFAULT_VISIT_GAP),
+            [Some(1), Some(1), Some(2), None, Some(3), None]
+        );
+    }
+
+    #[test]
+    fn records_keep_every_classification_in_both_formats() -> Result<()> {
+        let dir = tempdir()?;
+        let root = dir.path().join("scan");
+        fs::create_dir_all(root.join("sub"))?;
+        let row = |name: &str, decision: Option<Decision>, source: DecisionSource| {
+            let file = root.join(name);
+            fs::write(&file, b"jpg").unwrap();
+            ImageInfo {
+                file,
+                present: matches!(decision, Some(Decision::Label(_))),
+                classification: decision.map(|decision| Classification {
+                    decision,
+                    confidence: 0.75,
+                    provenance: Provenance {
+                        source,
+                        ..Provenance::default()
+                    },
+                }),
+                embedding: None,
+                embedding_model: None,
+                detections: Vec::new(),
+                top_k: Vec::new(),
+                error: None,
+            }
+        };
+        let mut rows = vec![
+            row(
+                "a.jpg",
+                Some(Decision::Label("Koolmees".into())),
+                DecisionSource::Model,
+            ),
+            row("sub/b.jpg", Some(Decision::Unknown), DecisionSource::Model),
+            row(
+                "c.jpg",
+                Some(Decision::Label("Merel".into())),
+                DecisionSource::Manual,
+            ),
+            row("d.jpg", Some(Decision::Unknown), DecisionSource::Prefilter),
+            row("e.jpg", None, DecisionSource::Model),
+        ];
+        rows[0].top_k = vec![
+            LabelScore {
+                label: "Koolmees".into(),
+                probability: 0.75,
+            },
+            LabelScore {
+                label: "Pimpelmees".into(),
+                probability: 0.2,
+            },
+        ];
+        rows[1].present = false;
+        rows[4].error = Some("kapotte JPEG".into());
+        let options = RecordOptions {
+            root: root.clone(),
+            model_version: Some("1.2.0".into()),
+            site: Some("tuin".into()),
+            ..RecordOptions::default()
+        };
+        let records = frame_records(&rows, &options);
+        assert_eq!(records[1].relative_path, "sub/b.jpg");
+        assert_eq!(records[1].confidence, Some(0.75));
+        assert_eq!(records[2].label_display.as_deref(), Some("Merel"));
+        assert_eq!(records[2].confidence, None);
+        assert_eq!(
+            records.iter().map(|r| r.source).collect::<Vec<_>>(),
+            [
+                Some(DecisionSource::Model),
+                Some(DecisionSource::Model),
+                Some(DecisionSource::Manual),
+                Some(DecisionSource::Prefilter),
+                None,
+            ]
+        );
+        assert_eq!(records[0].visit_id, Some(1));
+        assert_eq!(records[1].visit_id, None);
+        assert!(records[0].capture_time.is_some());
+
+        let csv_path = dir.path().join("records.csv");
+        write_records(&records, &csv_path, RecordFormat::Csv)?;
+        let written = fs::read_to_string(&csv_path)?;
+        let mut lines = written.lines();
+        assert_eq!(
+            lines.next(),
+            Some(format!("schema_version,{}", RECORD_COLUMNS.join(",")).as_str())
+        );
+        let first = lines.next().unwrap();
+        assert!(first.starts_with("2,"));
+        assert!(first.contains(
+            ",koolmees,Koolmees,,0.75,Koolmees:0.7500;Pimpelmees:0.2000,model,1.2.0,tuin,1,"
+        ));
+        let labels = import_csv(&csv_path)?;
+        assert_eq!(labels.len(), 5);
+        assert_eq!(labels[2].species.as_deref(), Some("Merel"));
+
+        let jsonl_path = dir.path().join("records.jsonl");
+        write_records(&records, &jsonl_path, RecordFormat::JsonLines)?;
+        let written = fs::read_to_string(&jsonl_path)?;
+        let lines: Vec<serde_json::Value> = written
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0]["schema_version"], 2);
+        assert_eq!(lines[0]["columns"][0], "file");
+        assert_eq!(lines[3]["source"], "manual");
+        assert_eq!(lines[1]["top_k"][1]["label"], "Pimpelmees");
+        assert_eq!(lines[5]["error"], "kapotte JPEG");
+        Ok(())
+    }
+}
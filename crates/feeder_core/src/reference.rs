@@ -0,0 +1,284 @@
+//! Few-shot reference set and nearest-neighbour classification.
+//!
+//! Manually labelled frames are stored as embeddings so labels that the
+//! EfficientViT head does not know (for example a user-created "Eekhoorn") can
+//! still be predicted once a handful of examples exist.
+
+use crate::{Classification, Decision, Provenance, canonical_label_for_match, cosine_similarity};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Single labelled embedding in a [`ReferenceSet`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceExample {
+    /// Label assigned by the user.
+    pub label: String,
+    /// Pooled backbone features of the labelled frame.
+    pub embedding: Vec<f32>,
+    /// Frame the example was taken from, used to replace earlier examples.
+    #[serde(default)]
+    pub source: Option<PathBuf>,
+}
+
+/// Tuning knobs for [`ReferenceSet::classify`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KnnConfig {
+    /// Number of neighbours that vote on the label.
+    pub k: usize,
+    /// Minimum cosine similarity of the nearest neighbour before a label is
+    /// returned; anything below abstains with [`Decision::Unknown`].
+    pub min_similarity: f32,
+    /// Labels with fewer examples than this are never predicted.
+    pub min_examples: usize,
+}
+
+impl Default for KnnConfig {
+    fn default() -> Self {
+        Self {
+            k: 5,
+            min_similarity: 0.85,
+            min_examples: 3,
+        }
+    }
+}
+
+/// Per-user collection of labelled embeddings.
+///
+/// # Examples
+///
+/// ```
+/// use feeder_core::{Decision, KnnConfig, ReferenceSet};
+///
+/// let mut refs = ReferenceSet::default();
+/// for i in 0..3 {
+///     refs.add("Eekhoorn", vec![1.0, 0.1 * i as f32], None);
+/// }
+/// let result = refs.classify(&[1.0, 0.05], &KnnConfig::default());
+/// assert_eq!(result.decision, Decision::Label("Eekhoorn".into()));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceSet {
+    /// Model whose embeddings the examples hold, see
+    /// [`ClassifierConfig::model_id`](crate::ClassifierConfig::model_id).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Stored examples in the order they were added.
+    pub examples: Vec<ReferenceExample>,
+}
+
+impl ReferenceSet {
+    /// Creates an empty set for embeddings of the model `model`.
+    pub fn for_model(model: impl Into<String>) -> Self {
+        Self {
+            model: Some(model.into()),
+            examples: Vec::new(),
+        }
+    }
+
+    /// Loads a reference set from JSON; a missing file yields an empty set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the file exists but cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let data = fs::read_to_string(path)
+            .with_context(|| format!("Referenties niet te lezen: {}", path.display()))?;
+        serde_json::from_str(&data)
+            .with_context(|| format!("Referenties ongeldig: {}", path.display()))
+    }
+
+    /// Writes the reference set as JSON, creating parent folders when needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns any I/O or serialization error.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Adds an example, replacing an earlier one taken from the same frame.
+    pub fn add(&mut self, label: &str, embedding: Vec<f32>, source: Option<PathBuf>) {
+        if let Some(source) = &source {
+            self.examples
+                .retain(|example| example.source.as_ref() != Some(source));
+        }
+        self.examples.push(ReferenceExample {
+            label: label.trim().to_string(),
+            embedding,
+            source,
+        });
+    }
+
+    /// Removes the examples taken from `source`; returns true when any existed.
+    pub fn remove_source(&mut self, source: &Path) -> bool {
+        let before = self.examples.len();
+        self.examples
+            .retain(|example| example.source.as_deref() != Some(source));
+        self.examples.len() != before
+    }
+
+    /// Returns the number of stored examples.
+    pub fn len(&self) -> usize {
+        self.examples.len()
+    }
+
+    /// Returns true when no examples are stored.
+    pub fn is_empty(&self) -> bool {
+        self.examples.is_empty()
+    }
+
+    /// Returns how many examples exist per canonical label.
+    pub fn label_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for example in &self.examples {
+            let canonical = canonical_label_for_match(&example.label);
+            match counts.iter_mut().find(|(label, _)| *label == canonical) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((canonical, 1)),
+            }
+        }
+        counts
+    }
+
+    /// Classifies an embedding by weighted vote of its nearest neighbours.
+    ///
+    /// Only the `k` nearest neighbours at least [`KnnConfig::min_similarity`]
+    /// close vote; the confidence is the summed similarity of the winning
+    /// label's voters divided by the number of voters. When even the nearest
+    /// usable neighbour is too far away, the result abstains with
+    /// [`Decision::Unknown`] and reports that similarity as confidence.
+    /// Examples whose similarity is not finite (NaN features) are ignored.
+    pub fn classify(&self, embedding: &[f32], cfg: &KnnConfig) -> Classification {
+        let counts: HashMap<String, usize> = self.label_counts().into_iter().collect();
+        let mut neighbours: Vec<(f32, &ReferenceExample)> = self
+            .examples
+            .iter()
+            .filter(|example| {
+                counts
+                    .get(&canonical_label_for_match(&example.label))
+                    .is_some_and(|count| *count >= cfg.min_examples)
+            })
+            .map(|example| (cosine_similarity(embedding, &example.embedding), example))
+            .filter(|(sim, _)| sim.is_finite())
+            .collect();
+        neighbours.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let nearest = neighbours.first().map(|(sim, _)| *sim).unwrap_or(0.0);
+        let abstain = Classification {
+            decision: Decision::Unknown,
+            confidence: nearest.max(0.0),
+            provenance: Provenance::default(),
+        };
+        if nearest < cfg.min_similarity {
+            return abstain;
+        }
+
+        let voters: Vec<&(f32, &ReferenceExample)> = neighbours
+            .iter()
+            .take(cfg.k.max(1))
+            .filter(|(sim, _)| *sim >= cfg.min_similarity)
+            .collect();
+        let mut votes: Vec<(String, &str, f32)> = Vec::new();
+        for (sim, example) in &voters {
+            let canonical = canonical_label_for_match(&example.label);
+            match votes.iter_mut().find(|(label, _, _)| *label == canonical) {
+                Some((_, _, score)) => *score += sim.max(0.0),
+                None => votes.push((canonical, example.label.as_str(), sim.max(0.0))),
+            }
+        }
+        let Some((_, label, score)) = votes.into_iter().max_by(|a, b| a.2.total_cmp(&b.2)) else {
+            return abstain;
+        };
+        Classification {
+            decision: Decision::Label(label.to_string()),
+            confidence: (score / voters.len() as f32).min(1.0),
+            provenance: Provenance::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reference_set_predicts_custom_label_and_abstains_on_unknown() {
+        let mut refs = ReferenceSet::default();
+        for i in 0..3 {
+            refs.add("Eekhoorn", vec![1.0, 0.05 * i as f32, 0.0], None);
+            refs.add("Ekster", vec![0.0, 1.0, 0.05 * i as f32], None);
+        }
+        let cfg = KnnConfig::default();
+
+        let squirrel = refs.classify(&[0.95, 0.05, 0.0], &cfg);
+        assert_eq!(squirrel.decision, Decision::Label("Eekhoorn".into()));
+        assert!(squirrel.confidence > 0.9);
+
+        let unknown = refs.classify(&[0.0, 0.0, 1.0], &cfg);
+        assert_eq!(unknown.decision, Decision::Unknown);
+        assert!(unknown.confidence < cfg.min_similarity);
+    }
+
+    #[test]
+    fn reference_set_ignores_labels_with_too_few_examples() {
+        let mut refs = ReferenceSet::default();
+        refs.add("Ekster", vec![1.0, 0.0], Some(PathBuf::from("a.jpg")));
+        refs.add("Ekster", vec![1.0, 0.1], Some(PathBuf::from("b.jpg")));
+        refs.add("Ekster", vec![1.0, 0.2], Some(PathBuf::from("b.jpg")));
+        assert_eq!(refs.len(), 2);
+
+        let result = refs.classify(&[1.0, 0.0], &KnnConfig::default());
+        assert_eq!(result.decision, Decision::Unknown);
+    }
+
+    #[test]
+    fn reference_set_ignores_nan_similarities() {
+        let mut refs = ReferenceSet::default();
+        for i in 0..3 {
+            refs.add("Eekhoorn", vec![1.0, 0.05 * i as f32], None);
+        }
+        refs.add("Eekhoorn", vec![f32::NAN, 1.0], None);
+        let result = refs.classify(&[1.0, 0.0], &KnnConfig::default());
+        assert_eq!(result.decision, Decision::Label("Eekhoorn".into()));
+
+        let unknown = refs.classify(&[f32::NAN, 0.0], &KnnConfig::default());
+        assert_eq!(unknown.decision, Decision::Unknown);
+    }
+
+    #[test]
+    fn reference_set_removes_examples_by_source() {
+        let mut refs = ReferenceSet::default();
+        refs.add("Ekster", vec![1.0, 0.0], Some(PathBuf::from("a.jpg")));
+        refs.add("Ekster", vec![1.0, 0.1], Some(PathBuf::from("b.jpg")));
+        assert!(refs.remove_source(Path::new("a.jpg")));
+        assert!(!refs.remove_source(Path::new("a.jpg")));
+        assert_eq!(refs.len(), 1);
+    }
+
+    #[test]
+    fn reference_set_roundtrips_through_json() -> Result<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("nested").join("references.json");
+        assert!(ReferenceSet::load(&path)?.is_empty());
+
+        let mut refs = ReferenceSet::default();
+        refs.add("Eekhoorn", vec![0.5, 0.5], Some(PathBuf::from("x.jpg")));
+        refs.save(&path)?;
+        assert_eq!(ReferenceSet::load(&path)?, refs);
+        assert_eq!(refs.label_counts(), vec![("eekhoorn".to_string(), 1)]);
+        Ok(())
+    }
+}
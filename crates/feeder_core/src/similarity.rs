@@ -0,0 +1,129 @@
+//! Visual similarity helpers built on the classifier embeddings.
+
+use crate::ImageInfo;
+
+/// Returns the cosine similarity between two feature vectors.
+///
+/// Vectors of different length or with zero magnitude yield `0.0`.
+///
+/// # Examples
+///
+/// ```
+/// let sim = feeder_core::cosine_similarity(&[1.0, 0.0], &[1.0, 0.0]);
+/// assert!((sim - 1.0).abs() < 1e-6);
+/// ```
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let mut dot = 0.0f32;
+    let mut norm_a = 0.0f32;
+    let mut norm_b = 0.0f32;
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a <= f32::EPSILON || norm_b <= f32::EPSILON {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Ranks every row with an embedding by similarity to the `query` rows.
+///
+/// The query embeddings are normalized and averaged into a single prototype,
+/// so selecting several frames of the same visitor sharpens the search. The
+/// result holds `(row index, similarity)` pairs sorted from most to least
+/// similar; rows without an embedding are left out.
+///
+/// # Examples
+///
+/// ```
+/// # use feeder_core::{ImageInfo, rank_by_similarity};
+/// # use std::path::PathBuf;
+/// let row = |v: Vec<f32>| ImageInfo {
+///     file: PathBuf::from("frame.jpg"),
+///     present: true,
+///     classification: None,
+///     embedding: Some(v),
+///     embedding_model: None,
+///     detections: Vec::new(),
+///     top_k: Vec::new(),
+///     error: None,
+/// };
+/// let rows = vec![row(vec![1.0, 0.0]), row(vec![0.0, 1.0]), row(vec![0.9, 0.1])];
+/// let ranked = rank_by_similarity(&rows, &[0]);
+/// assert_eq!(ranked.iter().map(|(idx, _)| *idx).collect::<Vec<_>>(), vec![0, 2, 1]);
+/// ```
+pub fn rank_by_similarity(rows: &[ImageInfo], query: &[usize]) -> Vec<(usize, f32)> {
+    let Some(prototype) = prototype_for(rows, query) else {
+        return Vec::new();
+    };
+    let mut ranked: Vec<(usize, f32)> = rows
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, info)| {
+            let embedding = info.embedding.as_ref()?;
+            Some((idx, cosine_similarity(&prototype, embedding)))
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    ranked
+}
+
+/// Averages the normalized embeddings of the query rows.
+fn prototype_for(rows: &[ImageInfo], query: &[usize]) -> Option<Vec<f32>> {
+    let mut sum: Option<Vec<f32>> = None;
+    for &idx in query {
+        let Some(embedding) = rows.get(idx).and_then(|info| info.embedding.as_ref()) else {
+            continue;
+        };
+        let norm = embedding.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm <= f32::EPSILON {
+            continue;
+        }
+        let acc = sum.get_or_insert_with(|| vec![0.0; embedding.len()]);
+        if acc.len() != embedding.len() {
+            continue;
+        }
+        for (slot, value) in acc.iter_mut().zip(embedding) {
+            *slot += value / norm;
+        }
+    }
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn rank_by_similarity_orders_rows_and_skips_missing_embeddings() {
+        let row = |name: &str, embedding: Option<Vec<f32>>| ImageInfo {
+            file: PathBuf::from(name),
+            present: true,
+            classification: None,
+            embedding,
+            embedding_model: None,
+            detections: Vec::new(),
+            top_k: Vec::new(),
+            error: None,
+        };
+        let rows = vec![
+            row("query.jpg", Some(vec![1.0, 0.0, 0.0])),
+            row("far.jpg", Some(vec![0.0, 0.0, 1.0])),
+            row("missing.jpg", None),
+            row("close.jpg", Some(vec![2.0, 0.2, 0.0])),
+        ];
+
+        let ranked = rank_by_similarity(&rows, &[0]);
+        let order: Vec<usize> = ranked.iter().map(|(idx, _)| *idx).collect();
+        assert_eq!(order, vec![0, 3, 1]);
+        assert!((ranked[0].1 - 1.0).abs() < 1e-6);
+        assert!(ranked[2].1.abs() < 1e-6);
+
+        assert!(rank_by_similarity(&rows, &[2]).is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+#!/usr/bin/env bash
+# Writes checksums and signatures of release files into manifest.json and
+# signs the manifest itself.
+#
+# Usage: scripts/release_manifest.sh [--manifest FILE] [--key PEM]
+#          [--model ZIP] [--pack ID=ZIP]... [--windows EXE] [--linux ARCH=APPIMAGE]...
+#
+# Every file gets its sha256 (and size_bytes for models and packs). With
+# --key, each file also gets a `signature` and the manifest is signed into
+# `<manifest>.sig`; the key is the ed25519 private key whose public half is
+# built into the app as FEEDIE_UPDATE_PUBLIC_KEY. The app refuses downloads
+# without a sha256 and manifests or files without a valid signature, so run
+# this for every release before publishing the manifest.
+set -euo pipefail
+
+ROOT="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
+MANIFEST="${ROOT}/manifest.json"
+KEY=""
+MODEL=""
+WINDOWS=""
+PACKS=()
+LINUX=()
+
+while [[ $# -gt 0 ]]; do
+  case "$1" in
+    --manifest) MANIFEST="$2"; shift 2 ;;
+    --key) KEY="$2"; shift 2 ;;
+    --model) MODEL="$2"; shift 2 ;;
+    --pack) PACKS+=("$2"); shift 2 ;;
+    --windows) WINDOWS="$2"; shift 2 ;;
+    --linux) LINUX+=("$2"); shift 2 ;;
+    *) echo "Unknown argument: $1" >&2; exit 1 ;;
+  esac
+done
+
+if [[ -n "${KEY}" && ! -f "${KEY}" ]]; then
+  echo "Signing key not found: ${KEY}" >&2
+  exit 1
+fi
+
+# Prints the hex ed25519 signature over the file $1.
+sign() {
+  openssl pkeyutl -sign -inkey "${KEY}" -rawin -in "$1" | od -An -v -tx1 | tr -d ' \n'
+}
+
+# Sets SHA, SIZE and SIG (empty without --key) for the release file $1.
+measure() {
+  if [[ ! -f "$1" ]]; then
+    echo "File not found: $1" >&2
+    exit 1
+  fi
+  SHA="$(sha256sum "$1" | cut -d' ' -f1)"
+  SIZE="$(wc -c < "$1" | tr -d ' ')"
+  SIG=""
+  if [[ -n "${KEY}" ]]; then
+    SIG="$(sign "$1")"
+  fi
+}
+
+# Applies the jq filter in the last argument to the entry selected by $1.
+update() {
+  local path="$1"
+  shift
+  local temp="${MANIFEST}.tmp"
+  jq --arg sha "${SHA}" --argjson size "${SIZE}" --arg sig "${SIG}" "${@:1:$#-1}" \
+    "${path} |= (${!#} | if \$sig == \"\" then . else .signature = \$sig end)" \
+    "${MANIFEST}" > "${temp}"
+  mv "${temp}" "${MANIFEST}"
+}
+
+if [[ -n "${MODEL}" ]]; then
+  measure "${MODEL}"
+  update '.model' '.sha256 = $sha | .size_bytes = $size'
+  echo "model: ${SHA} (${SIZE} bytes)"
+fi
+
+for pack in "${PACKS[@]}"; do
+  id="${pack%%=*}"
+  if ! jq -e --arg id "${id}" '.packs // [] | any(.id == $id)' "${MANIFEST}" >/dev/null; then
+    echo "Pack ${id} is not listed in ${MANIFEST}" >&2
+    exit 1
+  fi
+  measure "${pack#*=}"
+  update '(.packs[] | select(.id == $id))' --arg id "${id}" '.sha256 = $sha | .size_bytes = $size'
+  echo "pack ${id}: ${SHA} (${SIZE} bytes)"
+done
+
+if [[ -n "${WINDOWS}" ]]; then
+  if ! jq -e '.app.windows' "${MANIFEST}" >/dev/null; then
+    echo "No Windows installer is listed in ${MANIFEST}" >&2
+    exit 1
+  fi
+  measure "${WINDOWS}"
+  update '.app.windows' '.sha256 = $sha'
+  echo "windows: ${SHA}"
+fi
+
+for build in "${LINUX[@]}"; do
+  arch="${build%%=*}"
+  if ! jq -e --arg arch "${arch}" '.app.linux // {} | has($arch)' "${MANIFEST}" >/dev/null; then
+    echo "AppImage ${arch} is not listed in ${MANIFEST}" >&2
+    exit 1
+  fi
+  measure "${build#*=}"
+  update '.app.linux[$arch]' --arg arch "${arch}" '.sha256 = $sha'
+  echo "linux ${arch}: ${SHA}"
+done
+
+if [[ -n "${KEY}" ]]; then
+  sign "${MANIFEST}" > "${MANIFEST}.sig"
+  echo "signed: ${MANIFEST}.sig"
+else
+  echo "No --key given: files and ${MANIFEST} are not signed." >&2
+fi